@@ -1,9 +1,9 @@
 use derive_more::From;
 use galvan_ast_macro::PrintAst;
 
-use crate::{Expression, Ident, PrintAst};
+use crate::{Expression, Ident, PrintAst, Span};
 
-use super::ExpressionKind;
+use super::{ExpressionKind, Visibility};
 
 pub trait InfixOperator {
     fn symbol(&self) -> &str;
@@ -139,6 +139,7 @@ impl InfixOperator for BitwiseOperator {
 pub enum CollectionOperator {
     Concat,
     Remove,
+    Repeat,
     Contains,
 }
 
@@ -147,6 +148,7 @@ impl InfixOperator for CollectionOperator {
         match self {
             CollectionOperator::Concat => "++",
             CollectionOperator::Remove => "--",
+            CollectionOperator::Repeat => "**",
             CollectionOperator::Contains => "in",
         }
     }
@@ -194,9 +196,35 @@ impl InfixOperator for MemberOperator {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct CustomInfix(String);
 
+impl CustomInfix {
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self(symbol.into())
+    }
+}
+
+/// Binds a custom operator symbol to a function with two parameters, e.g.
+/// `infix <+> = combine, precedence: 6, associativity: right`
+#[derive(Debug, PartialEq, Eq)]
+pub struct InfixDecl {
+    pub visibility: Visibility,
+    pub operator: CustomInfix,
+    pub function: Ident,
+    /// Custom operators with a higher precedence bind tighter
+    pub precedence: u8,
+    pub associativity: Associativity,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Associativity {
+    #[default]
+    Left,
+    Right,
+}
+
 impl InfixOperator for CustomInfix {
     fn symbol(&self) -> &str {
         &self.0
@@ -211,6 +239,13 @@ pub enum RangeOperator {
     Interval,
 }
 
+impl PrintAst for RangeOperator {
+    fn print_ast(&self, indent: usize) -> String {
+        let indent_str = " ".repeat(indent);
+        format!("{indent_str}{}", self.symbol())
+    }
+}
+
 impl InfixOperator for RangeOperator {
     fn symbol(&self) -> &str {
         match self {
@@ -1,6 +1,8 @@
+use std::ops::Deref;
+
 use galvan_ast_macro::PrintAst;
 
-use crate::{Block, Expression, Ident, PrintAst, Span, TypeIdent};
+use crate::{Block, Expression, Ident, Literal, PrintAst, RangeOperator, Span, TypeIdent};
 
 #[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
 pub struct MatchExpression {
@@ -11,6 +13,8 @@ pub struct MatchExpression {
 #[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
 pub struct MatchArm {
     pub pattern: MatchPattern,
+    /// `if` condition that has to hold for the arm to be taken
+    pub guard: Option<Expression>,
     pub body: Block,
 }
 
@@ -18,6 +22,24 @@ pub struct MatchArm {
 pub enum MatchPattern {
     Wildcard(MatchWildcardPattern),
     EnumVariant(MatchEnumPattern),
+    Literal(MatchLiteralPattern),
+    Range(MatchRangePattern),
+    Tuple(MatchTuplePattern),
+    /// Patterns separated by `|`, the arm is taken if any of them matches
+    Alternatives(MatchAlternativesPattern),
+}
+
+impl MatchPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            MatchPattern::Wildcard(wildcard) => wildcard.span,
+            MatchPattern::EnumVariant(variant) => variant.span,
+            MatchPattern::Literal(literal) => literal.span,
+            MatchPattern::Range(range) => range.span,
+            MatchPattern::Tuple(tuple) => tuple.span,
+            MatchPattern::Alternatives(alternatives) => alternatives.span,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
@@ -32,6 +54,33 @@ pub struct MatchEnumPattern {
     pub span: Span,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct MatchLiteralPattern {
+    pub literal: Literal,
+    pub span: Span,
+}
+
+/// `start..<end` or `start..=end`
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct MatchRangePattern {
+    pub start: Literal,
+    pub operator: RangeOperator,
+    pub end: Literal,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct MatchTuplePattern {
+    pub elements: Vec<MatchBindingPattern>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct MatchAlternativesPattern {
+    pub alternatives: Vec<MatchPattern>,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
 pub enum MatchPatternArg {
     Binding(MatchBindingPattern),
@@ -49,4 +98,12 @@ pub struct MatchNamedPatternArg {
 pub enum MatchBindingPattern {
     Ident(Ident),
     Wildcard(MatchWildcardPattern),
+    /// A nested pattern, e.g. `Some(Circle(radius))` or `(0, y)`
+    Pattern(Box<MatchPattern>),
+}
+
+impl PrintAst for Box<MatchPattern> {
+    fn print_ast(&self, indent: usize) -> String {
+        self.deref().print_ast(indent)
+    }
 }
@@ -11,8 +11,10 @@ mod modifier;
 mod postfix;
 mod statement;
 mod toplevel;
+mod r#trait;
 mod r#type;
 mod type_item;
+mod unary;
 
 pub use assignment::*;
 pub use closure::*;
@@ -25,7 +27,9 @@ pub use match_expression::*;
 pub use modifier::*;
 pub use postfix::*;
 pub use r#fn::*;
+pub use r#trait::*;
 pub use r#type::*;
 pub use statement::*;
 pub use toplevel::*;
 pub use type_item::*;
+pub use unary::*;
@@ -7,7 +7,7 @@ use crate::{AstNode, DeclModifier, PrintAst, Span};
 
 #[type_union]
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
-pub type PostfixExpression = YeetExpression + AccessExpression;
+pub type PostfixExpression = YeetExpression + AccessExpression + SliceExpression;
 
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
 pub struct AccessExpression {
@@ -16,6 +16,15 @@ pub struct AccessExpression {
     pub span: Span,
 }
 
+/// `base[start:end]`, either bound may be omitted
+#[derive(Clone, Debug, PartialEq, Eq, AstNode)]
+pub struct SliceExpression {
+    pub base: Expression,
+    pub start: Option<Expression>,
+    pub end: Option<Expression>,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, From, PartialEq, Eq, AstNode)]
 pub struct YeetExpression {
     pub inner: Expression,
@@ -24,13 +24,44 @@ pub type Statement = Assignment + Declaration + Expression + Return + Throw + Br
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
 pub struct Declaration {
     pub decl_modifier: DeclModifier,
-    pub identifier: Ident,
+    pub pattern: DeclarationPattern,
     pub type_annotation: Option<TypeElement>,
     pub assignment_modifier: Option<DeclModifier>,
     pub assignment: Option<Expression>,
     pub span: Span,
 }
 
+/// The variables a declaration binds, e.g. `x`, `(a, b)` or `Point(x:, y: b)`
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub enum DeclarationPattern {
+    Ident(Ident),
+    Wildcard(MatchWildcardPattern),
+    Tuple(TupleDestructuring),
+    Struct(StructDestructuring),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct TupleDestructuring {
+    pub elements: Vec<DeclarationPattern>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct StructDestructuring {
+    pub ty: TypeIdent,
+    pub fields: Vec<FieldDestructuring>,
+    pub span: Span,
+}
+
+/// `field: pattern`, or `field:` to bind the field to a variable of the same
+/// name
+#[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
+pub struct FieldDestructuring {
+    pub field: Ident,
+    pub binding: Option<DeclarationPattern>,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
 pub struct Return {
     pub expression: Expression,
@@ -44,19 +75,35 @@ pub struct Throw {
     pub span: Span,
 }
 
+/// `break`, `break@label` or `break value`. A value can only be given when
+/// leaving a `loop`.
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
 pub struct Break {
+    pub label: Option<Ident>,
+    pub value: Option<Expression>,
     pub span: Span,
 }
 
+/// `continue` or `continue@label`
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
 pub struct Continue {
+    pub label: Option<Ident>,
+    pub span: Span,
+}
+
+/// A loop with a label, e.g. `outer@ for rows |row| { ... }`
+#[derive(Clone, Debug, PartialEq, Eq, AstNode)]
+pub struct LabeledExpression {
+    pub label: Ident,
+    pub inner: Expression,
     pub span: Span,
 }
 
 type Infix = Box<InfixExpression>;
+type Labeled = Box<LabeledExpression>;
 type Match = Box<MatchExpression>;
 type Postfix = Box<PostfixExpression>;
+type Unary = Box<UnaryExpression>;
 type Modified = Box<ModifiedExpression>;
 
 #[derive(Clone, Debug, PartialEq, Eq, PrintAst)]
@@ -77,6 +124,7 @@ pub type ExpressionKind = ElseExpression
     + Match
     + FunctionCall
     + Infix
+    + Unary
     + Postfix
     + Modified
     + CollectionLiteral
@@ -86,7 +134,8 @@ pub type ExpressionKind = ElseExpression
     + Literal
     + Ident
     + Closure
-    + Group;
+    + Group
+    + Labeled;
 
 #[derive(Clone, Debug, PartialEq, Eq, AstNode)]
 pub struct EnumAccess {
@@ -1,7 +1,7 @@
 use derive_more::From;
 use galvan_ast_macro::AstNode;
 
-use super::{Body, FnDecl, Ident, Param, ParamList, StringLiteral, TypeDecl};
+use super::{Body, FnDecl, Ident, InfixDecl, Param, ParamList, StringLiteral, TraitDecl, TypeDecl};
 use crate::{AstNode, PrintAst, Span};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -61,6 +61,8 @@ pub enum RootItem {
     Use(UseDecl),
     Fn(FnDecl),
     Type(TypeDecl),
+    Trait(TraitDecl),
+    Infix(InfixDecl),
     Test(TestDecl),
     Cmd(CmdDecl),
     // CustomTask(TaskDecl),
@@ -80,6 +82,14 @@ impl private::Seal for TypeDecl {}
 
 impl RootItemMarker for TypeDecl {}
 
+impl private::Seal for TraitDecl {}
+
+impl RootItemMarker for TraitDecl {}
+
+impl private::Seal for InfixDecl {}
+
+impl RootItemMarker for InfixDecl {}
+
 impl private::Seal for MainDecl {}
 
 impl RootItemMarker for MainDecl {}
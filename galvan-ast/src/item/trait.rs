@@ -0,0 +1,37 @@
+use galvan_ast_macro::AstNode;
+
+use super::{FnSignature, TypeIdent, Visibility};
+use crate::{AstNode, PrintAst, Span};
+
+/// A trait listing the member functions that conforming types must provide,
+/// e.g.:
+///
+/// ```galvan
+/// trait Shape {
+///     fn area(self: Self) -> Float
+/// }
+/// ```
+///
+/// `Self` stands for the conforming type in the required signatures.
+#[derive(Debug, PartialEq, Eq, AstNode)]
+pub struct TraitDecl {
+    pub visibility: Visibility,
+    pub ident: TypeIdent,
+    pub members: Vec<FnSignature>,
+    pub span: Span,
+}
+
+impl TraitDecl {
+    /// Finds the required member function matching a call by name and
+    /// argument labels
+    pub fn member(&self, name: &str, labels: &[&str]) -> Option<&FnSignature> {
+        self.members.iter().find(|member| {
+            member.identifier.as_str() == name
+                && member
+                    .overload_labels()
+                    .iter()
+                    .map(|label| label.as_str())
+                    .eq(labels.iter().copied())
+        })
+    }
+}
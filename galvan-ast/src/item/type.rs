@@ -25,6 +25,38 @@ impl TypeDecl {
         }
     }
 
+    pub fn visibility(&self) -> &Visibility {
+        match self {
+            TypeDecl::Tuple(t) => &t.visibility,
+            TypeDecl::Struct(s) => &s.visibility,
+            TypeDecl::Alias(a) => &a.visibility,
+            TypeDecl::Empty(e) => &e.visibility,
+            TypeDecl::Enum(e) => &e.visibility,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            TypeDecl::Tuple(t) => t.span,
+            TypeDecl::Struct(s) => s.span,
+            TypeDecl::Alias(a) => a.span,
+            TypeDecl::Empty(e) => e.span,
+            TypeDecl::Enum(e) => e.span,
+        }
+    }
+
+    /// The traits this type declares conformance to. Aliases cannot declare
+    /// conformances.
+    pub fn conformances(&self) -> &[TypeIdent] {
+        match self {
+            TypeDecl::Tuple(t) => &t.conformances,
+            TypeDecl::Struct(s) => &s.conformances,
+            TypeDecl::Enum(e) => &e.conformances,
+            TypeDecl::Empty(e) => &e.conformances,
+            TypeDecl::Alias(_) => &[],
+        }
+    }
+
     pub fn collect_generics(&self) -> std::collections::HashSet<super::Ident> {
         let mut generics = std::collections::HashSet::new();
         match self {
@@ -54,12 +86,22 @@ impl TypeDecl {
         }
         generics
     }
+
+    /// The generic type parameters of this type in the order they are bound
+    /// by the arguments of a parametric type, e.g. `Container<Int>`
+    pub fn generic_params(&self) -> Vec<Ident> {
+        let mut generics = self.collect_generics().into_iter().collect::<Vec<_>>();
+        generics.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        generics
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, AstNode)]
 pub struct TupleTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
+    /// Traits this type declares conformance to, e.g. `type Circle: Shape`
+    pub conformances: Vec<TypeIdent>,
     pub members: Vec<TupleTypeMember>,
     pub span: Span,
 }
@@ -75,6 +117,8 @@ pub struct TupleTypeMember {
 pub struct StructTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
+    /// Traits this type declares conformance to, e.g. `type Circle: Shape`
+    pub conformances: Vec<TypeIdent>,
     pub members: Vec<StructTypeMember>,
     pub span: Span,
 }
@@ -101,6 +145,8 @@ pub struct AliasTypeDecl {
 pub struct EnumTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
+    /// Traits this type declares conformance to, e.g. `type Circle: Shape`
+    pub conformances: Vec<TypeIdent>,
     pub members: Vec<EnumTypeMember>,
     pub span: Span,
 }
@@ -124,5 +170,6 @@ pub struct EnumVariantField {
 pub struct EmptyTypeDecl {
     pub visibility: Visibility,
     pub ident: TypeIdent,
+    pub conformances: Vec<TypeIdent>,
     pub span: Span,
 }
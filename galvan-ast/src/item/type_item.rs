@@ -16,6 +16,7 @@ type Plain = BasicTypeItem;
 type Generic = GenericTypeItem;
 type Parametric = ParametricTypeItem;
 type Closure = Box<ClosureTypeItem>;
+type Union = Box<UnionTypeItem>;
 type Void = VoidTypeItem;
 type Infer = InferTypeItem;
 type Never = NeverTypeItem;
@@ -33,6 +34,7 @@ pub type TypeElement = Array
     + Generic
     + Parametric
     + Closure
+    + Union
     + Infer
     + Void
     + Never;
@@ -126,6 +128,11 @@ impl TypeElement {
                     max_depth,
                 );
             }
+            TypeElement::Union(union) => {
+                for member in &union.members {
+                    member.collect_generics_recursive_with_depth(generics, depth + 1, max_depth);
+                }
+            }
             // No generics in these cases
             TypeElement::Plain(_)
             | TypeElement::Void(_)
@@ -206,6 +213,13 @@ pub struct ClosureTypeItem {
     pub span: Span,
 }
 
+/// An anonymous union of types, e.g. `Int | String`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, AstNode)]
+pub struct UnionTypeItem {
+    pub members: Vec<TypeElement>,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, AstNode)]
 pub struct ParametricTypeItem {
     pub base_type: TypeIdent,
@@ -273,6 +287,15 @@ impl fmt::Display for TypeElement {
                 write!(f, "| ")?;
                 write!(f, "{}", clos.return_ty)
             }
+            TypeElement::Union(union) => {
+                for (i, member) in union.members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{}", member)?;
+                }
+                Ok(())
+            }
             TypeElement::Void(_) => write!(f, "Void"),
             TypeElement::Infer(_) => write!(f, "_"),
             TypeElement::Never(_) => write!(f, "!"),
@@ -0,0 +1,39 @@
+use galvan_ast_macro::AstNode;
+
+use super::Expression;
+use crate::{AstNode, PrintAst, Span};
+
+/// A prefix operator applied to a single operand, e.g. `not done` or `-x`
+#[derive(Clone, Debug, PartialEq, Eq, AstNode)]
+pub struct UnaryExpression {
+    pub operator: UnaryOperator,
+    pub operand: Expression,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOperator {
+    /// `not`, `!`
+    Not,
+    /// `~`
+    BitwiseNot,
+    /// `-`
+    Negate,
+}
+
+impl UnaryOperator {
+    pub fn symbol(&self) -> &str {
+        match self {
+            UnaryOperator::Not => "not",
+            UnaryOperator::BitwiseNot => "~",
+            UnaryOperator::Negate => "-",
+        }
+    }
+}
+
+impl PrintAst for UnaryOperator {
+    fn print_ast(&self, indent: usize) -> String {
+        let indent_str = " ".repeat(indent);
+        format!("{indent_str}{}", self.symbol())
+    }
+}
@@ -5,6 +5,7 @@ use std::{cell::RefCell, ops::Deref};
 
 use galvan_files::Source;
 use itertools::Itertools;
+use thiserror::Error;
 
 mod item;
 
@@ -14,21 +15,38 @@ pub use item::*;
 pub struct Ast {
     pub toplevel: Vec<RootItem>,
     pub source: Source,
+    /// Syntax errors in the source. The toplevel items they occur in are
+    /// not part of the AST.
+    pub syntax_errors: Vec<SyntaxError>,
 }
 
 impl Ast {
-    pub fn with_source(self, source: Source) -> Ast {
+    /// Sets the source of an AST that was parsed without one
+    pub fn with_source(self, source: Source) -> Result<Ast, SourceAlreadySet> {
         if self.source != Source::Missing {
-            panic!("Attempting to set a source to an AST that already had a source!");
+            return Err(SourceAlreadySet(self.source));
         }
 
-        Ast {
-            toplevel: self.toplevel,
-            source,
-        }
+        Ok(Ast { source, ..self })
     }
 }
 
+/// Error when setting the source of an AST that already has one
+#[derive(Debug, Error)]
+#[error("Attempting to set a source to an AST that already had a source")]
+pub struct SourceAlreadySet(pub Source);
+
+/// Code that could not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub source: Source,
+    pub span: Span,
+    /// The unexpected code, or `None` if code is missing
+    pub found: Option<String>,
+    /// Kinds of syntax nodes that would have been valid instead
+    pub expected: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct ToplevelItem<R: RootItemMarker> {
     pub item: R,
@@ -51,10 +69,13 @@ where
 pub struct SegmentedAsts {
     pub uses: Vec<ToplevelItem<UseDecl>>,
     pub types: Vec<ToplevelItem<TypeDecl>>,
+    pub traits: Vec<ToplevelItem<TraitDecl>>,
+    pub infixes: Vec<ToplevelItem<InfixDecl>>,
     pub functions: Vec<ToplevelItem<FnDecl>>,
     pub tests: Vec<ToplevelItem<TestDecl>>,
     pub main: Option<ToplevelItem<MainDecl>>,
     pub cmds: Vec<ToplevelItem<CmdDecl>>,
+    pub syntax_errors: Vec<SyntaxError>,
     // pub other: Vec<ToplevelItem<CustomTaskDecl>>
 }
 
@@ -2,8 +2,11 @@ use crate::{FileError, GalvanFileExtension, Source};
 use std::path::Path;
 use walkdir::WalkDir;
 
+/// Reads the Galvan files below `path`, which is the source root that module
+/// paths are relative to
 pub fn read_sources(path: impl AsRef<Path>, filter: Vec<String>) -> Result<Vec<Source>, FileError> {
-    WalkDir::new(path)
+    let root = path.as_ref();
+    WalkDir::new(root)
         .into_iter()
         .filter_map(|e| e.ok())
         .map(|e| e.into_path())
@@ -12,6 +15,6 @@ pub fn read_sources(path: impl AsRef<Path>, filter: Vec<String>) -> Result<Vec<S
             filter.is_empty()
                 || filter.contains(&p.file_name().unwrap().to_str().unwrap().to_string())
         })
-        .map(Source::read)
+        .map(|p| Source::read_in(root, p))
         .collect()
 }
@@ -1,4 +1,6 @@
+mod module;
 mod source;
+pub use module::ModulePath;
 pub use source::*;
 
 #[cfg(feature = "exec")]
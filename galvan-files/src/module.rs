@@ -0,0 +1,92 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::{FileError, GalvanFileExtension};
+
+/// Files with these names at the source root form the root module of a crate
+const ROOT_FILE_NAMES: [&str; 2] = ["main", "lib"];
+
+/// The path of a module within its crate.
+///
+/// Every Galvan file is a module named after the file, nested in the modules
+/// named after the directories that contain it: `src/shop/orders.galvan` is
+/// `crate::shop::orders`. `main.galvan` and `lib.galvan` at the source root
+/// form the crate root.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModulePath(Vec<Arc<str>>);
+
+impl ModulePath {
+    pub const fn root() -> Self {
+        Self(Vec::new())
+    }
+
+    /// The module formed by `path`, a Galvan file below the source root
+    pub fn of_file(root: impl AsRef<Path>, path: impl AsRef<Path>) -> Result<Self, FileError> {
+        let path = path.as_ref();
+        if !path.has_galvan_extension() {
+            Err(FileError::missing_extension(path))?
+        }
+        let relative = path.strip_prefix(root).unwrap_or(path).with_extension("");
+
+        let mut segments = relative
+            .iter()
+            .map(|segment| {
+                let segment = segment
+                    .to_str()
+                    .ok_or_else(|| FileError::utf8(segment.to_string_lossy()))?;
+                if segment.is_empty()
+                    || !segment.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+                {
+                    Err(FileError::naming(segment))?
+                }
+                Ok(Arc::from(segment))
+            })
+            .collect::<Result<Vec<Arc<str>>, FileError>>()?;
+        if let [name] = segments.as_slice() {
+            if ROOT_FILE_NAMES.contains(&name.as_ref()) {
+                segments.clear();
+            }
+        }
+        Ok(Self(segments))
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|segment| segment.as_ref())
+    }
+
+    /// The last segment, or `None` for the crate root
+    pub fn name(&self) -> Option<&str> {
+        self.0.last().map(|segment| segment.as_ref())
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.0.split_last()?;
+        Some(Self(parent.to_vec()))
+    }
+
+    pub fn child(&self, name: &str) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(name.into());
+        Self(segments)
+    }
+
+    /// Whether this module is `ancestor` or nested in it
+    pub fn is_within(&self, ancestor: &ModulePath) -> bool {
+        self.0.starts_with(&ancestor.0)
+    }
+}
+
+impl fmt::Display for ModulePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "crate")?;
+        for segment in &self.0 {
+            write!(f, "::{segment}")?;
+        }
+        Ok(())
+    }
+}
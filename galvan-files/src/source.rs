@@ -3,7 +3,7 @@ use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::GalvanFileExtension;
+use crate::{GalvanFileExtension, ModulePath};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -44,6 +44,7 @@ pub enum Source {
         path: Arc<Path>,
         content: Arc<str>,
         canonical_name: Arc<str>,
+        module: ModulePath,
     },
     Str(Arc<str>),
     Missing,
@@ -55,7 +56,14 @@ impl Source {
         Self::Str(string.into())
     }
 
+    /// Reads a file as a module at the source root
     pub fn read(path: impl AsRef<Path>) -> SourceResult {
+        let path = path.as_ref();
+        Self::read_in(path.parent().unwrap_or(Path::new("")), path)
+    }
+
+    /// Reads a file below the source root `root`, see [`ModulePath`]
+    pub fn read_in(root: impl AsRef<Path>, path: impl AsRef<Path>) -> SourceResult {
         let path = path.as_ref();
         if !path.has_galvan_extension() {
             Err(FileError::missing_extension(path))?
@@ -71,6 +79,7 @@ impl Source {
             Err(FileError::naming(stem))?
         }
         let canonical_name = stem.replace(".", "_").into();
+        let module = ModulePath::of_file(root, path)?;
         let content = fs::read_to_string(path)
             .map_err(|e| FileError::io(path, e))?
             .into();
@@ -80,9 +89,30 @@ impl Source {
             path,
             content,
             canonical_name,
+            module,
         })
     }
 
+    /// A source with the given content that forms the given module, as if it
+    /// was read from the corresponding file
+    pub fn in_module(module: ModulePath, content: impl Into<Arc<str>>) -> Source {
+        let canonical_name: Arc<str> = module.name().unwrap_or("main").into();
+        let path = match module.parent() {
+            Some(parent) => parent
+                .segments()
+                .collect::<PathBuf>()
+                .join(format!("{canonical_name}.galvan")),
+            None => PathBuf::from("main.galvan"),
+        };
+
+        Self::File {
+            path: path.into(),
+            content: content.into(),
+            canonical_name,
+            module,
+        }
+    }
+
     pub fn content(&self) -> &str {
         match self {
             Self::File { content, .. } => content.as_ref(),
@@ -101,13 +131,19 @@ impl Source {
         }
     }
 
+    /// The module the items of this source belong to. Sources that are not
+    /// files belong to the crate root.
+    pub fn module(&self) -> &ModulePath {
+        static ROOT: ModulePath = ModulePath::root();
+        match self {
+            Self::File { module, .. } => module,
+            Self::Str(_) | Self::Missing | Self::Builtin => &ROOT,
+        }
+    }
+
     pub fn canonical_name(&self) -> Option<&str> {
         match self {
-            Self::File {
-                path: _,
-                content: _,
-                canonical_name,
-            } => Some(canonical_name),
+            Self::File { canonical_name, .. } => Some(canonical_name),
             Self::Str(_) => None,
             Self::Missing => None,
             Self::Builtin => Some("galvan_std"),
@@ -1,13 +1,14 @@
 use galvan_ast::{
     ArrayTypeItem, AstNode, BasicTypeItem, DictionaryTypeItem, EmptyTypeDecl, FnDecl, FnSignature,
-    GenericTypeItem, OptionalTypeItem, OrderedDictionaryTypeItem, Param, ParamList, ResultTypeItem,
-    SegmentedAsts, SetTypeItem, Span, ToplevelItem, TupleTypeItem, TypeDecl, TypeElement,
-    TypeIdent, Visibility, VisibilityKind,
+    GenericTypeItem, OptionalTypeItem, OrderedDictionaryTypeItem, Ownership, Param, ParamList,
+    ResultTypeItem, SegmentedAsts, SetTypeItem, Span, ToplevelItem, TupleTypeItem, TypeDecl,
+    TypeElement, TypeIdent, Visibility, VisibilityKind,
 };
 use galvan_files::Source;
 use itertools::Itertools;
 
 use crate::mapping::{mapping, Mapping};
+use crate::typecheck::union_members;
 
 /// Mapping of standard Galvan types to Rust types
 pub fn builtins() -> Mapping {
@@ -86,6 +87,7 @@ pub fn predefined_from(mapping: &Mapping, functions: Vec<FnDecl>) -> SegmentedAs
             item: TypeDecl::Empty(EmptyTypeDecl {
                 visibility: Visibility::new(VisibilityKind::Inherited, ident.span().clone()),
                 ident: ident.clone(),
+                conformances: vec![],
                 span: ident.span().clone(),
             }),
             source: Source::Missing,
@@ -103,10 +105,13 @@ pub fn predefined_from(mapping: &Mapping, functions: Vec<FnDecl>) -> SegmentedAs
     SegmentedAsts {
         uses: vec![],
         types,
+        traits: vec![],
+        infixes: vec![],
         functions,
         tests,
         main,
         cmds: vec![],
+        syntax_errors: vec![],
     }
 }
 
@@ -126,6 +131,250 @@ pub const BORROWED_ITERATOR_FNS: [&str; 12] = [
     "is_sorted_by",
 ];
 
+/// How a standard library method takes its receiver or an argument
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StdPassing {
+    /// By value; values in borrowed places are cloned
+    Owned,
+    /// By shared reference
+    Borrowed,
+    /// By mutable reference; method receivers are borrowed automatically
+    MutBorrowed,
+}
+
+/// Signature of a Rust standard library method in Galvan types
+#[derive(Clone, Debug)]
+pub struct StdMethod {
+    pub receiver: StdPassing,
+    pub params: Vec<(TypeElement, StdPassing)>,
+    pub return_type: TypeElement,
+    /// `Borrowed` for methods returning a view into their receiver, like `trim`
+    pub returns: Ownership,
+    /// Whether the returned optional reference is copied with `.cloned()`,
+    /// like the element returned by `first`
+    pub cloned: bool,
+}
+
+impl StdMethod {
+    fn new(receiver: StdPassing, params: Vec<(TypeElement, StdPassing)>, ret: TypeElement) -> Self {
+        Self {
+            receiver,
+            params,
+            return_type: ret,
+            returns: Ownership::UniqueOwned,
+            cloned: false,
+        }
+    }
+
+    fn borrowing(self) -> Self {
+        Self {
+            returns: Ownership::Borrowed,
+            ..self
+        }
+    }
+
+    fn cloning(self) -> Self {
+        Self {
+            cloned: true,
+            ..self
+        }
+    }
+}
+
+/// Looks up a method of the Rust type backing a builtin receiver type:
+/// `String`, arrays (`Vec`), sets (`HashSet`), dictionaries (`HashMap` and
+/// `IndexMap`), optionals and results
+///
+/// Only the methods listed here are checked. Methods returning Rust
+/// iterators, like `split` and `iter`, have their arguments checked but an
+/// inferred result, so adapters chained on them (`map`, `filter`, `collect`,
+/// ...) and methods missing from the table are passed through to rustc
+/// unchecked.
+pub fn std_method(receiver: &TypeElement, name: &str) -> Option<StdMethod> {
+    match receiver {
+        TypeElement::Plain(plain) if plain.ident.as_str() == "String" => string_method(name),
+        TypeElement::Array(array) => array_method(&array.elements, name),
+        TypeElement::Set(set) => set_method(&set.elements, name),
+        TypeElement::Dictionary(dict) => dictionary_method(&dict.key, &dict.value, name),
+        // `IndexMap::remove` is deprecated in favor of explicitly ordered removals
+        TypeElement::OrderedDictionary(_) if name == "remove" => None,
+        TypeElement::OrderedDictionary(dict) => dictionary_method(&dict.key, &dict.value, name),
+        TypeElement::Optional(optional) => optional_method(&optional.inner, name),
+        TypeElement::Result(result) => result_method(result, name),
+        _ => None,
+    }
+}
+
+fn string_method(name: &str) -> Option<StdMethod> {
+    use StdPassing::*;
+    let string = || builtin_type("String");
+    let method = match name {
+        "len" => StdMethod::new(Borrowed, vec![], builtin_type("USize")),
+        "is_empty" => StdMethod::new(Borrowed, vec![], TypeElement::bool()),
+        "to_uppercase" | "to_lowercase" => StdMethod::new(Borrowed, vec![], string()),
+        "trim" | "trim_start" | "trim_end" => {
+            StdMethod::new(Borrowed, vec![], string()).borrowing()
+        }
+        "contains" | "starts_with" | "ends_with" => {
+            StdMethod::new(Borrowed, vec![(string(), Borrowed)], TypeElement::bool())
+        }
+        "replace" => StdMethod::new(
+            Borrowed,
+            vec![(string(), Borrowed), (string(), Borrowed)],
+            string(),
+        ),
+        "repeat" => StdMethod::new(Borrowed, vec![(builtin_type("USize"), Owned)], string()),
+        "split" => StdMethod::new(Borrowed, vec![(string(), Borrowed)], TypeElement::infer()),
+        "push" => StdMethod::new(
+            MutBorrowed,
+            vec![(builtin_type("Char"), Owned)],
+            TypeElement::void(),
+        ),
+        "push_str" => StdMethod::new(MutBorrowed, vec![(string(), Borrowed)], TypeElement::void()),
+        "clear" => StdMethod::new(MutBorrowed, vec![], TypeElement::void()),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn array_method(element: &TypeElement, name: &str) -> Option<StdMethod> {
+    use StdPassing::*;
+    let method = match name {
+        "len" => StdMethod::new(Borrowed, vec![], builtin_type("USize")),
+        "is_empty" => StdMethod::new(Borrowed, vec![], TypeElement::bool()),
+        "contains" => StdMethod::new(
+            Borrowed,
+            vec![(element.clone(), Borrowed)],
+            TypeElement::bool(),
+        ),
+        "get" => StdMethod::new(
+            Borrowed,
+            vec![(builtin_type("USize"), Owned)],
+            optional(element.clone()),
+        )
+        .cloning(),
+        "first" | "last" => StdMethod::new(Borrowed, vec![], optional(element.clone())).cloning(),
+        "iter" => StdMethod::new(Borrowed, vec![], TypeElement::infer()),
+        "push" => StdMethod::new(
+            MutBorrowed,
+            vec![(element.clone(), Owned)],
+            TypeElement::void(),
+        ),
+        "pop" => StdMethod::new(MutBorrowed, vec![], optional(element.clone())),
+        "insert" => StdMethod::new(
+            MutBorrowed,
+            vec![(builtin_type("USize"), Owned), (element.clone(), Owned)],
+            TypeElement::void(),
+        ),
+        "remove" => StdMethod::new(
+            MutBorrowed,
+            vec![(builtin_type("USize"), Owned)],
+            element.clone(),
+        ),
+        "clear" | "reverse" | "sort" | "dedup" => {
+            StdMethod::new(MutBorrowed, vec![], TypeElement::void())
+        }
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn set_method(element: &TypeElement, name: &str) -> Option<StdMethod> {
+    use StdPassing::*;
+    let method = match name {
+        "len" => StdMethod::new(Borrowed, vec![], builtin_type("USize")),
+        "is_empty" => StdMethod::new(Borrowed, vec![], TypeElement::bool()),
+        "contains" => StdMethod::new(
+            Borrowed,
+            vec![(element.clone(), Borrowed)],
+            TypeElement::bool(),
+        ),
+        "insert" => StdMethod::new(
+            MutBorrowed,
+            vec![(element.clone(), Owned)],
+            TypeElement::bool(),
+        ),
+        "remove" => StdMethod::new(
+            MutBorrowed,
+            vec![(element.clone(), Borrowed)],
+            TypeElement::bool(),
+        ),
+        "clear" => StdMethod::new(MutBorrowed, vec![], TypeElement::void()),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn dictionary_method(key: &TypeElement, value: &TypeElement, name: &str) -> Option<StdMethod> {
+    use StdPassing::*;
+    let method = match name {
+        "len" => StdMethod::new(Borrowed, vec![], builtin_type("USize")),
+        "is_empty" => StdMethod::new(Borrowed, vec![], TypeElement::bool()),
+        "contains_key" => {
+            StdMethod::new(Borrowed, vec![(key.clone(), Borrowed)], TypeElement::bool())
+        }
+        "get" => StdMethod::new(
+            Borrowed,
+            vec![(key.clone(), Borrowed)],
+            optional(value.clone()),
+        )
+        .cloning(),
+        "insert" => StdMethod::new(
+            MutBorrowed,
+            vec![(key.clone(), Owned), (value.clone(), Owned)],
+            optional(value.clone()),
+        ),
+        "remove" => StdMethod::new(
+            MutBorrowed,
+            vec![(key.clone(), Borrowed)],
+            optional(value.clone()),
+        ),
+        "clear" => StdMethod::new(MutBorrowed, vec![], TypeElement::void()),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn optional_method(inner: &TypeElement, name: &str) -> Option<StdMethod> {
+    use StdPassing::*;
+    let method = match name {
+        "is_some" | "is_none" => StdMethod::new(Borrowed, vec![], TypeElement::bool()),
+        "unwrap" => StdMethod::new(Owned, vec![], inner.clone()),
+        "unwrap_or" => StdMethod::new(Owned, vec![(inner.clone(), Owned)], inner.clone()),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn result_method(result: &ResultTypeItem, name: &str) -> Option<StdMethod> {
+    use StdPassing::*;
+    let success = &result.success;
+    let error = result.error.clone().unwrap_or_else(TypeElement::infer);
+    let method = match name {
+        "is_ok" | "is_err" => StdMethod::new(Borrowed, vec![], TypeElement::bool()),
+        "unwrap" => StdMethod::new(Owned, vec![], success.clone()),
+        "unwrap_err" => StdMethod::new(Owned, vec![], error),
+        "unwrap_or" => StdMethod::new(Owned, vec![(success.clone(), Owned)], success.clone()),
+        "ok" => StdMethod::new(Owned, vec![], optional(success.clone())),
+        _ => return None,
+    };
+    Some(method)
+}
+
+fn builtin_type(name: &str) -> TypeElement {
+    TypeElement::Plain(BasicTypeItem {
+        ident: TypeIdent::new(name),
+        span: Span::default(),
+    })
+}
+
+fn optional(inner: TypeElement) -> TypeElement {
+    TypeElement::Optional(Box::new(OptionalTypeItem {
+        inner,
+        span: Span::default(),
+    }))
+}
+
 pub trait CheckBuiltins {
     fn is_infer(&self) -> bool;
     fn is_number(&self) -> bool;
@@ -173,6 +422,10 @@ impl IsSame for TypeElement {
                         .all(|(a, b)| a.is_same(b))
                     && a.return_ty.is_same(&b.return_ty)
             }
+            (TypeElement::Union(a), TypeElement::Union(b)) => {
+                let (a, b) = (union_members(a), union_members(b));
+                a.len() == b.len() && a.iter().zip(&b).all(|(a, b)| a.is_same(b))
+            }
             (TypeElement::Never(_), TypeElement::Never(_)) => true,
             (TypeElement::Void(_), TypeElement::Void(_)) => true,
             (TypeElement::Infer(_), TypeElement::Infer(_)) => true,
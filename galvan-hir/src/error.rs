@@ -1,7 +1,16 @@
+use std::collections::HashMap;
 use std::fmt;
 
+use galvan_ast::{Point, SyntaxError};
+use galvan_files::Source;
+use galvan_resolver::LookupError;
 use thiserror::Error;
 
+mod codes;
+mod json;
+
+pub use codes::{explain, CODES};
+
 /// Represents the severity of a diagnostic message
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DiagnosticSeverity {
@@ -10,20 +19,51 @@ pub enum DiagnosticSeverity {
     Info,
 }
 
+impl fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticSeverity::Error => write!(f, "error"),
+            DiagnosticSeverity::Warning => write!(f, "warning"),
+            DiagnosticSeverity::Info => write!(f, "info"),
+        }
+    }
+}
+
 /// Represents a single diagnostic message with source location
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Diagnostic {
     pub severity: DiagnosticSeverity,
+    /// Stable code of the kind of diagnostic, e.g. `G0002`
+    pub code: Option<&'static str>,
     pub message: String,
     pub span: Option<Span>,
     pub suggestion: Option<String>,
+    /// Code that replaces the spanned code to apply the suggestion
+    pub replacement: Option<String>,
+    /// Further locations the diagnostic refers to
+    pub related: Vec<RelatedSpan>,
+}
+
+/// A further location of a diagnostic, e.g. the first declaration of an item
+/// that is declared twice
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelatedSpan {
+    pub message: String,
+    pub span: Span,
 }
 
 /// Span information for error reporting
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Span {
+    /// Byte offset of the start in the file
     pub start: usize,
+    /// Byte offset of the end in the file
     pub end: usize,
+    /// Zero-based row and column of the start
+    pub start_point: Point,
+    /// Zero-based row and column of the end
+    pub end_point: Point,
+    /// The path of the file, or empty for sources that are not files
     pub file: String,
 }
 
@@ -32,9 +72,50 @@ impl From<galvan_ast::Span> for Span {
         Self {
             start: ast_span.range.0,
             end: ast_span.range.1,
-            file: "".to_string(), // AST span doesn't include file info
+            start_point: ast_span.start,
+            end_point: ast_span.end,
+            // Filled in by the collector with the file of the checked item
+            file: String::new(),
+        }
+    }
+}
+
+impl Span {
+    /// A span in the file `source` was read from
+    pub fn in_source(ast_span: galvan_ast::Span, source: &Source) -> Self {
+        Self {
+            file: file_name(source),
+            ..ast_span.into()
         }
     }
+
+    /// Spans synthesized for derived nodes do not point into the source
+    fn is_synthesized(&self) -> bool {
+        self.start == 0 && self.end == 0
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = if self.file.is_empty() {
+            "<input>"
+        } else {
+            &self.file
+        };
+        write!(
+            f,
+            "{file}:{}:{}",
+            self.start_point.row + 1,
+            self.start_point.col + 1
+        )
+    }
+}
+
+fn file_name(source: &Source) -> String {
+    source
+        .origin()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
 }
 
 /// Main error type for the transpiler
@@ -82,6 +163,12 @@ pub enum TranspilerError {
     #[error("Invalid syntax: {message}")]
     InvalidSyntax { message: String },
 
+    #[error("Unresolved path: {path}")]
+    UnresolvedPath { path: String },
+
+    #[error("Private item: {item} is private to module {module}")]
+    PrivateItem { item: String, module: String },
+
     #[error("Circular dependency detected")]
     CircularDependency,
 
@@ -100,6 +187,16 @@ pub enum TranspilerError {
         allowed_types: String,
     },
 
+    #[error("Type {ty} does not conform to trait {trait_name}: {message}")]
+    TraitConformance {
+        ty: String,
+        trait_name: String,
+        message: String,
+    },
+
+    #[error("Non-exhaustive match on {ty}: missing {missing}")]
+    NonExhaustiveMatch { ty: String, missing: String },
+
     #[error("Enum access error: {message}")]
     EnumAccessError { message: String },
 
@@ -114,6 +211,147 @@ pub enum TranspilerError {
         operation: String,
         type_name: String,
     },
+
+    #[error("Syntax error: {problem}")]
+    Syntax { problem: String },
+
+    #[error("Type file collision: {first} and {second} would both be generated into `{file}.rs`")]
+    TypeFileCollision {
+        first: String,
+        second: String,
+        file: String,
+    },
+}
+
+impl TranspilerError {
+    /// The stable code of this kind of error. Codes are never reused for a
+    /// different kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TranspilerError::TypeMismatch { .. } => "G0001",
+            TranspilerError::UnknownIdentifier { .. } => "G0002",
+            TranspilerError::UnknownType { .. } => "G0003",
+            TranspilerError::ImmutableAssignment { .. } => "G0004",
+            TranspilerError::InvalidOperation { .. } => "G0005",
+            TranspilerError::ArgumentCountMismatch { .. } => "G0006",
+            TranspilerError::ArgumentPassingMode { .. } => "G0007",
+            TranspilerError::TypeInferenceFailure => "G0008",
+            TranspilerError::Unimplemented { .. } => "G0009",
+            TranspilerError::InvalidSyntax { .. } => "G0010",
+            TranspilerError::UnresolvedPath { .. } => "G0011",
+            TranspilerError::PrivateItem { .. } => "G0012",
+            TranspilerError::CircularDependency => "G0013",
+            TranspilerError::InvalidModifier { .. } => "G0014",
+            TranspilerError::MissingArgument { .. } => "G0015",
+            TranspilerError::InvalidOperationOnType { .. } => "G0016",
+            TranspilerError::TraitConformance { .. } => "G0017",
+            TranspilerError::NonExhaustiveMatch { .. } => "G0018",
+            TranspilerError::EnumAccessError { .. } => "G0019",
+            TranspilerError::MemberAccessError { .. } => "G0020",
+            TranspilerError::IncompatibleOwnership { .. } => "G0021",
+            TranspilerError::UnsupportedDictSetAssignment { .. } => "G0022",
+            TranspilerError::Syntax { .. } => "G0026",
+            TranspilerError::TypeFileCollision { .. } => "G0027",
+        }
+    }
+}
+
+/// Problems that are reported as warnings and do not fail the build
+#[derive(Debug, Error)]
+pub enum TranspilerWarning {
+    #[error("Type mismatch: expected {expected}, found {found}")]
+    TypeMismatch { expected: String, found: String },
+
+    #[error("Unimplemented feature: {feature}")]
+    Unimplemented { feature: String },
+
+    #[error("Type resolving error: Type {name} not found")]
+    UnresolvedType { name: String },
+
+    #[error("Unreachable match arm: {reason}")]
+    UnreachableMatchArm { reason: String },
+
+    #[error("Wrapping non-matching type {ty} in {wrapper}")]
+    ImplicitWrap { ty: String, wrapper: String },
+
+    #[error("Skipped `{path}`: {reason}")]
+    SkippedImport { path: String, reason: String },
+
+    #[error("For loop on type that is not an iterator")]
+    NotAnIterator,
+
+    #[error("Types of match arms don't match: previous: {previous}, next: {next}")]
+    MatchArmTypeMismatch { previous: String, next: String },
+
+    #[error("Types of if and else expression don't match: if: {then_ty}, else: {else_ty}")]
+    IfElseTypeMismatch { then_ty: String, else_ty: String },
+
+    #[error("Cannot mutably borrow an immutably borrowed value")]
+    MutBorrowOfBorrowed,
+
+    #[error("Variable '{name}' needs a type annotation or an initializer")]
+    MissingTypeAnnotation { name: String },
+
+    #[error("Destructuring declarations need an initializer")]
+    MissingInitializer,
+
+    #[error("Type mismatch in arithmetic operation: {lhs} {operator} {rhs}")]
+    ArithmeticTypeMismatch {
+        lhs: String,
+        operator: String,
+        rhs: String,
+    },
+
+    #[error(
+        "Yeet operator {part}type mismatch: yielding {yielded} but function returns {returned}"
+    )]
+    YeetTypeMismatch {
+        /// `success ` or `error ` when only that side of a result differs
+        part: &'static str,
+        yielded: String,
+        returned: String,
+    },
+
+    #[error(
+        "Yeet operator type incompatibility: yielding {yielded} but function returns {returned}"
+    )]
+    YeetIncompatible { yielded: String, returned: String },
+}
+
+impl TranspilerWarning {
+    /// The stable code of this kind of warning. Warnings about the same
+    /// problem as an error share its code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TranspilerWarning::TypeMismatch { .. } => "G0001",
+            TranspilerWarning::UnresolvedType { .. } => "G0003",
+            TranspilerWarning::Unimplemented { .. } => "G0009",
+            TranspilerWarning::UnreachableMatchArm { .. } => "G0028",
+            TranspilerWarning::ImplicitWrap { .. } => "G0029",
+            TranspilerWarning::SkippedImport { .. } => "G0030",
+            TranspilerWarning::NotAnIterator => "G0031",
+            TranspilerWarning::MatchArmTypeMismatch { .. }
+            | TranspilerWarning::IfElseTypeMismatch { .. } => "G0032",
+            TranspilerWarning::MutBorrowOfBorrowed => "G0033",
+            TranspilerWarning::MissingTypeAnnotation { .. }
+            | TranspilerWarning::MissingInitializer => "G0034",
+            TranspilerWarning::ArithmeticTypeMismatch { .. } => "G0035",
+            TranspilerWarning::YeetTypeMismatch { .. }
+            | TranspilerWarning::YeetIncompatible { .. } => "G0036",
+        }
+    }
+}
+
+/// The stable code of a lookup error, continuing the codes of
+/// [`TranspilerError`]
+fn lookup_error_code(error: &LookupError) -> &'static str {
+    match error {
+        LookupError::TypeNotFound => "G0003",
+        LookupError::FunctionNotFound => "G0002",
+        LookupError::DuplicateType { .. } => "G0023",
+        LookupError::DuplicateFunction { .. } => "G0024",
+        LookupError::DuplicateInfix { .. } => "G0025",
+    }
 }
 
 /// Collects errors and warnings during compilation
@@ -122,6 +360,10 @@ pub struct ErrorCollector {
     diagnostics: Vec<Diagnostic>,
     error_count: usize,
     warning_count: usize,
+    /// The sources diagnostics point into, by file name
+    sources: HashMap<String, Source>,
+    /// The file of the item being checked, used for spans without a file
+    current_file: Option<String>,
 }
 
 impl ErrorCollector {
@@ -129,6 +371,44 @@ impl ErrorCollector {
         Self::default()
     }
 
+    /// Attributes spans without a file to `source` until the next call or
+    /// [`Self::leave_source`]
+    pub fn enter_source(&mut self, source: &Source) {
+        self.current_file = Some(self.add_source(source));
+    }
+
+    pub fn leave_source(&mut self) {
+        self.current_file = None;
+    }
+
+    /// The file entered with [`Self::enter_source`], empty for sources that
+    /// are not files
+    pub fn current_file(&self) -> Option<&str> {
+        self.current_file.as_deref()
+    }
+
+    fn add_source(&mut self, source: &Source) -> String {
+        let file = file_name(source);
+        self.sources
+            .entry(file.clone())
+            .or_insert_with(|| source.clone());
+        file
+    }
+
+    fn push(&mut self, mut diagnostic: Diagnostic) {
+        if let (Some(span), Some(file)) = (&mut diagnostic.span, &self.current_file) {
+            if span.file.is_empty() {
+                span.file = file.clone();
+            }
+        }
+        match diagnostic.severity {
+            DiagnosticSeverity::Error => self.error_count += 1,
+            DiagnosticSeverity::Warning => self.warning_count += 1,
+            DiagnosticSeverity::Info => {}
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
     /// Add an error to the collector
     pub fn error(&mut self, error: TranspilerError) {
         self.error_with_span(error, None);
@@ -136,13 +416,15 @@ impl ErrorCollector {
 
     /// Add an error with span information
     pub fn error_with_span(&mut self, error: TranspilerError, span: Option<Span>) {
-        self.diagnostics.push(Diagnostic {
+        self.push(Diagnostic {
             severity: DiagnosticSeverity::Error,
+            code: Some(error.code()),
             message: error.to_string(),
             span,
             suggestion: None,
+            replacement: None,
+            related: Vec::new(),
         });
-        self.error_count += 1;
     }
 
     /// Add an error with a suggestion
@@ -152,33 +434,152 @@ impl ErrorCollector {
         span: Option<Span>,
         suggestion: String,
     ) {
-        self.diagnostics.push(Diagnostic {
+        self.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            code: Some(error.code()),
+            message: error.to_string(),
+            span,
+            suggestion: Some(suggestion),
+            replacement: None,
+            related: Vec::new(),
+        });
+    }
+
+    /// Add an error with a suggestion to replace the spanned code with
+    /// `replacement`
+    pub fn error_with_replacement(
+        &mut self,
+        error: TranspilerError,
+        span: Option<Span>,
+        suggestion: String,
+        replacement: String,
+    ) {
+        self.push(Diagnostic {
             severity: DiagnosticSeverity::Error,
+            code: Some(error.code()),
             message: error.to_string(),
             span,
             suggestion: Some(suggestion),
+            replacement: Some(replacement),
+            related: Vec::new(),
+        });
+    }
+
+    /// Add an error for a name that cannot be resolved, e.g. an item that is
+    /// declared twice, pointing at both declarations
+    pub fn lookup_error(&mut self, error: &LookupError) {
+        let (span, related) = match error.declarations() {
+            Some((original, duplicate)) => {
+                let (span, related) = self.conflicting(
+                    (duplicate.span, &duplicate.source),
+                    (original.span, &original.source),
+                );
+                (Some(span), related)
+            }
+            None => (None, Vec::new()),
+        };
+        self.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            code: Some(lookup_error_code(error)),
+            message: error.to_string(),
+            span,
+            suggestion: error.help(),
+            replacement: None,
+            related,
+        });
+    }
+
+    /// Add an error for a declaration that conflicts with an earlier one,
+    /// pointing at both declarations
+    pub fn conflict_error(
+        &mut self,
+        error: TranspilerError,
+        declaration: (galvan_ast::Span, &Source),
+        earlier: (galvan_ast::Span, &Source),
+    ) {
+        let (span, related) = self.conflicting(declaration, earlier);
+        self.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            code: Some(error.code()),
+            message: error.to_string(),
+            span: Some(span),
+            suggestion: None,
+            replacement: None,
+            related,
+        });
+    }
+
+    /// The span of a conflicting declaration and a note pointing at the
+    /// earlier one
+    fn conflicting(
+        &mut self,
+        (span, source): (galvan_ast::Span, &Source),
+        (earlier_span, earlier_source): (galvan_ast::Span, &Source),
+    ) -> (Span, Vec<RelatedSpan>) {
+        self.add_source(source);
+        self.add_source(earlier_source);
+        (
+            Span::in_source(span, source),
+            vec![RelatedSpan {
+                message: "first declared here".to_owned(),
+                span: Span::in_source(earlier_span, earlier_source),
+            }],
+        )
+    }
+
+    /// Add an error for code that could not be parsed
+    pub fn syntax_error(&mut self, error: &SyntaxError) {
+        self.add_source(&error.source);
+        let problem = match &error.found {
+            Some(found) => format!("unexpected `{found}`"),
+            None => format!("missing `{}`", error.expected.join("`, `")),
+        };
+        let expected = error
+            .expected
+            .iter()
+            .map(|kind| format!("`{kind}`"))
+            .collect::<Vec<_>>();
+        let suggestion = match (&error.found, expected.as_slice()) {
+            (None, _) | (_, []) => None,
+            (_, [kind]) => Some(format!("expected {kind}")),
+            (_, kinds) => Some(format!("expected one of {}", kinds.join(", "))),
+        };
+        let syntax = TranspilerError::Syntax { problem };
+        self.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            code: Some(syntax.code()),
+            message: syntax.to_string(),
+            span: Some(Span::in_source(error.span, &error.source)),
+            suggestion,
+            replacement: None,
+            related: Vec::new(),
         });
-        self.error_count += 1;
     }
 
     /// Add a warning
-    pub fn warning(&mut self, message: String, span: Option<Span>) {
-        self.diagnostics.push(Diagnostic {
+    pub fn warning(&mut self, warning: TranspilerWarning, span: Option<Span>) {
+        self.push(Diagnostic {
             severity: DiagnosticSeverity::Warning,
-            message,
+            code: Some(warning.code()),
+            message: warning.to_string(),
             span,
             suggestion: None,
+            replacement: None,
+            related: Vec::new(),
         });
-        self.warning_count += 1;
     }
 
-    /// Add an info message
+    /// Add an info message. Info messages are not tied to a kind of
+    /// diagnostic and have no code.
     pub fn info(&mut self, message: String, span: Option<Span>) {
-        self.diagnostics.push(Diagnostic {
+        self.push(Diagnostic {
             severity: DiagnosticSeverity::Info,
+            code: None,
             message,
             span,
             suggestion: None,
+            replacement: None,
+            related: Vec::new(),
         });
     }
 
@@ -219,10 +620,71 @@ impl ErrorCollector {
     /// Merge another ErrorCollector into this one
     pub fn merge(&mut self, other: ErrorCollector) {
         self.diagnostics.extend(other.diagnostics);
+        for (file, source) in other.sources {
+            self.sources.entry(file).or_insert(source);
+        }
         self.error_count += other.error_count;
         self.warning_count += other.warning_count;
     }
 
+    /// Renders a diagnostic like rustc does: the message, followed by the
+    /// location and source line of each span with the spanned code underlined
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut rendered = match diagnostic.code {
+            Some(code) => format!("{}[{code}]: {}\n", diagnostic.severity, diagnostic.message),
+            None => format!("{}: {}\n", diagnostic.severity, diagnostic.message),
+        };
+        if let Some(span) = &diagnostic.span {
+            rendered += &self.render_span(span);
+        }
+        for related in &diagnostic.related {
+            rendered += &format!("note: {}\n", related.message);
+            rendered += &self.render_span(&related.span);
+        }
+        if let Some(suggestion) = &diagnostic.suggestion {
+            rendered += &format!("  = help: {suggestion}\n");
+        }
+        rendered
+    }
+
+    fn render_span(&self, span: &Span) -> String {
+        if span.is_synthesized() {
+            return String::new();
+        }
+        let line_number = (span.start_point.row + 1).to_string();
+        let gutter = " ".repeat(line_number.len());
+        let mut rendered = format!("{gutter}--> {span}\n");
+
+        if let Some((line, start, end)) = self.highlight(span) {
+            rendered += &format!(
+                "{gutter} |\n{line_number} | {line}\n{gutter} | {}{}\n",
+                " ".repeat(start),
+                "^".repeat(end - start)
+            );
+        }
+        rendered
+    }
+
+    /// The first source line of the span with the character range of the
+    /// spanned code on it. The range covers at least one character.
+    fn highlight(&self, span: &Span) -> Option<(&str, usize, usize)> {
+        let line = self
+            .sources
+            .get(&span.file)?
+            .content()
+            .lines()
+            .nth(span.start_point.row)?;
+        let start = span.start_point.col.min(line.len());
+        let end = if span.end_point.row == span.start_point.row {
+            span.end_point.col.clamp(start, line.len())
+        } else {
+            line.len()
+        };
+        let start_char = line.get(..start).map_or(0, |code| code.chars().count());
+        let width = line.get(start..end).map_or(0, |code| code.chars().count());
+        Some((line, start_char, start_char + width.max(1)))
+    }
+
     /// Create a Result type that fails if errors were collected
     pub fn into_result<T>(self, value: T) -> Result<T, Vec<Diagnostic>> {
         if self.has_errors() {
@@ -240,12 +702,13 @@ impl ErrorCollector {
         span: Option<Span>,
     ) {
         if let Some(suggestion) = find_closest_match(unknown, available) {
-            self.error_with_suggestion(
+            self.error_with_replacement(
                 TranspilerError::UnknownIdentifier {
                     name: unknown.to_string(),
                 },
                 span,
                 format!("Did you mean '{}'?", suggestion),
+                suggestion,
             );
         } else {
             self.error_with_span(
@@ -265,12 +728,13 @@ impl ErrorCollector {
         span: Option<Span>,
     ) {
         if let Some(suggestion) = find_closest_match(unknown, available) {
-            self.error_with_suggestion(
+            self.error_with_replacement(
                 TranspilerError::UnknownType {
                     name: unknown.to_string(),
                 },
                 span,
                 format!("Did you mean '{}'?", suggestion),
+                suggestion,
             );
         } else {
             self.error_with_span(
@@ -344,17 +808,7 @@ fn levenshtein_distance(a: &str, b: &str) -> usize {
 impl fmt::Display for ErrorCollector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for diagnostic in &self.diagnostics {
-            match diagnostic.severity {
-                DiagnosticSeverity::Error => write!(f, "error: ")?,
-                DiagnosticSeverity::Warning => write!(f, "warning: ")?,
-                DiagnosticSeverity::Info => write!(f, "info: ")?,
-            }
-
-            writeln!(f, "{}", diagnostic.message)?;
-
-            if let Some(ref suggestion) = diagnostic.suggestion {
-                writeln!(f, "  help: {}", suggestion)?;
-            }
+            write!(f, "{}", self.render(diagnostic))?;
         }
         Ok(())
     }
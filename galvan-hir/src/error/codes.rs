@@ -0,0 +1,216 @@
+//! Long-form explanations of the diagnostic codes, with an erroneous example
+//! and a fix for each
+
+macro_rules! explanations {
+    ($($code:literal),* $(,)?) => {
+        /// Every diagnostic code, in ascending order
+        pub const CODES: &[&str] = &[$($code),*];
+
+        /// The explanation of a diagnostic code like `G0007` as Markdown
+        pub fn explain(code: &str) -> Option<&'static str> {
+            match code {
+                $($code => Some(include_str!(concat!("codes/", $code, ".md"))),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+explanations!(
+    "G0001", "G0002", "G0003", "G0004", "G0005", "G0006", "G0007", "G0008", "G0009", "G0010",
+    "G0011", "G0012", "G0013", "G0014", "G0015", "G0016", "G0017", "G0018", "G0019", "G0020",
+    "G0021", "G0022", "G0023", "G0024", "G0025", "G0026", "G0027", "G0028", "G0029", "G0030",
+    "G0031", "G0032", "G0033", "G0034", "G0035", "G0036",
+);
+
+#[cfg(test)]
+mod tests {
+    use galvan_ast::{CustomInfix, Ident, Span, TypeIdent};
+    use galvan_files::Source;
+    use galvan_resolver::{Declaration, LookupError};
+
+    use super::*;
+    use crate::error::lookup_error_code;
+    use crate::{TranspilerError, TranspilerWarning};
+
+    fn text() -> String {
+        "text".to_string()
+    }
+
+    /// An error of every kind
+    fn every_error() -> Vec<TranspilerError> {
+        vec![
+            TranspilerError::TypeMismatch {
+                expected: text(),
+                found: text(),
+            },
+            TranspilerError::UnknownIdentifier { name: text() },
+            TranspilerError::UnknownType { name: text() },
+            TranspilerError::ImmutableAssignment { name: text() },
+            TranspilerError::InvalidOperation {
+                operation: text(),
+                left: text(),
+                right: text(),
+            },
+            TranspilerError::ArgumentCountMismatch {
+                name: text(),
+                expected: 1,
+                found: 2,
+            },
+            TranspilerError::ArgumentPassingMode {
+                parameter: text(),
+                expected: text(),
+                found: text(),
+            },
+            TranspilerError::TypeInferenceFailure,
+            TranspilerError::Unimplemented { feature: text() },
+            TranspilerError::InvalidSyntax { message: text() },
+            TranspilerError::UnresolvedPath { path: text() },
+            TranspilerError::PrivateItem {
+                item: text(),
+                module: text(),
+            },
+            TranspilerError::CircularDependency,
+            TranspilerError::InvalidModifier {
+                modifier: text(),
+                context: text(),
+            },
+            TranspilerError::MissingArgument {
+                operation: text(),
+                argument_type: text(),
+            },
+            TranspilerError::InvalidOperationOnType {
+                operation: text(),
+                allowed_types: text(),
+            },
+            TranspilerError::TraitConformance {
+                ty: text(),
+                trait_name: text(),
+                message: text(),
+            },
+            TranspilerError::NonExhaustiveMatch {
+                ty: text(),
+                missing: text(),
+            },
+            TranspilerError::EnumAccessError { message: text() },
+            TranspilerError::MemberAccessError { message: text() },
+            TranspilerError::IncompatibleOwnership { message: text() },
+            TranspilerError::UnsupportedDictSetAssignment {
+                operation: text(),
+                type_name: text(),
+            },
+            TranspilerError::Syntax { problem: text() },
+            TranspilerError::TypeFileCollision {
+                first: text(),
+                second: text(),
+                file: text(),
+            },
+        ]
+    }
+
+    /// A warning of every kind
+    fn every_warning() -> Vec<TranspilerWarning> {
+        vec![
+            TranspilerWarning::TypeMismatch {
+                expected: text(),
+                found: text(),
+            },
+            TranspilerWarning::Unimplemented { feature: text() },
+            TranspilerWarning::UnresolvedType { name: text() },
+            TranspilerWarning::UnreachableMatchArm { reason: text() },
+            TranspilerWarning::ImplicitWrap {
+                ty: text(),
+                wrapper: text(),
+            },
+            TranspilerWarning::SkippedImport {
+                path: text(),
+                reason: text(),
+            },
+            TranspilerWarning::NotAnIterator,
+            TranspilerWarning::MatchArmTypeMismatch {
+                previous: text(),
+                next: text(),
+            },
+            TranspilerWarning::IfElseTypeMismatch {
+                then_ty: text(),
+                else_ty: text(),
+            },
+            TranspilerWarning::MutBorrowOfBorrowed,
+            TranspilerWarning::MissingTypeAnnotation { name: text() },
+            TranspilerWarning::MissingInitializer,
+            TranspilerWarning::ArithmeticTypeMismatch {
+                lhs: text(),
+                operator: text(),
+                rhs: text(),
+            },
+            TranspilerWarning::YeetTypeMismatch {
+                part: "",
+                yielded: text(),
+                returned: text(),
+            },
+            TranspilerWarning::YeetIncompatible {
+                yielded: text(),
+                returned: text(),
+            },
+        ]
+    }
+
+    /// A lookup error of every kind
+    fn every_lookup_error() -> Vec<LookupError> {
+        let declaration = || {
+            Box::new(Declaration {
+                source: Source::Missing,
+                span: Span::default(),
+            })
+        };
+        vec![
+            LookupError::TypeNotFound,
+            LookupError::FunctionNotFound,
+            LookupError::DuplicateType {
+                ident: TypeIdent::new("Point"),
+                original: declaration(),
+                duplicate: declaration(),
+            },
+            LookupError::DuplicateFunction {
+                ident: Ident::new("distance"),
+                labels: Vec::new(),
+                original: declaration(),
+                duplicate: declaration(),
+            },
+            LookupError::DuplicateInfix {
+                operator: CustomInfix::new("<+>"),
+                original: declaration(),
+                duplicate: declaration(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_every_code_is_explained() {
+        for code in CODES {
+            let explanation = explain(code).unwrap();
+            assert!(explanation.contains("```galvan"), "{code} has no example");
+        }
+        assert_eq!(explain("G9999"), None);
+    }
+
+    #[test]
+    fn test_error_codes_are_in_catalog() {
+        let codes = every_error()
+            .iter()
+            .map(TranspilerError::code)
+            .chain(every_warning().iter().map(TranspilerWarning::code))
+            .chain(every_lookup_error().iter().map(lookup_error_code))
+            .collect::<Vec<_>>();
+        for code in &codes {
+            assert!(CODES.contains(code), "{code} is not in the catalog");
+        }
+        // A kind with a new code that is missing above leaves its code unused
+        for code in CODES {
+            assert!(
+                codes.contains(code),
+                "no kind of diagnostic has code {code}"
+            );
+        }
+    }
+}
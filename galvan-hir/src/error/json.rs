@@ -0,0 +1,152 @@
+//! Diagnostics as JSON in the shape of rustc's `--error-format=json`, so
+//! that editors and CI annotators can consume them like Rust diagnostics
+
+use serde_json::{json, Value};
+
+use super::{explain, Diagnostic, DiagnosticSeverity, ErrorCollector, Span};
+
+impl ErrorCollector {
+    /// Serializes a diagnostic as a single line of JSON
+    pub fn render_json(&self, diagnostic: &Diagnostic) -> String {
+        let mut children = diagnostic
+            .related
+            .iter()
+            .map(|related| child("note", &related.message, self.spans(&related.span, None)))
+            .collect::<Vec<_>>();
+        if let Some(suggestion) = &diagnostic.suggestion {
+            let spans = match (&diagnostic.span, &diagnostic.replacement) {
+                (Some(span), Some(replacement)) => self.spans(span, Some(replacement)),
+                _ => Vec::new(),
+            };
+            children.push(child("help", suggestion, spans));
+        }
+
+        json!({
+            "$message_type": "diagnostic",
+            "message": diagnostic.message,
+            "code": diagnostic
+                .code
+                .map(|code| json!({ "code": code, "explanation": explain(code) })),
+            "level": level(&diagnostic.severity),
+            "spans": diagnostic
+                .span
+                .as_ref()
+                .map_or_else(Vec::new, |span| self.spans(span, None)),
+            "children": children,
+            "rendered": self.render(diagnostic),
+        })
+        .to_string()
+    }
+
+    /// Serializes all diagnostics, one JSON object per line
+    pub fn to_json_lines(&self) -> String {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| self.render_json(diagnostic) + "\n")
+            .collect()
+    }
+
+    /// The JSON spans for `span`, empty if it does not point into the source
+    fn spans(&self, span: &Span, replacement: Option<&String>) -> Vec<Value> {
+        if span.is_synthesized() {
+            return Vec::new();
+        }
+        let text = self
+            .highlight(span)
+            .map_or_else(Vec::new, |(line, start, end)| {
+                vec![json!({
+                    "text": line,
+                    "highlight_start": start + 1,
+                    "highlight_end": end + 1,
+                })]
+            });
+        let file_name = if span.file.is_empty() {
+            "<input>"
+        } else {
+            &span.file
+        };
+
+        vec![json!({
+            "file_name": file_name,
+            "byte_start": span.start,
+            "byte_end": span.end,
+            "line_start": span.start_point.row + 1,
+            "line_end": span.end_point.row + 1,
+            "column_start": span.start_point.col + 1,
+            "column_end": span.end_point.col + 1,
+            "is_primary": true,
+            "text": text,
+            "label": null,
+            "suggested_replacement": replacement,
+            "suggestion_applicability": replacement.map(|_| "MachineApplicable"),
+            "expansion": null,
+        })]
+    }
+}
+
+fn child(level: &str, message: &str, spans: Vec<Value>) -> Value {
+    json!({
+        "message": message,
+        "code": null,
+        "level": level,
+        "spans": spans,
+        "children": [],
+        "rendered": null,
+    })
+}
+
+fn level(severity: &DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "error",
+        DiagnosticSeverity::Warning => "warning",
+        // rustc reports informational messages as notes
+        DiagnosticSeverity::Info => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TranspilerError;
+
+    #[test]
+    fn test_json_diagnostic_shape() {
+        let mut collector = ErrorCollector::new();
+        let span = galvan_ast::Span {
+            range: (4, 10),
+            start: galvan_ast::Point { row: 0, col: 4 },
+            end: galvan_ast::Point { row: 0, col: 10 },
+        };
+        collector.suggest_similar_identifier("countr", &["counter".to_string()], Some(span.into()));
+
+        let rendered = collector.to_json_lines();
+        assert_eq!(rendered.lines().count(), 1);
+        let value: Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["$message_type"], "diagnostic");
+        assert_eq!(value["level"], "error");
+        assert_eq!(value["code"]["code"], "G0002");
+        assert_eq!(value["code"]["explanation"], explain("G0002").unwrap());
+        assert_eq!(value["spans"][0]["byte_start"], 4);
+        assert_eq!(value["spans"][0]["column_start"], 5);
+        assert_eq!(value["spans"][0]["line_start"], 1);
+
+        let help = &value["children"][0];
+        assert_eq!(help["level"], "help");
+        assert_eq!(help["spans"][0]["suggested_replacement"], "counter");
+        assert_eq!(
+            help["spans"][0]["suggestion_applicability"],
+            "MachineApplicable"
+        );
+    }
+
+    #[test]
+    fn test_json_diagnostic_without_span() {
+        let mut collector = ErrorCollector::new();
+        collector.error(TranspilerError::TypeInferenceFailure);
+
+        let value: Value = serde_json::from_str(&collector.to_json_lines()).unwrap();
+        assert_eq!(value["spans"], json!([]));
+        assert_eq!(value["children"], json!([]));
+        assert_eq!(value["code"]["code"], "G0008");
+    }
+}
@@ -18,24 +18,62 @@
 use galvan_ast::{
     ArithmeticOperator, BitwiseOperator, CmdSignature, ComparisonOperator, DeclModifier,
     FnSignature, Ident, LogicalOperator, Ownership, RangeOperator, Span, StringLiteral,
-    ToplevelItem, TypeDecl, TypeElement, TypeIdent, UseDecl, UsePath,
+    ToplevelItem, TraitDecl, TypeDecl, TypeElement, TypeIdent, UnaryOperator, UseDecl, UsePath,
 };
-use galvan_files::Source;
+use galvan_files::{ModulePath, Source};
 
 /// A fully typechecked Galvan program.
 #[derive(Debug)]
 pub struct HirModule {
     pub uses: Vec<ToplevelItem<UseDecl>>,
     pub types: Vec<ToplevelItem<TypeDecl>>,
+    pub traits: Vec<ToplevelItem<TraitDecl>>,
     pub functions: Vec<HirFunction>,
     pub tests: Vec<HirTest>,
     pub main: Option<HirMain>,
     pub cmds: Vec<HirCmd>,
+    /// Anonymous union types used anywhere in the module, each generated as
+    /// a Rust enum
+    pub unions: Vec<HirUnion>,
+    /// Operators used on struct types, each generated as a member-wise
+    /// `std::ops` impl
+    pub operators: Vec<HirOperatorImpl>,
+    /// All modules of the crate, each preceded by its parent
+    pub modules: Vec<ModulePath>,
+}
+
+/// An arithmetic operator derived for a struct whose fields all support it,
+/// e.g. `Vec2 + Vec2` adding the fields member-wise
+#[derive(Clone, Debug, PartialEq)]
+pub struct HirOperatorImpl {
+    pub ty: TypeIdent,
+    pub operator: ArithmeticOperator,
+    /// The module declaring the struct
+    pub module: ModulePath,
+}
+
+/// The enum generated for an anonymous union type such as `Int | String`.
+///
+/// Unions with the same members share one enum regardless of member order.
+#[derive(Clone, Debug)]
+pub struct HirUnion {
+    pub ident: TypeIdent,
+    pub variants: Vec<HirUnionVariant>,
+}
+
+#[derive(Clone, Debug)]
+pub struct HirUnionVariant {
+    /// Variant name, also used as the case in `match` patterns
+    pub case: TypeIdent,
+    pub ty: TypeElement,
 }
 
 #[derive(Debug)]
 pub struct HirFunction {
     pub signature: FnSignature,
+    /// The function returns a borrow of its `self` receiver (`&T`) instead
+    /// of an owned value
+    pub returns_borrow: bool,
     pub body: HirBlock,
     pub source: Source,
     pub span: Span,
@@ -108,20 +146,51 @@ pub enum HirStatement {
     Expression(HirExpression),
     Return(HirReturn),
     Throw(HirThrow),
-    Break(Span),
-    Continue(Span),
+    Break(HirBreak),
+    Continue(HirContinue),
+}
+
+/// `break`, optionally leaving an outer labeled loop or producing the value
+/// of a `loop` expression
+#[derive(Clone, Debug)]
+pub struct HirBreak {
+    pub label: Option<Ident>,
+    pub value: Option<HirExpression>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct HirContinue {
+    pub label: Option<Ident>,
+    pub span: Span,
 }
 
 #[derive(Clone, Debug)]
 pub struct HirDeclaration {
     pub modifier: DeclModifier,
-    pub identifier: Ident,
-    /// Annotated or inferred type of the declared variable
+    pub pattern: HirDeclarationPattern,
+    /// Annotated or inferred type of the declared value
     pub ty: TypeElement,
     pub value: Option<HirExpression>,
     pub span: Span,
 }
 
+#[derive(Clone, Debug)]
+pub enum HirDeclarationPattern {
+    Ident(Ident),
+    Wildcard,
+    Tuple(Vec<HirDeclarationPattern>),
+    Struct(HirStructDestructuring),
+}
+
+#[derive(Clone, Debug)]
+pub struct HirStructDestructuring {
+    pub ty: TypeIdent,
+    pub fields: Vec<(Ident, HirDeclarationPattern)>,
+    /// Whether some fields of the struct are not destructured
+    pub has_rest: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct HirAssignment {
     pub target: HirExpression,
@@ -214,6 +283,8 @@ impl HirExpression {
                 Adjustment::WrapErr => Ownership::UniqueOwned,
                 Adjustment::LockRef => Ownership::MutBorrowed,
                 Adjustment::ArcClone => Ownership::UniqueOwned,
+                Adjustment::IntoUnion => Ownership::UniqueOwned,
+                Adjustment::AsStr => Ownership::Borrowed,
             })
             .unwrap_or(self.ownership)
     }
@@ -244,6 +315,10 @@ pub enum Adjustment {
     LockRef,
     /// `::std::sync::Arc::clone(&expr)` - share a `ref` variable
     ArcClone,
+    /// `expr.into()` - wrap a member value in the enum of a union type
+    IntoUnion,
+    /// `expr.as_str()` - match a string against string literal patterns
+    AsStr,
 }
 
 #[derive(Clone, Debug)]
@@ -252,6 +327,7 @@ pub enum HirExpressionKind {
     ElseUnwrap(Box<HirElseUnwrap>),
     Try(Box<HirTry>),
     For(Box<HirFor>),
+    Loop(Box<HirLoop>),
     Match(Box<HirMatch>),
     Assert(Box<HirAssert>),
     Print(HirPrint),
@@ -272,7 +348,10 @@ pub enum HirExpressionKind {
     Comparison(Box<HirBinary<ComparisonOperator>>),
     CollectionOp(Box<HirBinary<CollectionOperator>>),
     Range(Box<HirBinary<RangeOperator>>),
+    Unary(Box<HirUnary>),
     Index(Box<HirIndex>),
+    /// `base[start:end]` on arrays and strings
+    Slice(Box<HirSlice>),
     /// Error propagation with the `!` postfix operator, transpiled to `?`
     Yeet(Box<HirExpression>),
     Group(Box<HirExpression>),
@@ -280,15 +359,27 @@ pub enum HirExpressionKind {
     Error(String),
 }
 
-/// Collection infix operators (`++`, `--`, `in`). The concrete generated shape
-/// depends on the stored operand types.
+/// Collection infix operators (`++`, `--`, `**`, `in`). The concrete
+/// generated shape depends on the stored operand types.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CollectionOperator {
     Concat(ConcatKind),
-    Remove,
+    Remove(RemoveKind),
+    /// Repeats a string or array; the right-hand side is an integer count
+    Repeat,
     Contains,
 }
 
+/// Shape of a `--` removal, decided by the typechecker from the operand
+/// types. For dictionaries the removed values are keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoveKind {
+    /// The right-hand side is a single element (or key)
+    Element,
+    /// The right-hand side is an array or set of elements (or keys)
+    Collection,
+}
+
 /// Shape of a `++` concatenation, decided by the typechecker from the
 /// operand types.
 ///
@@ -374,6 +465,28 @@ pub struct HirFor {
     /// `Some(element_type)` when the loop is used as an expression and
     /// collects the value of each iteration into a vector
     pub collect: Option<TypeElement>,
+    pub label: Option<Ident>,
+}
+
+/// `loop`, `while cond` and `while try opt |x|`
+#[derive(Clone, Debug)]
+pub struct HirLoop {
+    pub kind: HirLoopKind,
+    pub body: HirBlock,
+    pub label: Option<Ident>,
+}
+
+#[derive(Clone, Debug)]
+pub enum HirLoopKind {
+    /// Runs until a `break`; the only loop that can produce a value
+    Infinite,
+    While(HirExpression),
+    /// Runs while the condition is `Some` or `Ok`, binding the unwrapped value
+    WhileTry {
+        condition: HirExpression,
+        kind: TryKind,
+        bindings: Vec<Ident>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -399,6 +512,7 @@ pub struct HirMatch {
 #[derive(Clone, Debug)]
 pub struct HirMatchArm {
     pub pattern: HirMatchPattern,
+    pub guard: Option<HirExpression>,
     pub body: HirBlock,
 }
 
@@ -406,6 +520,17 @@ pub struct HirMatchArm {
 pub enum HirMatchPattern {
     Wildcard,
     EnumVariant(HirEnumMatchPattern),
+    Literal(HirLiteral),
+    Range(HirMatchRange),
+    Tuple(Vec<HirMatchBindingPattern>),
+    Alternatives(Vec<HirMatchPattern>),
+}
+
+#[derive(Clone, Debug)]
+pub struct HirMatchRange {
+    pub start: HirLiteral,
+    pub end: HirLiteral,
+    pub inclusive: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -432,6 +557,7 @@ pub struct HirNamedMatchBinding {
 pub enum HirMatchBindingPattern {
     Binding(Ident),
     Wildcard,
+    Pattern(Box<HirMatchPattern>),
 }
 
 #[derive(Clone, Debug)]
@@ -593,6 +719,14 @@ pub struct HirBinary<Op> {
     pub rhs: HirExpression,
 }
 
+/// Prefix operator on a single operand. `not` and bitwise not both render
+/// as Rust's `!`.
+#[derive(Clone, Debug)]
+pub struct HirUnary {
+    pub operator: UnaryOperator,
+    pub operand: HirExpression,
+}
+
 /// Index access `base[index]`. Whether the index is borrowed depends on the
 /// stored type of `base` (dictionaries and sets index by reference).
 #[derive(Clone, Debug)]
@@ -600,3 +734,14 @@ pub struct HirIndex {
     pub base: HirExpression,
     pub index: HirExpression,
 }
+
+/// Slice of an array or string. Bounds are element positions with the
+/// end excluded, like `start..<end`; a missing bound extends to the edge.
+/// Bounds past the end are clamped to the length and a slice ending before
+/// its start is empty, for strings and arrays alike.
+#[derive(Clone, Debug)]
+pub struct HirSlice {
+    pub base: HirExpression,
+    pub start: Option<HirExpression>,
+    pub end: Option<HirExpression>,
+}
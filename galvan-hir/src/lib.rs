@@ -7,6 +7,8 @@ pub mod hir;
 pub mod mapping;
 pub mod typecheck;
 
-pub use error::{Diagnostic, DiagnosticSeverity, ErrorCollector, TranspilerError};
+pub use error::{
+    Diagnostic, DiagnosticSeverity, ErrorCollector, TranspilerError, TranspilerWarning,
+};
 pub use hir::*;
-pub use typecheck::typecheck;
+pub use typecheck::{typecheck, typecheck_with};
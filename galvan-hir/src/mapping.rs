@@ -2,7 +2,7 @@ use galvan_ast::{TypeElement, TypeIdent};
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Mapping {
     pub types: HashMap<TypeIdent, RustType>,
 }
@@ -47,6 +47,7 @@ impl Mapping {
             TypeElement::Generic(_) => false, // Generic types are not copy by default
             TypeElement::Parametric(_) => false, // Parametric types are not copy by default
             TypeElement::Closure(_) => false,
+            TypeElement::Union(_) => false, // Generated union enums only derive Clone
             TypeElement::Void(_) => true,
             TypeElement::Infer(_) => false,
             TypeElement::Never(_) => false,
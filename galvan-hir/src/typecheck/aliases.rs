@@ -0,0 +1,97 @@
+//! Type aliases such as `type Human = Person` or `type Inventory = {String: Int}`.
+//!
+//! Aliases are transparent: wherever the checker needs the structure of a
+//! type (its fields, member functions, elements or keys), a plain type naming
+//! an alias is replaced by the aliased type, following chains of aliases.
+//! Aliases with generic parameters, e.g. `type Boxed = Container<t>`, are
+//! instantiated with the arguments of a parametric type like `Boxed<Int>`.
+//! Cyclic aliases are reported once and otherwise left unresolved.
+
+use std::collections::HashSet;
+
+use galvan_ast::{FnDecl, Ident, SegmentedAsts, ToplevelItem, TypeDecl, TypeElement, TypeIdent};
+use galvan_resolver::Lookup;
+
+use crate::error::TranspilerError;
+
+use super::expr::receiver_type_ident;
+use super::generics::Substitution;
+use super::Checker;
+
+impl<'a> Checker<'a> {
+    /// Reports aliases whose definition refers back to themselves
+    pub(crate) fn check_aliases(&mut self, asts: &SegmentedAsts) {
+        for decl in &asts.types {
+            let TypeDecl::Alias(alias) = &decl.item else {
+                continue;
+            };
+            self.enter_module(&decl.source);
+            let visited = HashSet::from([alias.ident.clone()]);
+            // Aliases that only lead into a cycle are reported by its members
+            let cycle = self.follow_aliases(&alias.r#type, visited);
+            if matches!(cycle, Err(repeated) if repeated == alias.ident) {
+                self.errors.error_with_suggestion(
+                    TranspilerError::CircularDependency,
+                    Some(alias.span.into()),
+                    format!("type alias `{}` refers to itself", alias.ident),
+                );
+            }
+        }
+    }
+
+    /// The type an alias stands for, or the type itself when it does not name
+    /// an alias. Cyclic aliases are left unresolved.
+    pub(crate) fn resolve_alias(&self, ty: &TypeElement) -> TypeElement {
+        self.follow_aliases(ty, HashSet::new())
+            .unwrap_or_else(|_| ty.clone())
+    }
+
+    /// Follows a chain of aliases starting at `ty`. Fails with the first
+    /// alias that is reached twice.
+    fn follow_aliases(
+        &self,
+        ty: &TypeElement,
+        mut visited: HashSet<TypeIdent>,
+    ) -> Result<TypeElement, TypeIdent> {
+        let mut current = ty.clone();
+        loop {
+            let (ident, args) = match &current {
+                TypeElement::Plain(plain) => (&plain.ident, &[][..]),
+                TypeElement::Parametric(parametric) => {
+                    (&parametric.base_type, parametric.type_args.as_slice())
+                }
+                _ => break,
+            };
+            let Some(decl) = self.lookup.resolve_type(ident) else {
+                break;
+            };
+            let TypeDecl::Alias(alias) = &decl.item else {
+                break;
+            };
+            if !visited.insert(ident.clone()) {
+                return Err(ident.clone());
+            }
+            let mut substitution = Substitution::default();
+            substitution.bind_all(&decl.item.generic_params(), args);
+            current = substitution.apply(&alias.r#type);
+        }
+        Ok(current)
+    }
+
+    /// Resolves a member function declared on the receiver type, or on the
+    /// type its alias stands for
+    pub(crate) fn resolve_member_function(
+        &self,
+        receiver_ty: &TypeElement,
+        name: &Ident,
+        labels: &[&str],
+    ) -> Option<&'a ToplevelItem<FnDecl>> {
+        let lookup = self.lookup;
+        let aliased = self.resolve_alias(receiver_ty);
+        let function = [receiver_ty, &aliased]
+            .into_iter()
+            .filter_map(receiver_type_ident)
+            .find_map(|receiver| lookup.resolve_function(Some(&receiver), name, labels));
+        function
+    }
+}
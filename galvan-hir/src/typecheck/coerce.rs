@@ -1,8 +1,10 @@
 use galvan_ast::{Ownership, TypeElement};
 
 use crate::builtins::{CheckBuiltins, IsSame};
+use crate::error::TranspilerWarning;
 use crate::hir::{Adjustment, ConcatKind, HirExpression, HirExpressionKind, HirLiteral};
 
+use super::union::{union_member, union_members};
 use super::Checker;
 
 /// What the surrounding context expects of an expression.
@@ -53,12 +55,16 @@ impl Expected {
 }
 
 /// Checks whether two types are compatible, treating `Infer`, generic type
-/// parameters and the `__Number` intrinsic as wildcards (recursively)
+/// parameters and the `__Number` intrinsic as wildcards (recursively).
+///
+/// Calls instantiate generic parameters through a
+/// [`Substitution`](super::generics::Substitution) before checking their
+/// arguments, so only parameters that could not be bound reach the wildcard.
 pub fn types_compatible(expected: &TypeElement, actual: &TypeElement) -> bool {
     match (expected, actual) {
         (TypeElement::Infer(_), _) | (_, TypeElement::Infer(_)) => true,
         (TypeElement::Never(_), _) | (_, TypeElement::Never(_)) => true,
-        // Generic type parameters unify with any type
+        // Unbound generic type parameters unify with any type
         (TypeElement::Generic(_), _) | (_, TypeElement::Generic(_)) => true,
         (expected, actual) if expected.is_number() || actual.is_number() => {
             // The number intrinsic is compatible with every plain type
@@ -100,6 +106,23 @@ pub fn types_compatible(expected: &TypeElement, actual: &TypeElement) -> bool {
                     .all(|(a, b)| types_compatible(a, b))
                 && types_compatible(&a.return_ty, &b.return_ty)
         }
+        (TypeElement::Parametric(a), TypeElement::Parametric(b)) => {
+            a.base_type == b.base_type
+                && a.type_args.len() == b.type_args.len()
+                && a.type_args
+                    .iter()
+                    .zip(&b.type_args)
+                    .all(|(a, b)| types_compatible(a, b))
+        }
+        // A generic type without type arguments leaves them to inference
+        (TypeElement::Plain(plain), TypeElement::Parametric(parametric))
+        | (TypeElement::Parametric(parametric), TypeElement::Plain(plain)) => {
+            plain.ident == parametric.base_type
+        }
+        (TypeElement::Union(a), TypeElement::Union(b)) => {
+            let (a, b) = (union_members(a), union_members(b));
+            a.len() == b.len() && a.iter().zip(&b).all(|(a, b)| types_compatible(a, b))
+        }
         (expected, actual) => expected.is_same(actual),
     }
 }
@@ -139,7 +162,10 @@ pub(crate) fn concat_kind(lhs: &TypeElement, rhs: &TypeElement) -> ConcatKind {
 /// `true` when the value type matches a concrete (non-wildcard) element type,
 /// meaning a collection-typed right-hand side is appended as a single element
 /// (e.g. pushing a `[Int]` row into a `[[Int]]` matrix)
-fn value_matches_concrete_element(element_ty: &TypeElement, value_ty: &TypeElement) -> bool {
+pub(super) fn value_matches_concrete_element(
+    element_ty: &TypeElement,
+    value_ty: &TypeElement,
+) -> bool {
     !matches!(element_ty, TypeElement::Infer(_) | TypeElement::Generic(_))
         && types_compatible(element_ty, value_ty)
 }
@@ -173,12 +199,23 @@ impl Checker<'_> {
             return expr;
         }
 
-        match (&expected.ty, &expr.ty) {
+        // Aliases are interchangeable with the types they stand for
+        let expected_ty = self.resolve_alias(&expected.ty);
+        let actual_ty = self.resolve_alias(&expr.ty);
+        match (&expected_ty, &actual_ty) {
             // Unknown actual type: only reconcile ownership
             (_, TypeElement::Infer(_)) => self.adjust_ownership(expr, expected.ownership),
             (expected_ty, actual) if types_compatible(expected_ty, actual) => {
                 self.adjust_ownership(expr, expected.ownership)
             }
+            // Wrap member values in the enum generated for the union
+            (TypeElement::Union(union), actual)
+                if union_member(&union_members(union), actual).is_some() =>
+            {
+                self.register_union(union);
+                let expr = self.ensure_owned(expr).adjusted(Adjustment::IntoUnion);
+                self.adjust_ownership(expr, expected.ownership)
+            }
             // Auto-wrap values in `Some` when an optional is expected
             (TypeElement::Optional(some), actual)
                 if types_compatible(&some.inner, actual) || actual.is_number() =>
@@ -204,14 +241,22 @@ impl Checker<'_> {
                 expr.adjusted(Adjustment::WrapErr)
             }
             (TypeElement::Result(_), actual) => {
-                self.errors
-                    .warning(format!("Wrapping non-matching type {} in Ok", actual), None);
+                self.errors.warning(
+                    TranspilerWarning::ImplicitWrap {
+                        ty: actual.to_string(),
+                        wrapper: "Ok".to_string(),
+                    },
+                    None,
+                );
                 let expr = self.ensure_owned(expr);
                 expr.adjusted(Adjustment::WrapOk)
             }
             (TypeElement::Optional(_), actual) => {
                 self.errors.warning(
-                    format!("Wrapping non-matching type {} in Some", actual),
+                    TranspilerWarning::ImplicitWrap {
+                        ty: actual.to_string(),
+                        wrapper: "Some".to_string(),
+                    },
                     None,
                 );
                 let expr = self.ensure_owned(expr);
@@ -220,7 +265,10 @@ impl Checker<'_> {
             (_, actual) if actual.is_number() => expr,
             (expected_ty, actual) => {
                 self.errors.warning(
-                    format!("Type mismatch: expected {}, found {}", expected_ty, actual),
+                    TranspilerWarning::TypeMismatch {
+                        expected: expected_ty.to_string(),
+                        found: actual.to_string(),
+                    },
                     Some(expr.span.into()),
                 );
                 self.adjust_ownership(expr, expected.ownership)
@@ -262,7 +310,7 @@ impl Checker<'_> {
             (MutBorrowed, MutBorrowed) => expr,
             (MutBorrowed, Borrowed) => {
                 self.errors.warning(
-                    "Cannot mutably borrow an immutably borrowed value".to_string(),
+                    TranspilerWarning::MutBorrowOfBorrowed,
                     Some(expr.span.into()),
                 );
                 expr
@@ -324,7 +372,8 @@ impl Checker<'_> {
     }
 
     /// Coercion used for arguments to functions with *unknown* signatures
-    /// (e.g. methods of the Rust standard library). Since no parameter types
+    /// (e.g. Rust methods missing from
+    /// [`std_method`](crate::builtins::std_method)). Since no parameter types
     /// are available, this uses a heuristic over the expression kind:
     /// temporaries produced by calls and string literals are borrowed, shared
     /// locals are borrowed, everything else is passed through.
@@ -338,6 +387,12 @@ impl Checker<'_> {
                 UniqueOwned | Borrowed | MutBorrowed => expr,
             },
             HirExpressionKind::Closure(_) => expr,
+            // Calls returning a borrow already produce a reference
+            HirExpressionKind::FunctionCall(_) | HirExpressionKind::MethodCall(_)
+                if expr.ownership == Borrowed =>
+            {
+                expr
+            }
             HirExpressionKind::FunctionCall(_)
             | HirExpressionKind::MethodCall(_)
             | HirExpressionKind::Literal(HirLiteral::String(_)) => {
@@ -0,0 +1,155 @@
+//! Collection operators beyond concatenation.
+//!
+//! `--` removes an element, or every element of an array or set, from an
+//! array, set or dictionary (dictionaries remove by key). `**` repeats a
+//! string or an array an integer number of times. `base[start:end]` slices
+//! arrays and strings by element position, with the end excluded like in
+//! `start..<end`.
+
+use galvan_ast::{Expression, Ownership, SliceExpression, Span, TypeElement};
+
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::coerce::{types_compatible, value_matches_concrete_element, Expected};
+use super::expr::is_integer_type;
+use super::Checker;
+
+/// Classifies a `--` removal by its operand types: a collection-typed
+/// right-hand side removes all of its elements unless it is itself an
+/// element of the left-hand side (e.g. removing a row from a matrix)
+fn remove_kind(element_ty: &TypeElement, rhs: &TypeElement) -> RemoveKind {
+    if matches!(rhs, TypeElement::Array(_) | TypeElement::Set(_))
+        && !value_matches_concrete_element(element_ty, rhs)
+    {
+        RemoveKind::Collection
+    } else {
+        RemoveKind::Element
+    }
+}
+
+/// The elements removed by a `--`: values for arrays and sets, keys for
+/// dictionaries
+fn removed_element_type(lhs: &TypeElement) -> Option<TypeElement> {
+    match lhs {
+        TypeElement::Array(array) => Some(array.elements.clone()),
+        TypeElement::Set(set) => Some(set.elements.clone()),
+        TypeElement::Dictionary(dict) => Some(dict.key.clone()),
+        TypeElement::OrderedDictionary(dict) => Some(dict.key.clone()),
+        TypeElement::Infer(_) => Some(TypeElement::infer()),
+        _ => None,
+    }
+}
+
+fn is_string(ty: &TypeElement) -> bool {
+    matches!(ty, TypeElement::Plain(plain) if plain.ident.as_str() == "String")
+}
+
+impl Checker<'_> {
+    /// Lowers the right-hand side of `lhs -- rhs` and decides its shape
+    pub(crate) fn lower_remove(
+        &mut self,
+        lhs: &HirExpression,
+        rhs: HirExpression,
+        span: Span,
+    ) -> (CollectionOperator, HirExpression) {
+        let Some(element_ty) = removed_element_type(&lhs.ty) else {
+            self.errors.error_with_span(
+                TranspilerError::InvalidOperationOnType {
+                    operation: format!("`--` on {}", lhs.ty),
+                    allowed_types: "arrays, sets and dictionaries".to_string(),
+                },
+                Some(span.into()),
+            );
+            return (CollectionOperator::Remove(RemoveKind::Element), rhs);
+        };
+
+        let kind = remove_kind(&element_ty, &rhs.ty);
+        let removed_ty = match (kind, &rhs.ty) {
+            (RemoveKind::Collection, TypeElement::Array(array)) => &array.elements,
+            (RemoveKind::Collection, TypeElement::Set(set)) => &set.elements,
+            _ => &rhs.ty,
+        };
+        if !types_compatible(&element_ty, removed_ty) {
+            self.errors.error_with_span(
+                TranspilerError::TypeMismatch {
+                    expected: element_ty.to_string(),
+                    found: removed_ty.to_string(),
+                },
+                Some(rhs.span.into()),
+            );
+        }
+
+        let rhs = match kind {
+            // Compared against or removed from the collection by value
+            RemoveKind::Element => self.coerce(rhs, &Expected::owned(element_ty)),
+            // Only iterated, borrowed inside the generated pattern
+            RemoveKind::Collection => rhs,
+        };
+        (CollectionOperator::Remove(kind), rhs)
+    }
+
+    /// Validates the operands of `lhs ** count`
+    pub(crate) fn check_repeat(&mut self, lhs: &HirExpression, count: &HirExpression, span: Span) {
+        if !matches!(lhs.ty, TypeElement::Array(_) | TypeElement::Infer(_)) && !is_string(&lhs.ty) {
+            self.errors.error_with_span(
+                TranspilerError::InvalidOperationOnType {
+                    operation: format!("`**` on {}", lhs.ty),
+                    allowed_types: "strings and arrays".to_string(),
+                },
+                Some(span.into()),
+            );
+        }
+        if !is_integer_type(&count.ty) {
+            self.errors.error_with_span(
+                TranspilerError::TypeMismatch {
+                    expected: "an integer repetition count".to_string(),
+                    found: count.ty.to_string(),
+                },
+                Some(count.span.into()),
+            );
+        }
+    }
+
+    /// Lowers `base[start:end]`. The slice is a new owned array or string.
+    pub(crate) fn lower_slice(&mut self, slice: &SliceExpression, span: Span) -> HirExpression {
+        let (base, _) = self.lower_access_base(&slice.base);
+        if !matches!(base.ty, TypeElement::Array(_) | TypeElement::Infer(_)) && !is_string(&base.ty)
+        {
+            self.errors.error_with_span(
+                TranspilerError::InvalidOperationOnType {
+                    operation: format!("slicing {}", base.ty),
+                    allowed_types: "strings and arrays".to_string(),
+                },
+                Some(span.into()),
+            );
+        }
+
+        let start = slice
+            .start
+            .as_ref()
+            .map(|start| self.lower_slice_bound(start));
+        let end = slice.end.as_ref().map(|end| self.lower_slice_bound(end));
+        let ty = base.ty.clone();
+        HirExpression::new(
+            HirExpressionKind::Slice(Box::new(HirSlice { base, start, end })),
+            ty,
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+
+    fn lower_slice_bound(&mut self, bound: &Expression) -> HirExpression {
+        let bound = self.lower_expression(bound, &Expected::free());
+        if !is_integer_type(&bound.ty) {
+            self.errors.error_with_span(
+                TranspilerError::TypeMismatch {
+                    expected: "an integer slice bound".to_string(),
+                    found: bound.ty.to_string(),
+                },
+                Some(bound.span.into()),
+            );
+        }
+        bound
+    }
+}
@@ -0,0 +1,178 @@
+//! Destructuring declarations.
+//!
+//! `let (a, b) = pair` binds the elements of a tuple and
+//! `let Point(x:, y: vertical) = point` binds the fields of a struct. The
+//! declared value is owned, so the bound variables follow the same ownership
+//! rules as variables bound by `match` arms.
+
+use galvan_ast::{DeclModifier, Declaration, DeclarationPattern, Ident, TypeDecl, TypeElement};
+use galvan_resolver::Lookup;
+
+use crate::builtins::CheckBuiltins;
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::expr::match_binding_ownership;
+use super::generics::Substitution;
+use super::{Checker, Variable};
+
+impl Checker<'_> {
+    /// Lowers the pattern of a destructuring declaration of a value of type
+    /// `ty` and declares the variables it binds
+    pub(crate) fn lower_destructuring(
+        &mut self,
+        declaration: &Declaration,
+        ty: &TypeElement,
+    ) -> HirDeclarationPattern {
+        if declaration.decl_modifier == DeclModifier::Ref {
+            self.errors.error_with_span(
+                TranspilerError::InvalidModifier {
+                    modifier: "ref".to_string(),
+                    context: "destructuring declarations".to_string(),
+                },
+                Some(declaration.span.into()),
+            );
+        }
+
+        let mut bindings = Vec::new();
+        let pattern = self.lower_declaration_pattern(&declaration.pattern, ty, &mut bindings);
+        for (ident, ty) in bindings {
+            let ownership = match_binding_ownership(self.is_copy(&ty), ty.is_infer());
+            self.scopes.declare(Variable {
+                ident,
+                modifier: declaration.decl_modifier,
+                ty,
+                ownership,
+            });
+        }
+
+        pattern
+    }
+
+    fn lower_declaration_pattern(
+        &mut self,
+        pattern: &DeclarationPattern,
+        ty: &TypeElement,
+        bindings: &mut Vec<(Ident, TypeElement)>,
+    ) -> HirDeclarationPattern {
+        match pattern {
+            DeclarationPattern::Ident(ident) => {
+                bindings.push((ident.clone(), ty.clone()));
+                HirDeclarationPattern::Ident(ident.clone())
+            }
+            DeclarationPattern::Wildcard(_) => HirDeclarationPattern::Wildcard,
+            DeclarationPattern::Tuple(tuple) => {
+                let count = tuple.elements.len();
+                let element_types = match ty {
+                    TypeElement::Tuple(tuple_ty) if tuple_ty.elements.len() == count => {
+                        tuple_ty.elements.clone()
+                    }
+                    TypeElement::Infer(_) => vec![TypeElement::infer(); count],
+                    _ => {
+                        self.errors.error_with_span(
+                            TranspilerError::TypeMismatch {
+                                expected: ty.to_string(),
+                                found: format!("tuple with {count} elements"),
+                            },
+                            Some(tuple.span.into()),
+                        );
+                        vec![TypeElement::infer(); count]
+                    }
+                };
+
+                HirDeclarationPattern::Tuple(
+                    tuple
+                        .elements
+                        .iter()
+                        .zip(&element_types)
+                        .map(|(element, ty)| self.lower_declaration_pattern(element, ty, bindings))
+                        .collect(),
+                )
+            }
+            DeclarationPattern::Struct(destructuring) => {
+                let (matches_type, type_args) = match ty {
+                    TypeElement::Plain(plain) => (plain.ident == destructuring.ty, None),
+                    TypeElement::Parametric(parametric) => (
+                        parametric.base_type == destructuring.ty,
+                        Some(&parametric.type_args),
+                    ),
+                    TypeElement::Infer(_) => (true, None),
+                    _ => (false, None),
+                };
+                if !matches_type {
+                    self.errors.error_with_span(
+                        TranspilerError::TypeMismatch {
+                            expected: ty.to_string(),
+                            found: destructuring.ty.to_string(),
+                        },
+                        Some(destructuring.span.into()),
+                    );
+                }
+
+                let lookup = self.lookup;
+                let decl = lookup
+                    .resolve_type(&destructuring.ty)
+                    .map(|decl| &decl.item);
+                let mut substitution = Substitution::default();
+                if let (Some(decl), Some(type_args)) = (decl, type_args) {
+                    substitution.bind_all(&decl.generic_params(), type_args);
+                }
+                let members = match decl {
+                    Some(TypeDecl::Struct(def)) => Some(&def.members),
+                    _ => {
+                        self.errors.error_with_span(
+                            TranspilerError::InvalidOperationOnType {
+                                operation: format!("destructuring {}", destructuring.ty),
+                                allowed_types: "structs and tuples".to_string(),
+                            },
+                            Some(destructuring.span.into()),
+                        );
+                        None
+                    }
+                };
+
+                let mut fields = Vec::with_capacity(destructuring.fields.len());
+                for field in &destructuring.fields {
+                    let member = members.and_then(|members| {
+                        members.iter().find(|member| member.ident == field.field)
+                    });
+                    let field_ty = match member {
+                        Some(member) => substitution.apply(&member.r#type),
+                        None => {
+                            if members.is_some() {
+                                self.errors.error_with_span(
+                                    TranspilerError::MemberAccessError {
+                                        message: format!(
+                                            "Struct `{}` does not have field `{}`",
+                                            destructuring.ty, field.field
+                                        ),
+                                    },
+                                    Some(field.span.into()),
+                                );
+                            }
+                            TypeElement::infer()
+                        }
+                    };
+
+                    let binding = match &field.binding {
+                        Some(binding) => {
+                            self.lower_declaration_pattern(binding, &field_ty, bindings)
+                        }
+                        None => {
+                            bindings.push((field.field.clone(), field_ty));
+                            HirDeclarationPattern::Ident(field.field.clone())
+                        }
+                    };
+                    fields.push((field.field.clone(), binding));
+                }
+
+                let has_rest = members.is_some_and(|members| members.len() > fields.len());
+                HirDeclarationPattern::Struct(HirStructDestructuring {
+                    ty: destructuring.ty.clone(),
+                    fields,
+                    has_rest,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! Exhaustiveness and reachability of `match` arms.
+//!
+//! A `match` has to cover every value of the matched type. For enums and
+//! unions every variant has to be covered, either with an arm for the
+//! variant or with a wildcard, and variants matched with nested patterns
+//! have to be covered by the nested patterns together. `Bool` is covered by
+//! `true` and `false` arms, all other types need an irrefutable arm, since
+//! literals and ranges are never assumed to cover a whole type. Arms with a
+//! guard never count as covering anything. Arms that earlier arms already
+//! cover can never be taken and are reported as warnings.
+
+use galvan_ast::{Ident, MatchArm, Span, TypeDecl, TypeElement, TypeIdent};
+use galvan_resolver::Lookup;
+
+use crate::builtins::CheckBuiltins;
+use crate::error::{TranspilerError, TranspilerWarning};
+use crate::hir::*;
+
+use super::Checker;
+
+impl Checker<'_> {
+    /// Reports values of the matched type that no arm covers and arms that
+    /// are unreachable because earlier arms already cover them
+    pub(crate) fn check_match_coverage(
+        &mut self,
+        scrutinee_ty: &TypeElement,
+        target: Option<&TypeIdent>,
+        arms: &[MatchArm],
+        lowered: &[HirMatchArm],
+        span: Span,
+    ) {
+        let mut covered: Vec<&TypeIdent> = Vec::new();
+        let mut rows: Vec<Vec<CoveragePattern>> = Vec::new();
+        let mut exhaustive = false;
+        for (arm, lowered) in arms.iter().zip(lowered) {
+            let arm_span = arm.pattern.span();
+            if exhaustive {
+                self.errors.warning(
+                    TranspilerWarning::UnreachableMatchArm {
+                        reason: "all cases are already covered".to_string(),
+                    },
+                    Some(arm_span.into()),
+                );
+                continue;
+            }
+
+            // Guarded arms may be skipped, so they never cover anything
+            let guarded = lowered.guard.is_some();
+            let alternatives = match &lowered.pattern {
+                HirMatchPattern::Alternatives(alternatives) => alternatives.iter().collect(),
+                pattern => vec![pattern],
+            };
+            for pattern in alternatives {
+                if let HirMatchPattern::EnumVariant(variant) = pattern {
+                    if covered.contains(&&variant.case) {
+                        self.errors.warning(
+                            TranspilerWarning::UnreachableMatchArm {
+                                reason: format!("variant `{}` is already covered", variant.case),
+                            },
+                            Some(arm_span.into()),
+                        );
+                    } else if !guarded && covers_variant(&variant.arguments) {
+                        covered.push(&variant.case);
+                    }
+                }
+            }
+
+            if !guarded {
+                rows.push(vec![self.coverage_pattern(&lowered.pattern)]);
+                exhaustive = self.covers(&rows);
+            }
+        }
+
+        if exhaustive || scrutinee_ty.is_infer() {
+            return;
+        }
+
+        let variants = target.and_then(|target| Some((target, self.match_variants(target)?)));
+        let (ty, missing) = match variants {
+            Some((target, (ty, variants))) => {
+                let missing = variants
+                    .iter()
+                    .filter(|case| !self.covers_variant_case(&rows, target, case))
+                    .map(|variant| format!("`{variant}`"))
+                    .collect::<Vec<_>>();
+                (ty, missing)
+            }
+            None if is_bool(scrutinee_ty) => {
+                let missing = [true, false]
+                    .into_iter()
+                    .filter(|value| !self.covers_bool(&rows, *value))
+                    .map(|value| format!("`{value}`"))
+                    .collect::<Vec<_>>();
+                (scrutinee_ty.to_string(), missing)
+            }
+            None => (scrutinee_ty.to_string(), vec!["a `_` arm".to_string()]),
+        };
+        if missing.is_empty() {
+            return;
+        }
+        self.errors.error_with_span(
+            TranspilerError::NonExhaustiveMatch {
+                ty,
+                missing: missing.join(", "),
+            },
+            Some(span.into()),
+        );
+    }
+
+    /// Whether the rows of patterns together match every value. Each row
+    /// holds one pattern per matched value, starting with the first column.
+    fn covers(&self, rows: &[Vec<CoveragePattern>]) -> bool {
+        let Some(first) = rows.first() else {
+            return false;
+        };
+        if first.is_empty() {
+            return true;
+        }
+
+        let rows = expand_alternatives(rows);
+        let heads = rows.iter().map(|row| &row[0]).collect::<Vec<_>>();
+        if let Some(target) = heads.iter().find_map(|pattern| match pattern {
+            CoveragePattern::Variant { target, .. } => Some(target),
+            _ => None,
+        }) {
+            // Unknown targets are already reported when lowering the pattern
+            let Some((_, variants)) = self.match_variants(target) else {
+                return true;
+            };
+            return variants
+                .iter()
+                .all(|case| self.covers_variant_case(&rows, target, case));
+        }
+
+        if heads
+            .iter()
+            .any(|pattern| matches!(pattern, CoveragePattern::Bool(_)))
+        {
+            return [true, false]
+                .into_iter()
+                .all(|value| self.covers_bool(&rows, value));
+        }
+
+        if let Some(arity) = heads.iter().find_map(|pattern| match pattern {
+            CoveragePattern::Tuple(elements) => Some(elements.len()),
+            _ => None,
+        }) {
+            return self.covers(&specialize(&rows, |pattern| match pattern {
+                CoveragePattern::Tuple(_) | CoveragePattern::Wildcard => {
+                    Some(pattern.arguments(arity))
+                }
+                _ => None,
+            }));
+        }
+
+        // Literals and ranges never cover a whole type
+        self.covers(&specialize(&rows, |pattern| match pattern {
+            CoveragePattern::Wildcard => Some(Vec::new()),
+            _ => None,
+        }))
+    }
+
+    /// Whether the rows cover every value of one variant in their first
+    /// column
+    fn covers_variant_case(
+        &self,
+        rows: &[Vec<CoveragePattern>],
+        target: &TypeIdent,
+        case: &TypeIdent,
+    ) -> bool {
+        let arity = self.variant_fields(target, case).len();
+        self.covers(&specialize(rows, |pattern| match pattern {
+            CoveragePattern::Variant { case: other, .. } if other != case => None,
+            CoveragePattern::Variant { .. } | CoveragePattern::Wildcard => {
+                Some(pattern.arguments(arity))
+            }
+            _ => None,
+        }))
+    }
+
+    /// Whether the rows cover `value` in their first column
+    fn covers_bool(&self, rows: &[Vec<CoveragePattern>], value: bool) -> bool {
+        self.covers(&specialize(rows, |pattern| match pattern {
+            CoveragePattern::Bool(other) if *other != value => None,
+            CoveragePattern::Bool(_) | CoveragePattern::Wildcard => Some(Vec::new()),
+            _ => None,
+        }))
+    }
+
+    fn coverage_pattern(&self, pattern: &HirMatchPattern) -> CoveragePattern {
+        match pattern {
+            HirMatchPattern::Wildcard => CoveragePattern::Wildcard,
+            HirMatchPattern::EnumVariant(variant) => {
+                let fields = self.variant_fields(&variant.target, &variant.case);
+                let arguments = match &variant.arguments {
+                    HirMatchPatternArguments::None => vec![CoveragePattern::Wildcard; fields.len()],
+                    HirMatchPatternArguments::Tuple(bindings) => {
+                        let mut arguments = bindings
+                            .iter()
+                            .map(|binding| self.coverage_binding(binding))
+                            .collect::<Vec<_>>();
+                        arguments.resize(fields.len(), CoveragePattern::Wildcard);
+                        arguments
+                    }
+                    HirMatchPatternArguments::Named(bindings) => fields
+                        .iter()
+                        .map(|field| {
+                            bindings
+                                .iter()
+                                .find(|named| Some(&named.field) == field.as_ref())
+                                .map_or(CoveragePattern::Wildcard, |named| {
+                                    self.coverage_binding(&named.binding)
+                                })
+                        })
+                        .collect(),
+                };
+                CoveragePattern::Variant {
+                    target: variant.target.clone(),
+                    case: variant.case.clone(),
+                    arguments,
+                }
+            }
+            HirMatchPattern::Literal(HirLiteral::Boolean(value)) => CoveragePattern::Bool(*value),
+            HirMatchPattern::Literal(_) | HirMatchPattern::Range(_) => CoveragePattern::Refutable,
+            HirMatchPattern::Tuple(elements) => CoveragePattern::Tuple(
+                elements
+                    .iter()
+                    .map(|element| self.coverage_binding(element))
+                    .collect(),
+            ),
+            HirMatchPattern::Alternatives(alternatives) => CoveragePattern::Alternatives(
+                alternatives
+                    .iter()
+                    .map(|alternative| self.coverage_pattern(alternative))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn coverage_binding(&self, binding: &HirMatchBindingPattern) -> CoveragePattern {
+        match binding {
+            HirMatchBindingPattern::Binding(_) | HirMatchBindingPattern::Wildcard => {
+                CoveragePattern::Wildcard
+            }
+            HirMatchBindingPattern::Pattern(pattern) => self.coverage_pattern(pattern),
+        }
+    }
+
+    /// The field names of a variant of an enum or union. Union members are
+    /// matched like a variant with a single unnamed field.
+    fn variant_fields(&self, target: &TypeIdent, case: &TypeIdent) -> Vec<Option<Ident>> {
+        if self.unions.contains_key(target) {
+            return vec![None];
+        }
+
+        match self.lookup.resolve_type(target).map(|decl| &decl.item) {
+            Some(TypeDecl::Enum(enum_decl)) => enum_decl
+                .members
+                .iter()
+                .find(|member| member.ident == *case)
+                .map(|member| {
+                    member
+                        .fields
+                        .iter()
+                        .map(|field| field.name.clone())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+    /// The display name and variant names of a matched enum or union, or
+    /// `None` if the target is not a known enum or union
+    fn match_variants(&self, target: &TypeIdent) -> Option<(String, Vec<TypeIdent>)> {
+        if let Some(union) = self.unions.get(target) {
+            let members = union
+                .variants
+                .iter()
+                .map(|variant| variant.ty.to_string())
+                .collect::<Vec<_>>()
+                .join(" | ");
+            let cases = union
+                .variants
+                .iter()
+                .map(|variant| variant.case.clone())
+                .collect();
+            return Some((members, cases));
+        }
+
+        match &self.lookup.resolve_type(target)?.item {
+            TypeDecl::Enum(enum_decl) => Some((
+                target.to_string(),
+                enum_decl
+                    .members
+                    .iter()
+                    .map(|member| member.ident.clone())
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a variant pattern matches every value of its variant
+fn covers_variant(arguments: &HirMatchPatternArguments) -> bool {
+    match arguments {
+        HirMatchPatternArguments::None => true,
+        HirMatchPatternArguments::Tuple(bindings) => bindings.iter().all(is_irrefutable_binding),
+        HirMatchPatternArguments::Named(bindings) => bindings
+            .iter()
+            .all(|named| is_irrefutable_binding(&named.binding)),
+    }
+}
+
+/// Whether a pattern matches every value of the matched type without
+/// looking into variants
+fn is_irrefutable(pattern: &HirMatchPattern) -> bool {
+    match pattern {
+        HirMatchPattern::Wildcard => true,
+        HirMatchPattern::Tuple(elements) => elements.iter().all(is_irrefutable_binding),
+        HirMatchPattern::Alternatives(alternatives) => alternatives.iter().any(is_irrefutable),
+        HirMatchPattern::EnumVariant(_)
+        | HirMatchPattern::Literal(_)
+        | HirMatchPattern::Range(_) => false,
+    }
+}
+
+fn is_irrefutable_binding(binding: &HirMatchBindingPattern) -> bool {
+    match binding {
+        HirMatchBindingPattern::Binding(_) | HirMatchBindingPattern::Wildcard => true,
+        HirMatchBindingPattern::Pattern(pattern) => is_irrefutable(pattern),
+    }
+}
+
+/// A match pattern reduced to what matters for coverage
+#[derive(Clone, Debug)]
+enum CoveragePattern {
+    Wildcard,
+    Variant {
+        target: TypeIdent,
+        case: TypeIdent,
+        /// One pattern per field of the variant
+        arguments: Vec<CoveragePattern>,
+    },
+    Bool(bool),
+    Tuple(Vec<CoveragePattern>),
+    /// Literals and ranges other than `true` and `false`
+    Refutable,
+    Alternatives(Vec<CoveragePattern>),
+}
+
+impl CoveragePattern {
+    /// The patterns of the fields or elements, with wildcards for every
+    /// field of a wildcard
+    fn arguments(&self, arity: usize) -> Vec<CoveragePattern> {
+        match self {
+            CoveragePattern::Variant { arguments, .. } => arguments.clone(),
+            CoveragePattern::Tuple(elements) => elements.clone(),
+            _ => vec![CoveragePattern::Wildcard; arity],
+        }
+    }
+}
+
+/// Replaces rows whose first pattern is an alternative with one row per
+/// alternative
+fn expand_alternatives(rows: &[Vec<CoveragePattern>]) -> Vec<Vec<CoveragePattern>> {
+    let mut expanded = Vec::with_capacity(rows.len());
+    for row in rows {
+        match &row[0] {
+            CoveragePattern::Alternatives(alternatives) => {
+                let alternatives = alternatives
+                    .iter()
+                    .map(|alternative| {
+                        let mut row = row.clone();
+                        row[0] = alternative.clone();
+                        row
+                    })
+                    .collect::<Vec<_>>();
+                expanded.extend(expand_alternatives(&alternatives));
+            }
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+/// Keeps the rows whose first pattern matches a constructor and replaces
+/// that pattern with the patterns for the constructor's fields, as returned
+/// by `fields`
+fn specialize(
+    rows: &[Vec<CoveragePattern>],
+    fields: impl Fn(&CoveragePattern) -> Option<Vec<CoveragePattern>>,
+) -> Vec<Vec<CoveragePattern>> {
+    expand_alternatives(rows)
+        .into_iter()
+        .filter_map(|row| {
+            let mut specialized = fields(&row[0])?;
+            specialized.extend(row.into_iter().skip(1));
+            Some(specialized)
+        })
+        .collect()
+}
+
+fn is_bool(ty: &TypeElement) -> bool {
+    matches!(ty, TypeElement::Plain(plain) if plain.ident.as_str() == "Bool")
+}
@@ -3,18 +3,19 @@
 use galvan_ast::{
     BasicTypeItem, Closure, ClosureParameter, ClosureTypeItem, CollectionLiteral,
     ComparisonOperator, ConstructorCall, DeclModifier, DictLiteralElement, ElseExpression,
-    EnumConstructor, Expression, ExpressionKind, FunctionCall, FunctionCallArg, Ident,
-    InfixExpression, InfixOperation, Literal, MatchArm, MatchBindingPattern, MatchExpression,
-    MatchNamedPatternArg, MatchPattern, MatchPatternArg, MemberOperator, NeverTypeItem,
-    OptionalTypeItem, Ownership, Param, PostfixExpression, ResultTypeItem, Span, TypeDecl,
-    TypeElement, TypeIdent, UsePath,
+    EnumConstructor, Expression, ExpressionKind, FunctionCall, FunctionCallArg, GenericTypeItem,
+    Ident, InfixExpression, InfixOperation, Literal, MatchArm, MatchBindingPattern,
+    MatchExpression, MatchNamedPatternArg, MatchPattern, MatchPatternArg, MemberOperator,
+    NeverTypeItem, OptionalTypeItem, Ownership, Param, ParametricTypeItem, PostfixExpression,
+    RangeOperator, ResultTypeItem, Span, TypeDecl, TypeElement, TypeIdent, UsePath,
 };
 use galvan_resolver::Lookup;
 
-use crate::builtins::{CheckBuiltins, BORROWED_ITERATOR_FNS};
-use crate::error::{ErrorCollector, TranspilerError};
+use crate::builtins::{std_method, CheckBuiltins, BORROWED_ITERATOR_FNS};
+use crate::error::{TranspilerError, TranspilerWarning};
 use crate::hir::*;
 
+use super::generics::Substitution;
 use super::{concat_kind, types_compatible, Checker, Expected, Variable};
 
 impl Checker<'_> {
@@ -34,6 +35,7 @@ impl Checker<'_> {
             ExpressionKind::FunctionCall(call) => self.lower_function_call(call, expected, span),
             ExpressionKind::Infix(infix) => self.lower_infix(infix, expected, span),
             ExpressionKind::Postfix(postfix) => self.lower_postfix(postfix, span),
+            ExpressionKind::Unary(unary) => self.lower_unary(unary, span),
             ExpressionKind::Modified(modified) => {
                 self.errors.error_with_span(
                     TranspilerError::InvalidModifier {
@@ -63,6 +65,7 @@ impl Checker<'_> {
             ExpressionKind::Literal(literal) => self.lower_literal(literal, span),
             ExpressionKind::Ident(ident) => self.lower_variable_expression(ident, span),
             ExpressionKind::Closure(closure) => self.lower_closure(closure, expected, false, span),
+            ExpressionKind::Labeled(labeled) => self.lower_labeled(labeled, expected, span),
             ExpressionKind::Group(group) => {
                 if let Some(modifier) = group.modifier {
                     self.errors.error_with_span(
@@ -153,7 +156,17 @@ impl Checker<'_> {
     }
 
     fn lower_literal(&mut self, literal: &Literal, span: Span) -> HirExpression {
-        let (literal, ty) = match literal {
+        let (literal, ty) = self.lower_literal_value(literal);
+        HirExpression::new(
+            HirExpressionKind::Literal(literal),
+            ty,
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+
+    fn lower_literal_value(&mut self, literal: &Literal) -> (HirLiteral, TypeElement) {
+        match literal {
             Literal::BooleanLiteral(boolean) => {
                 (HirLiteral::Boolean(boolean.value), TypeElement::bool())
             }
@@ -186,14 +199,7 @@ impl Checker<'_> {
                     plain_type(TypeIdent::new("String")),
                 )
             }
-        };
-
-        HirExpression::new(
-            HirExpressionKind::Literal(literal),
-            ty,
-            Ownership::UniqueOwned,
-            span,
-        )
+        }
     }
 
     // ------------------------------------------------------------------
@@ -213,7 +219,9 @@ impl Checker<'_> {
                 "print" => return self.lower_print(PrintKind::Print, &call.arguments, span),
                 "debug" => return self.lower_print(PrintKind::Debug, &call.arguments, span),
                 "if" => return self.lower_if(call, expected, None, span),
-                "for" => return self.lower_for(call, expected, span),
+                "for" => return self.lower_for(call, expected, None, span),
+                "loop" => return self.lower_loop(call, expected, None, span),
+                "while" => return self.lower_while(call, None, span),
                 "try" => return self.lower_try(call, expected, None, span),
                 "assert" => return self.lower_assert(call, span),
                 name if BORROWED_ITERATOR_FNS.contains(&name) => {
@@ -271,37 +279,33 @@ impl Checker<'_> {
         arguments: &[FunctionCallArg],
         span: Span,
     ) -> HirExpression {
-        if let Some(namespace) = namespace {
-            let labels = argument_labels(arguments);
-            let args = arguments
-                .iter()
-                .map(|argument| self.lower_unknown_argument(argument))
-                .collect();
-            let namespace = Some(namespace.clone());
-            let kind = match receiver {
-                Some((receiver, modifier)) => {
-                    let receiver = self.lower_unknown_receiver(receiver, modifier, span);
-                    HirExpressionKind::MethodCall(Box::new(HirMethodCall {
-                        receiver,
-                        receiver_modifier: modifier,
-                        namespace,
-                        ident: ident.clone(),
-                        labels,
-                        args,
-                    }))
-                }
-                None => HirExpressionKind::FunctionCall(HirFunctionCall {
+        let labels = argument_labels(arguments);
+        let labels_ref = label_refs(&labels);
+        let mut qualified_function = None;
+        let namespace = match namespace {
+            Some(namespace) => {
+                let receiver_ty = receiver.as_ref().map(|(receiver, _)| &receiver.ty);
+                let qualified = self.resolve_qualified_function(
                     namespace,
-                    ident: ident.clone(),
-                    labels,
-                    args,
-                }),
-            };
-
-            return HirExpression::new(kind, TypeElement::infer(), Ownership::UniqueOwned, span);
-        }
+                    receiver_ty,
+                    ident,
+                    &labels_ref,
+                    span,
+                );
+                let Some((namespace, function)) = qualified else {
+                    let namespace = namespace.clone();
+                    return self.lower_unchecked_call(receiver, namespace, ident, arguments, span);
+                };
+                let Some(function) = function else {
+                    return self.lower_unchecked_call(receiver, namespace, ident, arguments, span);
+                };
+                qualified_function = Some(function);
+                Some(namespace)
+            }
+            None => None,
+        };
 
-        if receiver.is_none() {
+        if receiver.is_none() && namespace.is_none() {
             if let Some(variable) = self.scopes.get(ident).cloned() {
                 if let TypeElement::Closure(closure) = variable.ty {
                     self.validate_no_argument_labels(arguments, "closure calls");
@@ -321,25 +325,38 @@ impl Checker<'_> {
             }
         }
 
-        let receiver_ident = receiver
-            .as_ref()
-            .and_then(|(receiver, _)| match &receiver.ty {
-                TypeElement::Plain(basic) => Some(basic.ident.clone()),
-                TypeElement::Parametric(parametric) => Some(parametric.base_type.clone()),
-                TypeElement::Generic(generic) => Some(TypeIdent::new(generic.ident.as_str())),
-                _ => None,
-            });
-
         let lookup = self.lookup;
-        let labels = argument_labels(arguments);
-        let labels_ref = label_refs(&labels);
-        let function = lookup
-            .resolve_function(receiver_ident.as_ref(), ident, &labels_ref)
+        let function = qualified_function.or_else(|| match &receiver {
+            Some((receiver, _)) => self.resolve_member_function(&receiver.ty, ident, &labels_ref),
+            None => lookup.resolve_function(None, ident, &labels_ref),
+        });
+        let resolved = function
+            .map(|function| {
+                let ownership = self.call_result_ownership(&function.item);
+                (function.item.signature.clone(), ownership)
+            })
+            .or_else(|| {
+                receiver
+                    .as_ref()
+                    .and_then(|(receiver, _)| self.trait_member(&receiver.ty, ident, &labels_ref))
+                    .map(|signature| (signature, Ownership::UniqueOwned))
+            })
             // Extension functions on collection or generic receivers are
             // registered without a receiver type
-            .or_else(|| lookup.resolve_function(None, ident, &labels_ref));
+            .or_else(|| {
+                lookup
+                    .resolve_function(None, ident, &labels_ref)
+                    .map(|function| {
+                        let ownership = self.call_result_ownership(&function.item);
+                        (function.item.signature.clone(), ownership)
+                    })
+            });
+        let (signature, result_ownership) = match resolved {
+            Some((signature, ownership)) => (Some(signature), ownership),
+            None => (None, Ownership::UniqueOwned),
+        };
 
-        if receiver.is_none() && function.is_none() {
+        if receiver.is_none() && signature.is_none() {
             if let Some((receiver_argument, arguments)) = arguments.split_first() {
                 let (lowered_receiver, expression_modifier) =
                     self.lower_call_value(&receiver_argument.expression);
@@ -348,21 +365,27 @@ impl Checker<'_> {
                     expression_modifier,
                     receiver_argument.expression.span,
                 );
-                let receiver_ident = receiver_type_ident(&lowered_receiver.ty);
                 let receiver_labels = argument_labels(arguments);
                 let receiver_label_refs = label_refs(&receiver_labels);
 
-                if let Some(function) = receiver_ident.as_ref().and_then(|receiver| {
-                    lookup.resolve_function(Some(receiver), ident, &receiver_label_refs)
-                }) {
+                if let Some(function) =
+                    self.resolve_member_function(&lowered_receiver.ty, ident, &receiver_label_refs)
+                {
                     let signature = function.item.signature.clone();
+                    let result_ownership = self.call_result_ownership(&function.item);
+                    let mut substitution = Substitution::default();
                     let receiver = self.lower_known_receiver(
                         lowered_receiver,
                         modifier,
                         signature.receiver(),
+                        &mut substitution,
                         receiver_argument.expression.span,
                     );
-                    let args = self.lower_call_args(&signature.parameters.params, arguments);
+                    let args = self.lower_call_args(
+                        &signature.parameters.params,
+                        arguments,
+                        &mut substitution,
+                    );
                     return HirExpression::new(
                         HirExpressionKind::MethodCall(Box::new(HirMethodCall {
                             receiver,
@@ -374,46 +397,67 @@ impl Checker<'_> {
                             labels: receiver_labels,
                             args,
                         })),
-                        signature.return_type,
-                        Ownership::UniqueOwned,
+                        substitution.apply(&signature.return_type),
+                        result_ownership,
                         span,
                     );
                 }
             }
         }
 
-        match function {
-            Some(function) => {
-                let signature = function.item.signature.clone();
-                let args = self.lower_call_args(&signature.parameters.params, arguments);
-                let ty = signature.return_type.clone();
+        // Methods of the Rust types backing builtin receivers
+        let builtin_method = match (&signature, &receiver) {
+            (None, Some((receiver, _))) => {
+                std_method(&self.resolve_alias(&receiver.ty), ident.as_str())
+            }
+            _ => None,
+        };
+        if let Some(method) = builtin_method {
+            if let Some(receiver) = receiver {
+                return self.lower_std_call(receiver, method, ident, arguments, span);
+            }
+        }
+
+        match signature {
+            Some(signature) => {
+                let mut substitution = Substitution::default();
+                // The receiver is lowered first so that it binds the generic
+                // parameters of the receiver type before the arguments
+                let receiver = receiver.map(|(receiver, modifier)| {
+                    let receiver = self.lower_known_receiver(
+                        receiver,
+                        modifier,
+                        signature.receiver(),
+                        &mut substitution,
+                        span,
+                    );
+                    (receiver, modifier)
+                });
+                let args = self.lower_call_args(
+                    &signature.parameters.params,
+                    arguments,
+                    &mut substitution,
+                );
+                let ty = substitution.apply(&signature.return_type);
                 let kind = match receiver {
-                    Some((receiver, modifier)) => {
-                        let receiver = self.lower_known_receiver(
-                            receiver,
-                            modifier,
-                            signature.receiver(),
-                            span,
-                        );
-                        HirExpressionKind::MethodCall(Box::new(HirMethodCall {
-                            receiver,
-                            receiver_modifier: signature
-                                .receiver()
-                                .and_then(|receiver| receiver.decl_modifier),
-                            namespace: None,
-                            ident: ident.clone(),
-                            labels: labels.clone(),
-                            args,
-                        }))
-                    }
+                    Some((receiver, _)) => HirExpressionKind::MethodCall(Box::new(HirMethodCall {
+                        receiver,
+                        receiver_modifier: signature
+                            .receiver()
+                            .and_then(|receiver| receiver.decl_modifier),
+                        namespace,
+                        ident: ident.clone(),
+                        labels: labels.clone(),
+                        args,
+                    })),
                     None => HirExpressionKind::FunctionCall(HirFunctionCall {
-                        namespace: None,
+                        namespace,
                         ident: ident.clone(),
                         labels: labels.clone(),
                         args,
                     }),
                 };
-                HirExpression::new(kind, ty, Ownership::UniqueOwned, span)
+                HirExpression::new(kind, ty, result_ownership, span)
             }
             None => {
                 let args = arguments
@@ -444,21 +488,61 @@ impl Checker<'_> {
         }
     }
 
+    /// Lowers a call into another crate, whose signature is unknown
+    fn lower_unchecked_call(
+        &mut self,
+        receiver: Option<(HirExpression, Option<DeclModifier>)>,
+        namespace: UsePath,
+        ident: &Ident,
+        arguments: &[FunctionCallArg],
+        span: Span,
+    ) -> HirExpression {
+        let labels = argument_labels(arguments);
+        let args = arguments
+            .iter()
+            .map(|argument| self.lower_unknown_argument(argument))
+            .collect();
+        let namespace = Some(namespace);
+        let kind = match receiver {
+            Some((receiver, modifier)) => {
+                let receiver = self.lower_unknown_receiver(receiver, modifier, span);
+                HirExpressionKind::MethodCall(Box::new(HirMethodCall {
+                    receiver,
+                    receiver_modifier: modifier,
+                    namespace,
+                    ident: ident.clone(),
+                    labels,
+                    args,
+                }))
+            }
+            None => HirExpressionKind::FunctionCall(HirFunctionCall {
+                namespace,
+                ident: ident.clone(),
+                labels,
+                args,
+            }),
+        };
+
+        HirExpression::new(kind, TypeElement::infer(), Ownership::UniqueOwned, span)
+    }
+
     fn lower_known_receiver(
         &mut self,
         receiver: HirExpression,
         modifier: Option<DeclModifier>,
         param: Option<&Param>,
+        substitution: &mut Substitution,
         span: Span,
     ) -> HirExpression {
         let Some(param) = param else {
             return self.lower_unknown_receiver(receiver, modifier, span);
         };
 
-        self.lower_known_argument(receiver, modifier, param, span)
+        let param = self.instantiate_param(param, &receiver.ty, substitution, span);
+        self.lower_known_argument(receiver, modifier, &param, span)
     }
 
-    fn lower_unknown_receiver(
+    pub(super) fn lower_unknown_receiver(
         &mut self,
         receiver: HirExpression,
         modifier: Option<DeclModifier>,
@@ -474,11 +558,13 @@ impl Checker<'_> {
 
     /// Lowers arguments for a call with a known signature: every argument is
     /// coerced to the parameter type and the ownership implied by the
-    /// parameter's declaration modifier
+    /// parameter's declaration modifier. Generic parameters are bound from
+    /// the argument types and instantiated through `substitution`.
     fn lower_call_args(
         &mut self,
         params: &[Param],
         arguments: &[FunctionCallArg],
+        substitution: &mut Substitution,
     ) -> Vec<HirExpression> {
         let params = params
             .iter()
@@ -506,11 +592,54 @@ impl Checker<'_> {
                     expression_modifier,
                     argument.expression.span,
                 );
-                self.lower_known_argument(lowered, modifier, param, argument.expression.span)
+                let span = argument.expression.span;
+                let param = self.instantiate_param(param, &lowered.ty, substitution, span);
+                self.lower_known_argument(lowered, modifier, &param, span)
             })
             .collect()
     }
 
+    /// Binds the generic parameters of the parameter type from the type of
+    /// the value passed for it and returns the instantiated parameter
+    fn instantiate_param(
+        &mut self,
+        param: &Param,
+        actual: &TypeElement,
+        substitution: &mut Substitution,
+        span: Span,
+    ) -> Param {
+        Param {
+            param_type: self.instantiate_type(&param.param_type, actual, substitution, span),
+            ..param.clone()
+        }
+    }
+
+    /// Binds the generic parameters in `ty` from the type of the value used
+    /// for it, reporting conflicting bindings, and returns the instantiated
+    /// type
+    fn instantiate_type(
+        &mut self,
+        ty: &TypeElement,
+        actual: &TypeElement,
+        substitution: &mut Substitution,
+        span: Span,
+    ) -> TypeElement {
+        if let Err(conflict) = substitution.unify(ty, actual) {
+            self.errors.error_with_span(
+                TranspilerError::TypeMismatch {
+                    expected: format!(
+                        "{} for generic parameter `{}`",
+                        conflict.bound, conflict.generic
+                    ),
+                    found: conflict.found.to_string(),
+                },
+                Some(span.into()),
+            );
+        }
+
+        substitution.apply(ty)
+    }
+
     fn validate_argument_label(&mut self, param: &Param, argument: &FunctionCallArg) {
         match (param.call_label(), &argument.label) {
             (None, None) => {}
@@ -544,7 +673,11 @@ impl Checker<'_> {
         }
     }
 
-    fn validate_no_argument_labels(&mut self, arguments: &[FunctionCallArg], context: &str) {
+    pub(super) fn validate_no_argument_labels(
+        &mut self,
+        arguments: &[FunctionCallArg],
+        context: &str,
+    ) {
         for argument in arguments {
             if let Some(label) = &argument.label {
                 self.errors.error_with_span(
@@ -610,7 +743,7 @@ impl Checker<'_> {
         );
     }
 
-    fn merge_argument_modifiers(
+    pub(super) fn merge_argument_modifiers(
         &mut self,
         prefix: Option<DeclModifier>,
         postfix: Option<DeclModifier>,
@@ -645,7 +778,7 @@ impl Checker<'_> {
         }
     }
 
-    fn lower_call_value(
+    pub(super) fn lower_call_value(
         &mut self,
         expression: &Expression,
     ) -> (HirExpression, Option<DeclModifier>) {
@@ -783,8 +916,7 @@ impl Checker<'_> {
     ) -> HirExpression {
         let scrutinee = self.lower_expression(&match_expression.scrutinee, &Expected::free());
         let scrutinee_ty = scrutinee.ty.clone();
-        let target = self.match_target_type(&scrutinee_ty, span);
-        let scrutinee = self.coerce(scrutinee, &Expected::owned(scrutinee_ty.clone()));
+        let target = self.enum_target(&scrutinee_ty);
 
         let branch_expected = if expected.is_void() {
             Expected::void()
@@ -796,12 +928,26 @@ impl Checker<'_> {
         let mut arms = Vec::with_capacity(match_expression.arms.len());
 
         for arm in &match_expression.arms {
-            let lowered = self.lower_match_arm(arm, target.as_ref(), &branch_expected);
+            let lowered = self.lower_match_arm(arm, &scrutinee_ty, &branch_expected);
             if branch_expected.is_free() {
                 unified_ty = self.unify_match_type(unified_ty, &lowered.body.ty, span);
             }
             arms.push(lowered);
         }
+        self.check_match_coverage(
+            &scrutinee_ty,
+            target.as_ref(),
+            &match_expression.arms,
+            &arms,
+            span,
+        );
+
+        // String literal patterns can only match a `&str`
+        let scrutinee = if arms.iter().any(|arm| has_string_pattern(&arm.pattern)) {
+            scrutinee.adjusted(Adjustment::AsStr)
+        } else {
+            self.coerce(scrutinee, &Expected::owned(scrutinee_ty))
+        };
 
         let ty = if branch_expected.is_free() {
             unified_ty.unwrap_or_else(TypeElement::infer)
@@ -820,56 +966,204 @@ impl Checker<'_> {
     fn lower_match_arm(
         &mut self,
         arm: &MatchArm,
-        target: Option<&TypeIdent>,
+        scrutinee_ty: &TypeElement,
         expected: &Expected,
     ) -> HirMatchArm {
-        let (pattern, bindings) = self.lower_match_pattern(&arm.pattern, target);
+        let mut bindings = Vec::new();
+        let pattern = self.lower_match_pattern(&arm.pattern, scrutinee_ty, &mut bindings);
 
         self.scopes.push();
         for binding in bindings {
             self.scopes.declare(binding);
         }
+        let guard = arm
+            .guard
+            .as_ref()
+            .map(|guard| self.lower_expression(guard, &Expected::owned(TypeElement::bool())));
         let body = self.lower_block(&arm.body.body, expected);
         self.scopes.pop();
 
-        HirMatchArm { pattern, body }
+        HirMatchArm {
+            pattern,
+            guard,
+            body,
+        }
     }
 
+    /// Lowers a pattern matching values of type `ty` and collects the
+    /// variables it binds
     fn lower_match_pattern(
         &mut self,
         pattern: &MatchPattern,
-        target: Option<&TypeIdent>,
-    ) -> (HirMatchPattern, Vec<Variable>) {
+        ty: &TypeElement,
+        bindings: &mut Vec<Variable>,
+    ) -> HirMatchPattern {
         match pattern {
-            MatchPattern::Wildcard(_) => (HirMatchPattern::Wildcard, Vec::new()),
+            MatchPattern::Wildcard(_) => HirMatchPattern::Wildcard,
             MatchPattern::EnumVariant(pattern) => {
-                let target = target
-                    .cloned()
+                let target = self
+                    .match_target_type(ty, pattern.span)
                     .unwrap_or_else(|| TypeIdent::new("__UnknownMatchTarget"));
                 let fields = self
                     .resolve_match_variant_fields(&target, &pattern.case, pattern.span)
                     .unwrap_or_default();
-                let (arguments, bindings) =
-                    self.lower_match_pattern_arguments(&pattern.arguments, &fields, pattern.span);
-
-                (
-                    HirMatchPattern::EnumVariant(HirEnumMatchPattern {
-                        target,
-                        case: pattern.case.clone(),
-                        arguments,
-                    }),
+                let arguments = self.lower_match_pattern_arguments(
+                    &pattern.arguments,
+                    &fields,
+                    pattern.span,
                     bindings,
+                );
+
+                HirMatchPattern::EnumVariant(HirEnumMatchPattern {
+                    target,
+                    case: pattern.case.clone(),
+                    arguments,
+                })
+            }
+            MatchPattern::Literal(pattern) => HirMatchPattern::Literal(self.lower_pattern_literal(
+                &pattern.literal,
+                ty,
+                pattern.span,
+            )),
+            MatchPattern::Range(pattern) => {
+                let inclusive = match pattern.operator {
+                    RangeOperator::Inclusive => true,
+                    RangeOperator::Exclusive => false,
+                    RangeOperator::Tolerance | RangeOperator::Interval => {
+                        self.errors.error_with_span(
+                            TranspilerError::InvalidSyntax {
+                                message:
+                                    "Only `..<` and `..=` ranges can be used as match patterns"
+                                        .to_string(),
+                            },
+                            Some(pattern.span.into()),
+                        );
+                        true
+                    }
+                };
+
+                HirMatchPattern::Range(HirMatchRange {
+                    start: self.lower_pattern_literal(&pattern.start, ty, pattern.span),
+                    end: self.lower_pattern_literal(&pattern.end, ty, pattern.span),
+                    inclusive,
+                })
+            }
+            MatchPattern::Tuple(pattern) => {
+                let count = pattern.elements.len();
+                let element_types = match ty {
+                    TypeElement::Tuple(tuple) if tuple.elements.len() == count => {
+                        tuple.elements.clone()
+                    }
+                    TypeElement::Infer(_) => vec![TypeElement::infer(); count],
+                    _ => {
+                        self.errors.error_with_span(
+                            TranspilerError::TypeMismatch {
+                                expected: ty.to_string(),
+                                found: format!("tuple pattern with {count} elements"),
+                            },
+                            Some(pattern.span.into()),
+                        );
+                        vec![TypeElement::infer(); count]
+                    }
+                };
+
+                HirMatchPattern::Tuple(
+                    pattern
+                        .elements
+                        .iter()
+                        .zip(&element_types)
+                        .map(|(element, ty)| self.lower_match_binding(element, ty, bindings))
+                        .collect(),
                 )
             }
+            MatchPattern::Alternatives(pattern) => {
+                let mut alternatives = Vec::with_capacity(pattern.alternatives.len());
+                let mut bound: Option<Vec<Variable>> = None;
+                for alternative in &pattern.alternatives {
+                    let mut alternative_bindings = Vec::new();
+                    alternatives.push(self.lower_match_pattern(
+                        alternative,
+                        ty,
+                        &mut alternative_bindings,
+                    ));
+
+                    match &bound {
+                        None => bound = Some(alternative_bindings),
+                        Some(bound)
+                            if binding_names(bound) != binding_names(&alternative_bindings) =>
+                        {
+                            self.errors.error_with_span(
+                                TranspilerError::InvalidSyntax {
+                                    message: "All alternatives of a match pattern must bind the same variables".to_string(),
+                                },
+                                Some(alternative.span().into()),
+                            );
+                        }
+                        Some(bound) => {
+                            if let Some((expected, found)) =
+                                mismatched_binding(bound, &alternative_bindings)
+                            {
+                                self.errors.error_with_span(
+                                    TranspilerError::TypeMismatch {
+                                        expected: expected.to_string(),
+                                        found: found.to_string(),
+                                    },
+                                    Some(alternative.span().into()),
+                                );
+                            }
+                        }
+                    }
+                }
+
+                bindings.extend(bound.unwrap_or_default());
+                HirMatchPattern::Alternatives(alternatives)
+            }
         }
     }
 
+    /// Lowers a literal used as a pattern for values of type `ty`
+    fn lower_pattern_literal(
+        &mut self,
+        literal: &Literal,
+        ty: &TypeElement,
+        span: Span,
+    ) -> HirLiteral {
+        if let Literal::StringLiteral(string) = literal {
+            if !string.interpolations.is_empty() {
+                self.errors.error_with_span(
+                    TranspilerError::InvalidSyntax {
+                        message: "String patterns cannot contain interpolations".to_string(),
+                    },
+                    Some(span.into()),
+                );
+            }
+        }
+
+        let (lowered, literal_ty) = self.lower_literal_value(literal);
+        let numeric = match (&lowered, ty) {
+            (HirLiteral::Number(_), TypeElement::Plain(plain)) => is_numeric_type(&plain.ident),
+            _ => false,
+        };
+        if !numeric && !types_compatible(ty, &literal_ty) {
+            self.errors.error_with_span(
+                TranspilerError::TypeMismatch {
+                    expected: ty.to_string(),
+                    found: literal_ty.to_string(),
+                },
+                Some(span.into()),
+            );
+        }
+
+        lowered
+    }
+
     fn lower_match_pattern_arguments(
         &mut self,
         arguments: &[MatchPatternArg],
         fields: &[(Option<Ident>, TypeElement)],
         span: Span,
-    ) -> (HirMatchPatternArguments, Vec<Variable>) {
+        bindings: &mut Vec<Variable>,
+    ) -> HirMatchPatternArguments {
         let has_named = arguments
             .iter()
             .any(|argument| matches!(argument, MatchPatternArg::Named(_)));
@@ -887,13 +1181,13 @@ impl Checker<'_> {
         }
 
         if arguments.is_empty() {
-            return (self.wildcard_match_arguments(fields), Vec::new());
+            return self.wildcard_match_arguments(fields);
         }
 
         if has_named {
-            self.lower_named_match_arguments(arguments, fields, span)
+            self.lower_named_match_arguments(arguments, fields, span, bindings)
         } else {
-            self.lower_positional_match_arguments(arguments, fields, span)
+            self.lower_positional_match_arguments(arguments, fields, span, bindings)
         }
     }
 
@@ -902,7 +1196,8 @@ impl Checker<'_> {
         arguments: &[MatchPatternArg],
         fields: &[(Option<Ident>, TypeElement)],
         span: Span,
-    ) -> (HirMatchPatternArguments, Vec<Variable>) {
+        bindings: &mut Vec<Variable>,
+    ) -> HirMatchPatternArguments {
         if fields.iter().any(|(name, _)| name.is_some()) {
             self.errors.error_with_span(
                 TranspilerError::InvalidSyntax {
@@ -923,7 +1218,6 @@ impl Checker<'_> {
             );
         }
 
-        let mut bindings = Vec::new();
         let mut patterns = Vec::with_capacity(fields.len());
 
         for (i, (_, ty)) in fields.iter().enumerate() {
@@ -932,20 +1226,17 @@ impl Checker<'_> {
                 MatchPatternArg::Named(_) => None,
             });
 
-            let (pattern, variable) = match binding {
-                Some(binding) => self.lower_match_binding(binding, ty),
-                None => (HirMatchBindingPattern::Wildcard, None),
+            let pattern = match binding {
+                Some(binding) => self.lower_match_binding(binding, ty, bindings),
+                None => HirMatchBindingPattern::Wildcard,
             };
-            if let Some(variable) = variable {
-                bindings.push(variable);
-            }
             patterns.push(pattern);
         }
 
         if fields.is_empty() {
-            (HirMatchPatternArguments::None, bindings)
+            HirMatchPatternArguments::None
         } else {
-            (HirMatchPatternArguments::Tuple(patterns), bindings)
+            HirMatchPatternArguments::Tuple(patterns)
         }
     }
 
@@ -954,7 +1245,8 @@ impl Checker<'_> {
         arguments: &[MatchPatternArg],
         fields: &[(Option<Ident>, TypeElement)],
         span: Span,
-    ) -> (HirMatchPatternArguments, Vec<Variable>) {
+        bindings: &mut Vec<Variable>,
+    ) -> HirMatchPatternArguments {
         if fields.iter().any(|(name, _)| name.is_none()) {
             self.errors.error_with_span(
                 TranspilerError::InvalidSyntax {
@@ -987,7 +1279,6 @@ impl Checker<'_> {
             }
         }
 
-        let mut bindings = Vec::new();
         let mut patterns = Vec::with_capacity(fields.len());
 
         for (field, ty) in fields {
@@ -999,13 +1290,10 @@ impl Checker<'_> {
                 .iter()
                 .find(|argument| argument.field == *field)
                 .map(|argument| &argument.binding);
-            let (binding, variable) = match binding {
-                Some(binding) => self.lower_match_binding(binding, ty),
-                None => (HirMatchBindingPattern::Wildcard, None),
+            let binding = match binding {
+                Some(binding) => self.lower_match_binding(binding, ty, bindings),
+                None => HirMatchBindingPattern::Wildcard,
             };
-            if let Some(variable) = variable {
-                bindings.push(variable);
-            }
             patterns.push(HirNamedMatchBinding {
                 field: field.clone(),
                 binding,
@@ -1013,26 +1301,38 @@ impl Checker<'_> {
         }
 
         if fields.is_empty() {
-            (HirMatchPatternArguments::None, bindings)
+            HirMatchPatternArguments::None
         } else {
-            (HirMatchPatternArguments::Named(patterns), bindings)
+            HirMatchPatternArguments::Named(patterns)
         }
     }
 
     fn lower_match_binding(
-        &self,
+        &mut self,
         binding: &MatchBindingPattern,
         ty: &TypeElement,
-    ) -> (HirMatchBindingPattern, Option<Variable>) {
+        bindings: &mut Vec<Variable>,
+    ) -> HirMatchBindingPattern {
         match binding {
             MatchBindingPattern::Ident(ident) => {
                 let ownership = match_binding_ownership(self.is_copy(ty), ty.is_infer());
-                (
-                    HirMatchBindingPattern::Binding(ident.clone()),
-                    Some(let_variable(ident.clone(), ty.clone(), ownership)),
-                )
+                bindings.push(let_variable(ident.clone(), ty.clone(), ownership));
+                HirMatchBindingPattern::Binding(ident.clone())
+            }
+            MatchBindingPattern::Wildcard(_) => HirMatchBindingPattern::Wildcard,
+            MatchBindingPattern::Pattern(pattern) => {
+                let lowered = self.lower_match_pattern(pattern, ty, bindings);
+                // Strings are only matched as `&str` on the scrutinee itself
+                if has_string_pattern(&lowered) {
+                    self.errors.error_with_span(
+                        TranspilerError::InvalidSyntax {
+                            message: "String literals can only be matched at the top level of a pattern, use a guard instead".to_string(),
+                        },
+                        Some(pattern.span().into()),
+                    );
+                }
+                HirMatchBindingPattern::Pattern(Box::new(lowered))
             }
-            MatchBindingPattern::Wildcard(_) => (HirMatchBindingPattern::Wildcard, None),
         }
     }
 
@@ -1066,10 +1366,21 @@ impl Checker<'_> {
         }
     }
 
-    fn match_target_type(&mut self, ty: &TypeElement, span: Span) -> Option<TypeIdent> {
+    /// The enum or union a value of type `ty` is matched against, if any
+    fn enum_target(&mut self, ty: &TypeElement) -> Option<TypeIdent> {
         match ty {
             TypeElement::Plain(basic) => Some(basic.ident.clone()),
             TypeElement::Parametric(parametric) => Some(parametric.base_type.clone()),
+            TypeElement::Union(union) => Some(self.register_union(union)),
+            _ => None,
+        }
+    }
+
+    fn match_target_type(&mut self, ty: &TypeElement, span: Span) -> Option<TypeIdent> {
+        match ty {
+            TypeElement::Plain(_) | TypeElement::Parametric(_) | TypeElement::Union(_) => {
+                self.enum_target(ty)
+            }
             TypeElement::Infer(_) => None,
             _ => {
                 self.errors.error_with_span(
@@ -1090,6 +1401,26 @@ impl Checker<'_> {
         case: &TypeIdent,
         span: Span,
     ) -> Option<Vec<(Option<Ident>, TypeElement)>> {
+        // Matching a union narrows it to the member named by the case
+        if let Some(union) = self.unions.get(target) {
+            let Some(variant) = union.variants.iter().find(|variant| variant.case == *case) else {
+                let members = union
+                    .variants
+                    .iter()
+                    .map(|variant| variant.ty.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                self.errors.error_with_span(
+                    TranspilerError::EnumAccessError {
+                        message: format!("Union type `{members}` does not have member `{case}`"),
+                    },
+                    Some(span.into()),
+                );
+                return None;
+            };
+            return Some(vec![(None, variant.ty.clone())]);
+        }
+
         let Some(decl) = self.lookup.resolve_type(target) else {
             self.errors.error_with_span(
                 TranspilerError::UnknownType {
@@ -1144,10 +1475,10 @@ impl Checker<'_> {
             None => Some(next.clone()),
             Some(current) => unify_types(&current, next).or_else(|| {
                 self.errors.warning(
-                    format!(
-                        "Types of match arms don't match: previous: {}, next: {}",
-                        current, next
-                    ),
+                    TranspilerWarning::MatchArmTypeMismatch {
+                        previous: current.to_string(),
+                        next: next.to_string(),
+                    },
                     Some(span.into()),
                 );
                 Some(TypeElement::infer())
@@ -1181,7 +1512,7 @@ impl Checker<'_> {
     ) -> HirExpression {
         let receiver = self.lower_expression(&else_expression.receiver, &Expected::free());
 
-        let (kind, inner_ty, err_ty) = match &receiver.ty {
+        let (kind, inner_ty, err_ty) = match &self.resolve_alias(&receiver.ty) {
             TypeElement::Optional(optional) => {
                 (HirElseUnwrapKind::Optional, optional.inner.clone(), None)
             }
@@ -1286,10 +1617,10 @@ impl Checker<'_> {
 
                 let ty = unify_types(&then_block.ty, &else_block.ty).unwrap_or_else(|| {
                     self.errors.warning(
-                        format!(
-                            "Types of if and else expression don't match: if: {}, else: {}",
-                            then_block.ty, else_block.ty
-                        ),
+                        TranspilerWarning::IfElseTypeMismatch {
+                            then_ty: then_block.ty.to_string(),
+                            else_ty: else_block.ty.to_string(),
+                        },
                         Some(span.into()),
                     );
                     TypeElement::infer()
@@ -1389,7 +1720,7 @@ impl Checker<'_> {
 
         let condition = self.lower_expression(&call.arguments[0].expression, &Expected::free());
 
-        let (kind, ok_ty, err_ty) = match &condition.ty {
+        let (kind, ok_ty, err_ty) = match &self.resolve_alias(&condition.ty) {
             TypeElement::Optional(optional) => (TryKind::Optional, optional.inner.clone(), None),
             TypeElement::Result(result) => (
                 TryKind::Result,
@@ -1425,7 +1756,10 @@ impl Checker<'_> {
                         ident
                     })
                     .collect();
+                // The body runs inside a closure that `break` cannot leave
+                let loops = std::mem::take(&mut self.loops);
                 let block = self.lower_block(&body.block.body, &Expected::free());
+                self.loops = loops;
                 self.scopes.pop();
 
                 HirExpression::new(
@@ -1445,6 +1779,24 @@ impl Checker<'_> {
         }
     }
 
+    /// Adjusts the scrutinee of a `try`. Clones the scrutinee when matching
+    /// on it would move a value that is used again later; borrows
+    /// optionals/results that are already behind a reference.
+    pub(crate) fn try_scrutinee(&mut self, condition: HirExpression) -> HirExpression {
+        let condition_ownership = condition.adjusted_ownership();
+        let condition = self.adjust_ownership(condition, condition_ownership);
+        if condition_ownership == Ownership::Borrowed
+            && matches!(
+                condition.ty,
+                TypeElement::Optional(_) | TypeElement::Result(_)
+            )
+        {
+            condition.adjusted(Adjustment::Borrow)
+        } else {
+            condition
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn lower_try_else(
         &mut self,
@@ -1457,20 +1809,7 @@ impl Checker<'_> {
         expected: &Expected,
         span: Span,
     ) -> HirExpression {
-        // Clone the scrutinee when matching on it would move a value that is
-        // used again later; borrow optionals/results that are already behind
-        // a reference
-        let condition_ownership = condition.adjusted_ownership();
-        let condition = self.adjust_ownership(condition, condition_ownership);
-        let condition = if condition_ownership == Ownership::Borrowed
-            && matches!(
-                condition.ty,
-                TypeElement::Optional(_) | TypeElement::Result(_)
-            ) {
-            condition.adjusted(Adjustment::Borrow)
-        } else {
-            condition
-        };
+        let condition = self.try_scrutinee(condition);
 
         let branch_expected = if expected.is_void() {
             Expected::void()
@@ -1526,7 +1865,13 @@ impl Checker<'_> {
         )
     }
 
-    fn lower_for(&mut self, call: &FunctionCall, expected: &Expected, span: Span) -> HirExpression {
+    pub(super) fn lower_for(
+        &mut self,
+        call: &FunctionCall,
+        expected: &Expected,
+        label: Option<Ident>,
+        span: Span,
+    ) -> HirExpression {
         if call.arguments.len() != 2 {
             self.errors.error(TranspilerError::MissingArgument {
                 operation: "for".to_string(),
@@ -1543,7 +1888,8 @@ impl Checker<'_> {
         };
 
         let iterable = self.lower_expression(&call.arguments[0].expression, &Expected::free());
-        let iterable_info = self.for_iterable_info(&iterable.ty);
+        let iterable_info =
+            self.for_iterable_info(&self.resolve_alias(&iterable.ty), iterable.span);
 
         // Borrow iterated locals so the loop does not consume them
         let iterable = match (&iterable.kind, iterable.adjusted_ownership()) {
@@ -1602,7 +1948,9 @@ impl Checker<'_> {
             Some(collect_ty) => Expected::owned(collect_ty.clone()),
             None => Expected::void(),
         };
+        self.push_loop(label.clone(), false, Expected::void());
         let block = self.lower_block(&body.block.body, &body_expected);
+        self.pop_loop();
         self.scopes.pop();
 
         let ty = match &collect {
@@ -1620,6 +1968,7 @@ impl Checker<'_> {
                 iterable,
                 body: block,
                 collect,
+                label,
             })),
             ty,
             Ownership::UniqueOwned,
@@ -1627,7 +1976,7 @@ impl Checker<'_> {
         )
     }
 
-    fn for_iterable_info(&mut self, iterable_ty: &TypeElement) -> ForIterableInfo {
+    fn for_iterable_info(&mut self, iterable_ty: &TypeElement, span: Span) -> ForIterableInfo {
         match iterable_ty {
             TypeElement::Array(array) => ForIterableInfo::single(array.elements.clone()),
             TypeElement::Set(set) => ForIterableInfo::single(set.elements.clone()),
@@ -1643,25 +1992,53 @@ impl Checker<'_> {
             ),
             TypeElement::Optional(optional) => ForIterableInfo::single(optional.inner.clone()),
             TypeElement::Result(result) => ForIterableInfo::single(result.success.clone()),
-            TypeElement::Tuple(tuple) => {
-                let item_ty = unify_tuple_iteration_type(&tuple.elements, &mut self.errors);
-                ForIterableInfo {
-                    item_ty: item_ty.clone(),
-                    binding_tys: vec![item_ty],
-                    kind: HirForIterableKind::Tuple {
-                        len: tuple.elements.len(),
-                    },
-                }
-            }
             TypeElement::Infer(_) | TypeElement::Void(_) => {
                 ForIterableInfo::single(TypeElement::infer())
             }
-            _ => {
-                self.errors
-                    .warning("For loop on type that is not an iterator".to_string(), None);
-                ForIterableInfo::single(TypeElement::infer())
+            _ => match self.tuple_elements(iterable_ty) {
+                Some(elements) => {
+                    let item_ty = self.tuple_iteration_type(iterable_ty, &elements, span);
+                    ForIterableInfo {
+                        item_ty: item_ty.clone(),
+                        binding_tys: vec![item_ty],
+                        kind: HirForIterableKind::Tuple {
+                            len: elements.len(),
+                        },
+                    }
+                }
+                None => {
+                    self.errors.warning(TranspilerWarning::NotAnIterator, None);
+                    ForIterableInfo::single(TypeElement::infer())
+                }
+            },
+        }
+    }
+
+    /// The common type of the elements of an iterated tuple
+    fn tuple_iteration_type(
+        &mut self,
+        tuple_ty: &TypeElement,
+        elements: &[TypeElement],
+        span: Span,
+    ) -> TypeElement {
+        let mut unified = TypeElement::infer();
+        for element in elements {
+            match unify_types(&unified, element) {
+                Some(ty) => unified = ty,
+                None => {
+                    self.errors.error_with_span(
+                        TranspilerError::InvalidOperationOnType {
+                            operation: format!("`for` over {tuple_ty}"),
+                            allowed_types: "tuples whose elements all have the same type"
+                                .to_string(),
+                        },
+                        Some(span.into()),
+                    );
+                    return TypeElement::infer();
+                }
             }
         }
+        unified
     }
 
     fn for_binding_deref(
@@ -1670,10 +2047,12 @@ impl Checker<'_> {
         borrows_iterable: bool,
         iterable_info: &ForIterableInfo,
     ) -> bool {
-        borrows_iterable
-            && iterable_info.kind == HirForIterableKind::Normal
-            && self.is_copy(binding_ty)
-            && !binding_ty.is_infer()
+        // Tuple elements are always iterated by reference
+        let yields_references = match iterable_info.kind {
+            HirForIterableKind::Normal => borrows_iterable,
+            HirForIterableKind::Tuple { .. } => true,
+        };
+        yields_references && self.is_copy(binding_ty) && !binding_ty.is_infer()
     }
 
     fn lower_for_binding(
@@ -1693,7 +2072,7 @@ impl Checker<'_> {
         HirForBinding { ident, deref }
     }
 
-    fn unwrap_binding_ownership(
+    pub(super) fn unwrap_binding_ownership(
         &self,
         ty: &TypeElement,
         scrutinee_ownership: Ownership,
@@ -1891,13 +2270,24 @@ impl Checker<'_> {
                 let lhs = self.lower_expression(&operation.lhs, &Expected::free());
                 let rhs = self.lower_expression(&operation.rhs, &Expected::free());
 
+                if let Some(ty) = self.struct_operand(&lhs.ty) {
+                    return self.lower_struct_arithmetic(
+                        ty,
+                        lhs,
+                        operation.operator.clone(),
+                        rhs,
+                        span,
+                    );
+                }
+
                 if let (TypeElement::Plain(a), TypeElement::Plain(b)) = (&lhs.ty, &rhs.ty) {
                     if !are_compatible_numeric_types(&a.ident, &b.ident) {
                         self.errors.warning(
-                            format!(
-                                "Type mismatch in arithmetic operation: {} {:?} {}",
-                                lhs.ty, operation.operator, rhs.ty
-                            ),
+                            TranspilerWarning::ArithmeticTypeMismatch {
+                                lhs: lhs.ty.to_string(),
+                                operator: format!("{:?}", operation.operator),
+                                rhs: rhs.ty.to_string(),
+                            },
                             Some(operation.rhs.span.into()),
                         );
                     }
@@ -1974,19 +2364,30 @@ impl Checker<'_> {
                 )
             }
             InfixExpression::Collection(operation) => {
-                let lhs = self.lower_expression(&operation.lhs, &Expected::free());
-                let rhs = self.lower_expression(&operation.rhs, &Expected::free());
+                let mut lhs = self.lower_expression(&operation.lhs, &Expected::free());
+                let mut rhs = self.lower_expression(&operation.rhs, &Expected::free());
+                // Operands are typed as the aliased collections, the result
+                // keeps the declared type of the left-hand side
+                let declared_ty = lhs.ty.clone();
+                lhs.ty = self.resolve_alias(&lhs.ty);
+                rhs.ty = self.resolve_alias(&rhs.ty);
                 let (operator, rhs) = match operation.operator {
                     galvan_ast::CollectionOperator::Concat => {
                         let kind = concat_kind(&lhs.ty, &rhs.ty);
                         let rhs = self.coerce_concat_value(&lhs.ty, kind, rhs, false);
                         (CollectionOperator::Concat(kind), rhs)
                     }
-                    galvan_ast::CollectionOperator::Remove => (CollectionOperator::Remove, rhs),
+                    galvan_ast::CollectionOperator::Remove => self.lower_remove(&lhs, rhs, span),
+                    galvan_ast::CollectionOperator::Repeat => {
+                        self.check_repeat(&lhs, &rhs, span);
+                        (CollectionOperator::Repeat, rhs)
+                    }
                     galvan_ast::CollectionOperator::Contains => (CollectionOperator::Contains, rhs),
                 };
                 let ty = match operator {
-                    CollectionOperator::Concat(_) | CollectionOperator::Remove => lhs.ty.clone(),
+                    CollectionOperator::Concat(_)
+                    | CollectionOperator::Remove(_)
+                    | CollectionOperator::Repeat => declared_ty,
                     CollectionOperator::Contains => TypeElement::bool(),
                 };
                 HirExpression::new(
@@ -1997,12 +2398,8 @@ impl Checker<'_> {
                 )
             }
             InfixExpression::Member(operation) => self.lower_member(operation, expected, span),
-            InfixExpression::Custom(_) => {
-                self.errors.warning(
-                    "Custom infix operators are not yet implemented".to_string(),
-                    Some(span.into()),
-                );
-                HirExpression::error("custom infix operators are not implemented", span)
+            InfixExpression::Custom(operation) => {
+                self.lower_custom_infix(operation, expected, span)
             }
         }
     }
@@ -2028,22 +2425,10 @@ impl Checker<'_> {
                 ExpressionKind::Ident(field) => {
                     let (receiver, locks_ref) = self.lower_access_base(&operation.lhs);
                     let field_ty = self.field_type(&receiver.ty, field, span);
-                    let ownership = if self.is_copy(&field_ty) {
-                        Ownership::UniqueOwned
-                    } else if locks_ref {
-                        Ownership::SharedOwned
-                    } else {
-                        receiver.adjusted_ownership()
-                    };
-                    HirExpression::new(
-                        HirExpressionKind::FieldAccess(Box::new(HirFieldAccess {
-                            receiver,
-                            field: field.clone(),
-                        })),
-                        field_ty,
-                        ownership,
-                        span,
-                    )
+                    self.field_access(receiver, locks_ref, field.clone(), field_ty, span)
+                }
+                ExpressionKind::Literal(Literal::NumberLiteral(position)) => {
+                    self.lower_tuple_access(&operation.lhs, position, span)
                 }
                 _ => {
                     self.errors.error(TranspilerError::MemberAccessError {
@@ -2057,8 +2442,10 @@ impl Checker<'_> {
         }
     }
 
-    fn lower_access_base(&mut self, expression: &Expression) -> (HirExpression, bool) {
+    pub(super) fn lower_access_base(&mut self, expression: &Expression) -> (HirExpression, bool) {
         let mut base = self.lower_expression(expression, &Expected::free());
+        // Fields, elements and keys are those of the aliased type
+        base.ty = self.resolve_alias(&base.ty);
         let locks_ref = base.adjusted_ownership() == Ownership::Ref;
         if locks_ref {
             base = base.adjusted(Adjustment::LockRef);
@@ -2066,8 +2453,34 @@ impl Checker<'_> {
         (base, locks_ref)
     }
 
+    /// Accesses a field of `receiver`. Fields that are not `Copy` share the
+    /// ownership of the receiver, or are cloned out of locked `ref` values.
+    pub(super) fn field_access(
+        &mut self,
+        receiver: HirExpression,
+        locks_ref: bool,
+        field: Ident,
+        field_ty: TypeElement,
+        span: Span,
+    ) -> HirExpression {
+        let ownership = if self.is_copy(&field_ty) {
+            Ownership::UniqueOwned
+        } else if locks_ref {
+            Ownership::SharedOwned
+        } else {
+            receiver.adjusted_ownership()
+        };
+        HirExpression::new(
+            HirExpressionKind::FieldAccess(Box::new(HirFieldAccess { receiver, field })),
+            field_ty,
+            ownership,
+            span,
+        )
+    }
+
     /// Resolves the type of a field on a receiver type
     fn field_type(&mut self, receiver_ty: &TypeElement, field: &Ident, span: Span) -> TypeElement {
+        let receiver_ty = &self.resolve_alias(receiver_ty);
         let type_ident = match receiver_ty {
             TypeElement::Plain(basic) => basic.ident.clone(),
             TypeElement::Parametric(parametric) => parametric.base_type.clone(),
@@ -2087,12 +2500,18 @@ impl Checker<'_> {
             return TypeElement::infer();
         };
 
+        // Fields of a parametric type are instantiated with its type arguments
+        let mut substitution = Substitution::default();
+        if let TypeElement::Parametric(parametric) = receiver_ty {
+            substitution.bind_all(&decl.item.generic_params(), &parametric.type_args);
+        }
+
         match &decl.item {
             TypeDecl::Struct(decl) => decl
                 .members
                 .iter()
                 .find(|member| member.ident == *field)
-                .map(|member| member.r#type.clone())
+                .map(|member| substitution.apply(&member.r#type))
                 .unwrap_or_else(|| {
                     self.errors.error(TranspilerError::MemberAccessError {
                         message: format!("struct does not have field: {field}"),
@@ -2100,8 +2519,12 @@ impl Checker<'_> {
                     TypeElement::infer()
                 }),
             TypeDecl::Tuple(_) => {
-                self.errors.warning(
-                    "Tuple member access not yet implemented".to_string(),
+                self.errors.error_with_span(
+                    TranspilerError::MemberAccessError {
+                        message: format!(
+                            "{type_ident} is a tuple type, its elements are accessed by position, e.g. `.0`"
+                        ),
+                    },
                     Some(span.into()),
                 );
                 TypeElement::infer()
@@ -2112,7 +2535,7 @@ impl Checker<'_> {
                 });
                 TypeElement::infer()
             }
-            // TODO: Handle inference for alias types
+            // Only cyclic aliases remain unresolved, they are reported once
             TypeDecl::Alias(_) => TypeElement::infer(),
             TypeDecl::Empty(_) => {
                 self.errors.error(TranspilerError::MemberAccessError {
@@ -2130,12 +2553,14 @@ impl Checker<'_> {
     ) -> HirExpression {
         let receiver = self.lower_expression(&operation.lhs, &Expected::free());
 
-        let (inner_ty, err_ty) = match &receiver.ty {
+        let (inner_ty, err_ty) = match self.resolve_alias(&receiver.ty) {
             TypeElement::Optional(optional) => (optional.inner.clone(), None),
             TypeElement::Result(result) => (result.success.clone(), Some(result.error.clone())),
             _ => (TypeElement::infer(), None),
         };
 
+        // Calls returning a borrow of the element are cloned inside the map
+        let mut access_borrows = false;
         let (access, access_ty) = match &operation.rhs.kind {
             ExpressionKind::Ident(field) => {
                 let field_ty = self.field_type(&inner_ty, field, span);
@@ -2151,7 +2576,9 @@ impl Checker<'_> {
                         .collect();
                     (
                         SafeAccessKind::Call(
-                            call.namespace.clone(),
+                            call.namespace
+                                .as_ref()
+                                .map(|namespace| self.canonical_namespace(namespace)),
                             call.identifier.clone(),
                             labels,
                             args,
@@ -2159,24 +2586,32 @@ impl Checker<'_> {
                         TypeElement::infer(),
                     )
                 } else {
-                    let receiver_ident = match &inner_ty {
-                        TypeElement::Plain(basic) => Some(basic.ident.clone()),
-                        TypeElement::Parametric(parametric) => Some(parametric.base_type.clone()),
-                        _ => None,
-                    };
                     let lookup = self.lookup;
                     let label_refs = label_refs(&labels);
-                    let function = lookup
-                        .resolve_function(receiver_ident.as_ref(), &call.identifier, &label_refs)
+                    let function = self
+                        .resolve_member_function(&inner_ty, &call.identifier, &label_refs)
                         .or_else(|| lookup.resolve_function(None, &call.identifier, &label_refs));
                     match function {
                         Some(function) => {
                             let signature = function.item.signature.clone();
-                            let args =
-                                self.lower_call_args(&signature.parameters.params, &call.arguments);
+                            access_borrows = self.returns_borrow(&function.item);
+                            let mut substitution = Substitution::default();
+                            if let Some(receiver) = signature.receiver() {
+                                self.instantiate_param(
+                                    receiver,
+                                    &inner_ty,
+                                    &mut substitution,
+                                    span,
+                                );
+                            }
+                            let args = self.lower_call_args(
+                                &signature.parameters.params,
+                                &call.arguments,
+                                &mut substitution,
+                            );
                             (
                                 SafeAccessKind::Call(None, call.identifier.clone(), labels, args),
-                                signature.return_type.clone(),
+                                substitution.apply(&signature.return_type),
                             )
                         }
                         None => {
@@ -2204,11 +2639,14 @@ impl Checker<'_> {
 
         let style = match receiver.adjusted_ownership() {
             Ownership::SharedOwned | Ownership::MutBorrowed => SafeAccessStyle::RefClone,
+            Ownership::UniqueOwned if access_borrows => SafeAccessStyle::Clone,
             Ownership::UniqueOwned => SafeAccessStyle::Move,
             Ownership::Borrowed => SafeAccessStyle::Clone,
             Ownership::Ref => {
                 self.errors.warning(
-                    "Safe-call on ref variables is not implemented yet".to_string(),
+                    TranspilerWarning::Unimplemented {
+                        feature: "safe-call on ref variables".to_string(),
+                    },
                     Some(span.into()),
                 );
                 SafeAccessStyle::RefClone
@@ -2247,7 +2685,7 @@ impl Checker<'_> {
         match postfix {
             PostfixExpression::YeetExpression(yeet) => {
                 let inner = self.lower_expression(&yeet.inner, &Expected::free());
-                let ty = match &inner.ty {
+                let ty = match &self.resolve_alias(&inner.ty) {
                     TypeElement::Optional(optional) => {
                         self.validate_yeet_return_type(&inner.ty);
                         optional.inner.clone()
@@ -2304,6 +2742,7 @@ impl Checker<'_> {
                     span,
                 )
             }
+            PostfixExpression::SliceExpression(slice) => self.lower_slice(slice, span),
         }
     }
 
@@ -2319,10 +2758,11 @@ impl Checker<'_> {
             (TypeElement::Optional(yeet), TypeElement::Optional(ret)) => {
                 if !types_compatible(&yeet.inner, &ret.inner) {
                     self.errors.warning(
-                        format!(
-                            "Yeet operator type mismatch: yielding {} but function returns {}",
-                            yeet.inner, ret.inner
-                        ),
+                        TranspilerWarning::YeetTypeMismatch {
+                            part: "",
+                            yielded: yeet.inner.to_string(),
+                            returned: ret.inner.to_string(),
+                        },
                         None,
                     );
                 }
@@ -2330,20 +2770,22 @@ impl Checker<'_> {
             (TypeElement::Result(yeet), TypeElement::Result(ret)) => {
                 if !types_compatible(&yeet.success, &ret.success) {
                     self.errors.warning(
-                        format!(
-                            "Yeet operator success type mismatch: yielding {} but function returns {}",
-                            yeet.success, ret.success
-                        ),
+                        TranspilerWarning::YeetTypeMismatch {
+                            part: "success ",
+                            yielded: yeet.success.to_string(),
+                            returned: ret.success.to_string(),
+                        },
                         None,
                     );
                 }
                 if let (Some(yeet_err), Some(ret_err)) = (&yeet.error, &ret.error) {
                     if !types_compatible(yeet_err, ret_err) {
                         self.errors.warning(
-                            format!(
-                                "Yeet operator error type mismatch: yielding {} but function expects {}",
-                                yeet_err, ret_err
-                            ),
+                            TranspilerWarning::YeetTypeMismatch {
+                                part: "error ",
+                                yielded: yeet_err.to_string(),
+                                returned: ret_err.to_string(),
+                            },
                             None,
                         );
                     }
@@ -2352,10 +2794,10 @@ impl Checker<'_> {
             (TypeElement::Optional(_), TypeElement::Result(_))
             | (TypeElement::Result(_), TypeElement::Optional(_)) => {
                 self.errors.warning(
-                    format!(
-                        "Yeet operator type incompatibility: yielding {} but function returns {}",
-                        yeet_ty, fn_return
-                    ),
+                    TranspilerWarning::YeetIncompatible {
+                        yielded: yeet_ty.to_string(),
+                        returned: fn_return.to_string(),
+                    },
                     None,
                 );
             }
@@ -2479,6 +2921,7 @@ impl Checker<'_> {
         let lookup = self.lookup;
         let type_decl = lookup.resolve_type(&constructor.identifier);
 
+        let mut substitution = Substitution::default();
         let args = match type_decl.map(|decl| &decl.item) {
             Some(TypeDecl::Struct(decl)) => {
                 let mut args = Vec::with_capacity(decl.members.len());
@@ -2496,8 +2939,14 @@ impl Checker<'_> {
                                 is_ref_field,
                                 "constructor arguments",
                             );
+                            let member_ty = self.instantiate_type(
+                                &member.r#type,
+                                &value.ty,
+                                &mut substitution,
+                                argument.expression.span,
+                            );
                             if !is_ref_field || argument.modifier != Some(DeclModifier::Ref) {
-                                let expected = Expected::owned(member.r#type.clone());
+                                let expected = Expected::owned(member_ty);
                                 value = self.coerce(value, &expected);
                             }
                             value
@@ -2505,7 +2954,13 @@ impl Checker<'_> {
                         None => match &member.default_value {
                             Some(default) => {
                                 let value = self.lower_expression(default, &Expected::free());
-                                let expected = Expected::owned(member.r#type.clone());
+                                let member_ty = self.instantiate_type(
+                                    &member.r#type,
+                                    &value.ty,
+                                    &mut substitution,
+                                    default.span,
+                                );
+                                let expected = Expected::owned(member_ty);
                                 self.coerce(value, &expected)
                             }
                             None => {
@@ -2546,12 +3001,40 @@ impl Checker<'_> {
                 .collect(),
         };
 
+        // Generic types are instantiated with the type arguments bound by the
+        // constructor arguments, as long as all of them could be inferred
+        let generics = type_decl
+            .map(|decl| decl.item.generic_params())
+            .unwrap_or_default();
+        let type_args = generics
+            .iter()
+            .map(|generic| {
+                substitution.apply(&TypeElement::Generic(GenericTypeItem {
+                    ident: generic.clone(),
+                    span: Span::default(),
+                }))
+            })
+            .collect::<Vec<_>>();
+        let ty = if generics.is_empty()
+            || type_args
+                .iter()
+                .any(|arg| matches!(arg, TypeElement::Generic(_)))
+        {
+            plain_type(constructor.identifier.clone())
+        } else {
+            TypeElement::Parametric(ParametricTypeItem {
+                base_type: constructor.identifier.clone(),
+                type_args,
+                span,
+            })
+        };
+
         HirExpression::new(
             HirExpressionKind::ConstructorCall(HirConstructorCall {
                 ident: constructor.identifier.clone(),
                 args,
             }),
-            plain_type(constructor.identifier.clone()),
+            ty,
             Ownership::UniqueOwned,
             span,
         )
@@ -2617,6 +3100,7 @@ impl Checker<'_> {
                         .cloned()
                         .unwrap_or_else(TypeElement::infer)
                 } else {
+                    self.register_unions(&parameter.ty);
                     parameter.ty.clone()
                 };
                 let ownership = if self.is_copy(&ty) && !ty.is_infer() {
@@ -2646,7 +3130,10 @@ impl Checker<'_> {
             }
             _ => Expected::free(),
         };
+        // `break` and `continue` cannot leave the closure
+        let loops = std::mem::take(&mut self.loops);
         let body = self.lower_block(&closure.block.body, &body_expected);
+        self.loops = loops;
         self.scopes.pop();
 
         let ty = TypeElement::Closure(Box::new(ClosureTypeItem {
@@ -2671,21 +3158,21 @@ impl Checker<'_> {
 // Helpers
 // ----------------------------------------------------------------------
 
-fn plain_type(ident: TypeIdent) -> TypeElement {
+pub(super) fn plain_type(ident: TypeIdent) -> TypeElement {
     TypeElement::Plain(BasicTypeItem {
         ident,
         span: Span::default(),
     })
 }
 
-fn closure_argument(argument: &FunctionCallArg) -> Option<&Closure> {
+pub(super) fn closure_argument(argument: &FunctionCallArg) -> Option<&Closure> {
     match &argument.expression.kind {
         ExpressionKind::Closure(closure) => Some(closure),
         _ => None,
     }
 }
 
-fn try_ok_parameters(body: &Closure, ok_ty: &TypeElement) -> Vec<(Ident, TypeElement)> {
+pub(super) fn try_ok_parameters(body: &Closure, ok_ty: &TypeElement) -> Vec<(Ident, TypeElement)> {
     if body.parameters.is_empty() {
         return vec![(Ident::new("it"), ok_ty.clone())];
     }
@@ -2732,7 +3219,7 @@ fn value_argument_ownership(is_copy: bool) -> Ownership {
     }
 }
 
-fn match_binding_ownership(is_copy: bool, is_infer: bool) -> Ownership {
+pub(super) fn match_binding_ownership(is_copy: bool, is_infer: bool) -> Ownership {
     if is_copy || is_infer {
         Ownership::UniqueOwned
     } else {
@@ -2740,6 +3227,38 @@ fn match_binding_ownership(is_copy: bool, is_infer: bool) -> Ownership {
     }
 }
 
+fn binding_names(bindings: &[Variable]) -> Vec<&Ident> {
+    let mut names = bindings
+        .iter()
+        .map(|binding| &binding.ident)
+        .collect::<Vec<_>>();
+    names.sort_by_key(|ident| ident.as_str());
+    names
+}
+
+/// The types of the first variable that is bound with incompatible types by
+/// two alternatives of a pattern
+fn mismatched_binding<'v>(
+    bound: &'v [Variable],
+    alternative: &'v [Variable],
+) -> Option<(&'v TypeElement, &'v TypeElement)> {
+    alternative.iter().find_map(|variable| {
+        let expected = bound
+            .iter()
+            .find(|binding| binding.ident == variable.ident)?;
+        (!types_compatible(&expected.ty, &variable.ty)).then_some((&expected.ty, &variable.ty))
+    })
+}
+
+/// Whether the pattern compares the scrutinee with a string literal
+fn has_string_pattern(pattern: &HirMatchPattern) -> bool {
+    match pattern {
+        HirMatchPattern::Literal(literal) => matches!(literal, HirLiteral::String(_)),
+        HirMatchPattern::Alternatives(alternatives) => alternatives.iter().any(has_string_pattern),
+        _ => false,
+    }
+}
+
 fn argument_labels(arguments: &[FunctionCallArg]) -> Vec<Ident> {
     arguments
         .iter()
@@ -2825,30 +3344,6 @@ fn for_binding_ownership(deref: bool) -> Ownership {
     }
 }
 
-fn unify_tuple_iteration_type(
-    elements: &[TypeElement],
-    errors: &mut ErrorCollector,
-) -> TypeElement {
-    let mut unified = None;
-    for element in elements {
-        unified = match unified {
-            None => Some(element.clone()),
-            Some(current) => unify_types(&current, element).or_else(|| {
-                errors.warning(
-                    format!(
-                        "Tuple iteration requires compatible element types, found {} and {}",
-                        current, element
-                    ),
-                    None,
-                );
-                Some(TypeElement::infer())
-            }),
-        };
-    }
-
-    unified.unwrap_or_else(TypeElement::infer)
-}
-
 /// Infer the most appropriate type for a number literal
 fn infer_number_type(value: &str) -> TypeElement {
     if value.contains('.') || value.contains('e') || value.contains('E') {
@@ -2904,22 +3399,54 @@ fn extract_type_suffix(value: &str) -> Option<&'static str> {
     }
 }
 
-fn are_compatible_numeric_types(a: &TypeIdent, b: &TypeIdent) -> bool {
-    let integer_types = [
-        "I8", "I16", "I32", "I64", "I128", "ISize", "Int", "U8", "U16", "U32", "U64", "U128",
-        "USize", "UInt",
-    ];
-    let float_types = ["Float", "Double"];
+const INTEGER_TYPES: [&str; 14] = [
+    "I8", "I16", "I32", "I64", "I128", "ISize", "Int", "U8", "U16", "U32", "U64", "U128", "USize",
+    "UInt",
+];
+const FLOAT_TYPES: [&str; 2] = ["Float", "Double"];
 
+fn are_compatible_numeric_types(a: &TypeIdent, b: &TypeIdent) -> bool {
     let a = a.as_str();
     let b = b.as_str();
 
-    (integer_types.contains(&a) && integer_types.contains(&b))
-        || (float_types.contains(&a) && float_types.contains(&b))
+    (INTEGER_TYPES.contains(&a) && INTEGER_TYPES.contains(&b))
+        || (FLOAT_TYPES.contains(&a) && FLOAT_TYPES.contains(&b))
         || a.starts_with("__")
         || b.starts_with("__")
 }
 
+/// Whether the type is one of the builtin integer or floating point types
+pub(super) fn is_numeric_type(ident: &TypeIdent) -> bool {
+    INTEGER_TYPES.contains(&ident.as_str()) || FLOAT_TYPES.contains(&ident.as_str())
+}
+
+/// Whether values of the type can be integers. Number literals and types
+/// not known yet are accepted and left to rustc.
+pub(super) fn is_integer_type(ty: &TypeElement) -> bool {
+    match ty {
+        TypeElement::Infer(_) | TypeElement::Generic(_) | TypeElement::Never(_) => true,
+        TypeElement::Plain(plain) => {
+            ty.is_number() || INTEGER_TYPES.contains(&plain.ident.as_str())
+        }
+        _ => false,
+    }
+}
+
+/// Whether values of the type can be negated: signed integers and floating
+/// point numbers. Number literals and types not known yet are accepted.
+pub(super) fn is_signed_numeric_type(ty: &TypeElement) -> bool {
+    match ty {
+        TypeElement::Infer(_) | TypeElement::Generic(_) | TypeElement::Never(_) => true,
+        TypeElement::Plain(plain) => {
+            let ident = plain.ident.as_str();
+            ty.is_number()
+                || FLOAT_TYPES.contains(&ident)
+                || (INTEGER_TYPES.contains(&ident) && !ident.starts_with('U'))
+        }
+        _ => false,
+    }
+}
+
 fn modifier_name(modifier: DeclModifier) -> &'static str {
     match modifier {
         DeclModifier::Let => "let",
@@ -2937,7 +3464,7 @@ fn passing_mode_name(modifier: Option<DeclModifier>) -> &'static str {
     }
 }
 
-fn receiver_type_ident(ty: &TypeElement) -> Option<TypeIdent> {
+pub(super) fn receiver_type_ident(ty: &TypeElement) -> Option<TypeIdent> {
     match ty {
         TypeElement::Plain(basic) => Some(basic.ident.clone()),
         TypeElement::Parametric(parametric) => Some(parametric.base_type.clone()),
@@ -0,0 +1,210 @@
+//! Unification of generic type parameters for a single call.
+
+use std::collections::HashMap;
+
+use galvan_ast::{
+    ArrayTypeItem, ClosureTypeItem, DictionaryTypeItem, Ident, OptionalTypeItem,
+    OrderedDictionaryTypeItem, ParametricTypeItem, ResultTypeItem, SetTypeItem, TupleTypeItem,
+    TypeElement, UnionTypeItem,
+};
+
+use crate::builtins::CheckBuiltins;
+
+use super::types_compatible;
+
+/// A generic parameter that was bound to two incompatible types
+#[derive(Debug)]
+pub(crate) struct GenericConflict {
+    pub generic: Ident,
+    pub bound: TypeElement,
+    pub found: TypeElement,
+}
+
+/// Bindings of generic type parameters to concrete types, built per call
+/// from the receiver and argument types
+#[derive(Debug, Default)]
+pub(crate) struct Substitution {
+    bindings: HashMap<Ident, TypeElement>,
+    /// The type `Self` stands for in trait member signatures
+    self_type: Option<TypeElement>,
+}
+
+impl Substitution {
+    /// A substitution that replaces `Self` with the given type
+    pub fn with_self(self_type: TypeElement) -> Self {
+        Self {
+            bindings: HashMap::new(),
+            self_type: Some(self_type),
+        }
+    }
+
+    /// Binds the generic parameters in `param` by structurally matching it
+    /// against the type of the value passed for it
+    pub fn unify(
+        &mut self,
+        param: &TypeElement,
+        actual: &TypeElement,
+    ) -> Result<(), Box<GenericConflict>> {
+        match (param, actual) {
+            (_, TypeElement::Infer(_) | TypeElement::Never(_)) => Ok(()),
+            (TypeElement::Generic(generic), actual) => self.bind(&generic.ident, actual),
+            (TypeElement::Array(a), TypeElement::Array(b)) => self.unify(&a.elements, &b.elements),
+            (TypeElement::Set(a), TypeElement::Set(b)) => self.unify(&a.elements, &b.elements),
+            (TypeElement::Dictionary(a), TypeElement::Dictionary(b)) => {
+                self.unify(&a.key, &b.key)?;
+                self.unify(&a.value, &b.value)
+            }
+            (TypeElement::OrderedDictionary(a), TypeElement::OrderedDictionary(b)) => {
+                self.unify(&a.key, &b.key)?;
+                self.unify(&a.value, &b.value)
+            }
+            (TypeElement::Tuple(a), TypeElement::Tuple(b))
+                if a.elements.len() == b.elements.len() =>
+            {
+                a.elements
+                    .iter()
+                    .zip(&b.elements)
+                    .try_for_each(|(a, b)| self.unify(a, b))
+            }
+            (TypeElement::Optional(a), TypeElement::Optional(b)) => self.unify(&a.inner, &b.inner),
+            // Values are wrapped in `Some` when passed for an optional
+            (TypeElement::Optional(a), actual) => self.unify(&a.inner, actual),
+            (TypeElement::Result(a), TypeElement::Result(b)) => {
+                self.unify(&a.success, &b.success)?;
+                match (&a.error, &b.error) {
+                    (Some(a), Some(b)) => self.unify(a, b),
+                    _ => Ok(()),
+                }
+            }
+            (TypeElement::Parametric(a), TypeElement::Parametric(b))
+                if a.base_type == b.base_type && a.type_args.len() == b.type_args.len() =>
+            {
+                a.type_args
+                    .iter()
+                    .zip(&b.type_args)
+                    .try_for_each(|(a, b)| self.unify(a, b))
+            }
+            (TypeElement::Closure(a), TypeElement::Closure(b))
+                if a.parameters.len() == b.parameters.len() =>
+            {
+                a.parameters
+                    .iter()
+                    .zip(&b.parameters)
+                    .try_for_each(|(a, b)| self.unify(a, b))?;
+                self.unify(&a.return_ty, &b.return_ty)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn bind(&mut self, generic: &Ident, actual: &TypeElement) -> Result<(), Box<GenericConflict>> {
+        let Some(bound) = self.bindings.get(generic) else {
+            self.bindings.insert(generic.clone(), actual.clone());
+            return Ok(());
+        };
+
+        if !types_compatible(bound, actual) {
+            return Err(Box::new(GenericConflict {
+                generic: generic.clone(),
+                bound: bound.clone(),
+                found: actual.clone(),
+            }));
+        }
+
+        // A number literal only constrains the parameter until a concrete
+        // numeric type is found
+        if bound.is_number() && !actual.is_number() {
+            self.bindings.insert(generic.clone(), actual.clone());
+        }
+        Ok(())
+    }
+
+    /// Binds generic parameters directly, e.g. the type parameters of a
+    /// generic type from the arguments of a parametric type
+    pub fn bind_all(&mut self, generics: &[Ident], args: &[TypeElement]) {
+        for (generic, arg) in generics.iter().zip(args) {
+            if !arg.is_infer() {
+                self.bindings.insert(generic.clone(), arg.clone());
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty() && self.self_type.is_none()
+    }
+
+    /// Instantiates a type by replacing all bound generic parameters
+    pub fn apply(&self, ty: &TypeElement) -> TypeElement {
+        if self.is_empty() {
+            return ty.clone();
+        }
+
+        match ty {
+            TypeElement::Generic(generic) => self
+                .bindings
+                .get(&generic.ident)
+                .cloned()
+                .unwrap_or_else(|| ty.clone()),
+            TypeElement::Array(array) => TypeElement::Array(Box::new(ArrayTypeItem {
+                elements: self.apply(&array.elements),
+                span: array.span,
+            })),
+            TypeElement::Set(set) => TypeElement::Set(Box::new(SetTypeItem {
+                elements: self.apply(&set.elements),
+                span: set.span,
+            })),
+            TypeElement::Dictionary(dict) => {
+                TypeElement::Dictionary(Box::new(DictionaryTypeItem {
+                    key: self.apply(&dict.key),
+                    value: self.apply(&dict.value),
+                    span: dict.span,
+                }))
+            }
+            TypeElement::OrderedDictionary(dict) => {
+                TypeElement::OrderedDictionary(Box::new(OrderedDictionaryTypeItem {
+                    key: self.apply(&dict.key),
+                    value: self.apply(&dict.value),
+                    span: dict.span,
+                }))
+            }
+            TypeElement::Tuple(tuple) => TypeElement::Tuple(Box::new(TupleTypeItem {
+                elements: tuple.elements.iter().map(|ty| self.apply(ty)).collect(),
+                span: tuple.span,
+            })),
+            TypeElement::Optional(optional) => TypeElement::Optional(Box::new(OptionalTypeItem {
+                inner: self.apply(&optional.inner),
+                span: optional.span,
+            })),
+            TypeElement::Result(result) => TypeElement::Result(Box::new(ResultTypeItem {
+                success: self.apply(&result.success),
+                error: result.error.as_ref().map(|error| self.apply(error)),
+                span: result.span,
+            })),
+            TypeElement::Parametric(parametric) => TypeElement::Parametric(ParametricTypeItem {
+                base_type: parametric.base_type.clone(),
+                type_args: parametric
+                    .type_args
+                    .iter()
+                    .map(|ty| self.apply(ty))
+                    .collect(),
+                span: parametric.span,
+            }),
+            TypeElement::Closure(closure) => TypeElement::Closure(Box::new(ClosureTypeItem {
+                parameters: closure.parameters.iter().map(|ty| self.apply(ty)).collect(),
+                return_ty: self.apply(&closure.return_ty),
+                span: closure.span,
+            })),
+            TypeElement::Union(union) => TypeElement::Union(Box::new(UnionTypeItem {
+                members: union.members.iter().map(|ty| self.apply(ty)).collect(),
+                span: union.span,
+            })),
+            TypeElement::Plain(plain) if plain.ident.as_str() == "Self" => {
+                self.self_type.clone().unwrap_or_else(|| ty.clone())
+            }
+            TypeElement::Plain(_)
+            | TypeElement::Void(_)
+            | TypeElement::Infer(_)
+            | TypeElement::Never(_) => ty.clone(),
+        }
+    }
+}
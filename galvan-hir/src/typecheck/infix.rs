@@ -0,0 +1,228 @@
+//! User-defined infix operators.
+//!
+//! `infix <+> = combine, precedence: 6, associativity: right` binds the
+//! operator `<+>` to the function `combine`, which has to take exactly two
+//! parameters. Chains of custom operators are re-associated according to the
+//! declared precedence and associativity and then desugared into plain calls,
+//! so `a <+> b` is typechecked and lowered exactly like `combine(a, b)`.
+
+use galvan_ast::{
+    Associativity, CustomInfix, Expression, ExpressionKind, FnDecl, FunctionCall, FunctionCallArg,
+    Ident, InfixDecl, InfixExpression, InfixOperation, InfixOperator, SegmentedAsts, Span,
+    ToplevelItem,
+};
+use galvan_resolver::Lookup;
+
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::{Checker, Expected};
+
+impl<'a> Checker<'a> {
+    /// Checks that every custom infix operator is bound to a function with
+    /// two parameters
+    pub(crate) fn check_infixes(&mut self, asts: &SegmentedAsts) {
+        for infix_decl in &asts.infixes {
+            self.enter_module(&infix_decl.source);
+            let decl = &infix_decl.item;
+            let function = match self.infix_functions(decl).as_slice() {
+                [function] => *function,
+                [] => {
+                    self.errors.error_with_span(
+                        TranspilerError::UnknownIdentifier {
+                            name: decl.function.to_string(),
+                        },
+                        Some(decl.span.into()),
+                    );
+                    continue;
+                }
+                overloads => {
+                    self.errors.error_with_span(
+                        TranspilerError::InvalidSyntax {
+                            message: format!(
+                                "Infix operator {} is bound to {}, which has {} overloads",
+                                decl.operator.symbol(),
+                                decl.function,
+                                overloads.len()
+                            ),
+                        },
+                        Some(decl.span.into()),
+                    );
+                    continue;
+                }
+            };
+
+            let found = function.item.signature.parameters.params.len();
+            if found != 2 {
+                self.errors.error_with_span(
+                    TranspilerError::ArgumentCountMismatch {
+                        name: format!(
+                            "{} (bound to infix operator {})",
+                            decl.function,
+                            decl.operator.symbol()
+                        ),
+                        expected: 2,
+                        found,
+                    },
+                    Some(decl.span.into()),
+                );
+            }
+        }
+    }
+
+    /// The functions an infix operator may be bound to. An unlabeled function
+    /// takes precedence, otherwise every free overload of the name is a
+    /// candidate, so operators can be bound to functions with labels.
+    fn infix_functions(&self, decl: &InfixDecl) -> Vec<&'a ToplevelItem<FnDecl>> {
+        let lookup = self.lookup;
+        if let Some(function) = lookup.resolve_function(None, &decl.function, &[]) {
+            return vec![function];
+        }
+
+        lookup
+            .functions
+            .values()
+            .copied()
+            .filter(|function| {
+                let signature = &function.item.signature;
+                signature.identifier == decl.function
+                    && !signature
+                        .parameters
+                        .params
+                        .first()
+                        .is_some_and(|param| param.identifier.is_self())
+            })
+            .collect()
+    }
+
+    /// Lowers a chain of custom infix operations as calls to the functions
+    /// the operators are bound to
+    pub(crate) fn lower_custom_infix(
+        &mut self,
+        operation: &InfixOperation<CustomInfix>,
+        expected: &Expected,
+        span: Span,
+    ) -> HirExpression {
+        let mut operands = Vec::new();
+        let mut operators = Vec::new();
+        flatten_custom_infix(&operation.lhs, &mut operands, &mut operators);
+        operators.push(&operation.operator);
+        flatten_custom_infix(&operation.rhs, &mut operands, &mut operators);
+
+        let lookup = self.lookup;
+        let mut decls = Vec::with_capacity(operators.len());
+        for operator in operators {
+            match lookup.resolve_infix(operator) {
+                Some(decl) => decls.push(self.bind_infix(&decl.item)),
+                None => {
+                    self.errors.error_with_span(
+                        TranspilerError::UnknownIdentifier {
+                            name: format!("infix operator {}", operator.symbol()),
+                        },
+                        Some(span.into()),
+                    );
+                    return HirExpression::error("unknown infix operator", span);
+                }
+            }
+        }
+
+        let mut position = 0;
+        let desugared = associate(&operands, &decls, &mut position, 0);
+        self.lower_expression(&desugared, expected)
+    }
+
+    fn bind_infix<'d>(&self, decl: &'d InfixDecl) -> BoundInfix<'d> {
+        let labels = match self.infix_functions(decl).as_slice() {
+            [function] => {
+                let mut labels = function
+                    .item
+                    .signature
+                    .parameters
+                    .params
+                    .iter()
+                    .map(|param| param.call_label().cloned());
+                [labels.next().flatten(), labels.next().flatten()]
+            }
+            // Already reported when checking the declaration
+            _ => [None, None],
+        };
+        BoundInfix { decl, labels }
+    }
+}
+
+/// An infix operator together with the call labels of the parameters of
+/// the function it is bound to
+struct BoundInfix<'d> {
+    decl: &'d InfixDecl,
+    labels: [Option<Ident>; 2],
+}
+
+/// Collects the operands and operators of a chain of custom infix operations
+/// in source order. Groups are kept as a single operand.
+fn flatten_custom_infix<'e>(
+    expression: &'e Expression,
+    operands: &mut Vec<&'e Expression>,
+    operators: &mut Vec<&'e CustomInfix>,
+) {
+    match &expression.kind {
+        ExpressionKind::Infix(infix) => match infix.as_ref() {
+            InfixExpression::Custom(operation) => {
+                flatten_custom_infix(&operation.lhs, operands, operators);
+                operators.push(&operation.operator);
+                flatten_custom_infix(&operation.rhs, operands, operators);
+            }
+            _ => operands.push(expression),
+        },
+        _ => operands.push(expression),
+    }
+}
+
+/// Rebuilds the chain by precedence climbing, starting at the operand in
+/// front of the operator at `position`
+fn associate(
+    operands: &[&Expression],
+    decls: &[BoundInfix<'_>],
+    position: &mut usize,
+    min_precedence: u16,
+) -> Expression {
+    let mut lhs = operands[*position].clone();
+    while let Some(infix) = decls.get(*position) {
+        let decl = infix.decl;
+        let precedence = u16::from(decl.precedence);
+        if precedence < min_precedence {
+            break;
+        }
+
+        *position += 1;
+        let next_precedence = match decl.associativity {
+            Associativity::Left => precedence + 1,
+            Associativity::Right => precedence,
+        };
+        let rhs = associate(operands, decls, position, next_precedence);
+        lhs = desugar(infix, lhs, rhs);
+    }
+    lhs
+}
+
+fn desugar(infix: &BoundInfix<'_>, lhs: Expression, rhs: Expression) -> Expression {
+    let span = Span {
+        range: (lhs.span.range.0, rhs.span.range.1),
+        start: lhs.span.start,
+        end: rhs.span.end,
+    };
+    let [lhs_label, rhs_label] = infix.labels.clone();
+    let argument = |label, expression| FunctionCallArg {
+        label,
+        modifier: None,
+        expression,
+    };
+
+    Expression {
+        kind: ExpressionKind::FunctionCall(FunctionCall {
+            namespace: None,
+            identifier: infix.decl.function.clone(),
+            arguments: vec![argument(lhs_label, lhs), argument(rhs_label, rhs)],
+        }),
+        span,
+    }
+}
@@ -0,0 +1,344 @@
+//! `loop` and `while` loops and the `break` and `continue` statements.
+//!
+//! `loop { ... }` runs until a `break`, `while cond { ... }` while the
+//! condition holds and `while try opt |x| { ... }` while the optional or
+//! result has a value. Loops can be labeled, e.g. `outer@ for rows |row| {}`,
+//! so that `break@outer` and `continue@outer` address an enclosing loop from
+//! inside a nested one. `break value` ends a `loop` with a value, which makes
+//! `loop` usable as an expression.
+
+use galvan_ast::{
+    Break, Closure, Continue, DeclModifier, Expression, ExpressionKind, FunctionCall,
+    FunctionCallArg, Ident, LabeledExpression, NeverTypeItem, Ownership, Span, TypeElement,
+};
+
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::expr::{closure_argument, try_ok_parameters};
+use super::{types_compatible, Checker, Expected, Variable};
+
+/// A loop enclosing the code currently being lowered
+pub(crate) struct LoopFrame {
+    label: Option<Ident>,
+    /// Whether `break` can carry a value, which only `loop` allows
+    accepts_value: bool,
+    /// Expectation for the values given to `break`
+    expected: Expected,
+    /// Type of the values given to `break` so far
+    value_ty: Option<TypeElement>,
+    /// Whether any `break` leaves this loop
+    breaks: bool,
+}
+
+impl Checker<'_> {
+    pub(crate) fn push_loop(
+        &mut self,
+        label: Option<Ident>,
+        accepts_value: bool,
+        expected: Expected,
+    ) {
+        self.loops.push(LoopFrame {
+            label,
+            accepts_value,
+            expected,
+            value_ty: None,
+            breaks: false,
+        });
+    }
+
+    pub(crate) fn pop_loop(&mut self) -> Option<LoopFrame> {
+        self.loops.pop()
+    }
+
+    /// Lowers a labeled expression, which has to be a loop
+    pub(crate) fn lower_labeled(
+        &mut self,
+        labeled: &LabeledExpression,
+        expected: &Expected,
+        span: Span,
+    ) -> HirExpression {
+        let label = Some(labeled.label.clone());
+        if let ExpressionKind::FunctionCall(call) = &labeled.inner.kind {
+            if call.namespace.is_none() {
+                match call.identifier.as_str() {
+                    "for" => return self.lower_for(call, expected, label, span),
+                    "loop" => return self.lower_loop(call, expected, label, span),
+                    "while" => return self.lower_while(call, label, span),
+                    _ => {}
+                }
+            }
+        }
+
+        self.errors.error_with_span(
+            TranspilerError::InvalidSyntax {
+                message: format!("Label @{} can only be applied to loops", labeled.label),
+            },
+            Some(span.into()),
+        );
+        self.lower_expression(&labeled.inner, expected)
+    }
+
+    /// Lowers `loop { ... }`. The loop has the type of the values given to
+    /// `break`, or never completes if nothing breaks out of it.
+    pub(crate) fn lower_loop(
+        &mut self,
+        call: &FunctionCall,
+        expected: &Expected,
+        label: Option<Ident>,
+        span: Span,
+    ) -> HirExpression {
+        let Some(body) = call.arguments.first().and_then(closure_argument) else {
+            self.errors.error(TranspilerError::MissingArgument {
+                operation: "loop".to_string(),
+                argument_type: "body expression".to_string(),
+            });
+            return HirExpression::error("invalid loop body", span);
+        };
+        if call.arguments.len() > 1 || !body.parameters.is_empty() {
+            self.errors.error_with_span(
+                TranspilerError::InvalidSyntax {
+                    message: "`loop` only takes a body".to_string(),
+                },
+                Some(span.into()),
+            );
+        }
+
+        let value_expected = if expected.is_void() {
+            Expected::free()
+        } else {
+            expected.clone()
+        };
+        self.push_loop(label.clone(), true, value_expected);
+        let block = self.lower_block(&body.block.body, &Expected::void());
+        let frame = self.pop_loop();
+
+        let ty = match frame {
+            Some(LoopFrame {
+                value_ty: Some(ty), ..
+            }) => ty,
+            Some(LoopFrame { breaks: true, .. }) => TypeElement::void(),
+            _ => TypeElement::Never(NeverTypeItem {
+                span: Span::default(),
+            }),
+        };
+
+        HirExpression::new(
+            HirExpressionKind::Loop(Box::new(HirLoop {
+                kind: HirLoopKind::Infinite,
+                body: block,
+                label,
+            })),
+            ty,
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+
+    /// Lowers `while cond { ... }` and `while try opt |x| { ... }`
+    pub(crate) fn lower_while(
+        &mut self,
+        call: &FunctionCall,
+        label: Option<Ident>,
+        span: Span,
+    ) -> HirExpression {
+        let (condition, body) = match call.arguments.as_slice() {
+            [condition, body] => (condition, closure_argument(body)),
+            // The body of `while try opt |x| { ... }` is parsed as the
+            // trailing closure of `try`
+            [condition] if try_condition(condition).is_some() => (
+                condition,
+                try_condition(condition).and_then(|(_, body)| body),
+            ),
+            _ => {
+                self.errors.error(TranspilerError::MissingArgument {
+                    operation: "while".to_string(),
+                    argument_type: "condition and body".to_string(),
+                });
+                return HirExpression::error("invalid while", span);
+            }
+        };
+        let Some(body) = body else {
+            self.errors.error(TranspilerError::MissingArgument {
+                operation: "while".to_string(),
+                argument_type: "body expression".to_string(),
+            });
+            return HirExpression::error("invalid while body", span);
+        };
+
+        self.scopes.push();
+        let kind = match try_condition(condition) {
+            Some((condition, _)) => {
+                let condition = self.lower_expression(condition, &Expected::free());
+                // The condition is evaluated on every iteration, so owned
+                // variables are borrowed instead of moved into the first one
+                let condition = match condition.adjusted_ownership() {
+                    Ownership::SharedOwned => condition.adjusted(Adjustment::Borrow),
+                    _ => self.try_scrutinee(condition),
+                };
+                let (kind, ok_ty) = match &self.resolve_alias(&condition.ty) {
+                    TypeElement::Optional(optional) => (TryKind::Optional, optional.inner.clone()),
+                    TypeElement::Result(result) => (TryKind::Result, result.success.clone()),
+                    _ => (TryKind::Optional, TypeElement::infer()),
+                };
+
+                let scrutinee_ownership = condition.adjusted_ownership();
+                let bindings = try_ok_parameters(body, &ok_ty)
+                    .into_iter()
+                    .map(|(ident, ty)| {
+                        let ownership = self.unwrap_binding_ownership(&ty, scrutinee_ownership);
+                        self.scopes.declare(Variable {
+                            ident: ident.clone(),
+                            modifier: DeclModifier::Let,
+                            ty,
+                            ownership,
+                        });
+                        ident
+                    })
+                    .collect();
+
+                HirLoopKind::WhileTry {
+                    condition,
+                    kind,
+                    bindings,
+                }
+            }
+            None => {
+                if !body.parameters.is_empty() {
+                    self.errors.error_with_span(
+                        TranspilerError::InvalidSyntax {
+                            message: "Only `while try` binds parameters".to_string(),
+                        },
+                        Some(span.into()),
+                    );
+                }
+                HirLoopKind::While(
+                    self.lower_expression(
+                        &condition.expression,
+                        &Expected::owned(TypeElement::bool()),
+                    ),
+                )
+            }
+        };
+
+        self.push_loop(label.clone(), false, Expected::void());
+        let block = self.lower_block(&body.block.body, &Expected::void());
+        self.pop_loop();
+        self.scopes.pop();
+
+        HirExpression::new(
+            HirExpressionKind::Loop(Box::new(HirLoop {
+                kind,
+                body: block,
+                label,
+            })),
+            TypeElement::void(),
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+
+    pub(crate) fn lower_break(&mut self, brk: &Break) -> HirBreak {
+        let target = self.loop_target(brk.label.as_ref(), "break", brk.span);
+
+        let value = brk.value.as_ref().map(|value| {
+            let Some(target) = target else {
+                return self.lower_expression(value, &Expected::free());
+            };
+            if !self.loops[target].accepts_value {
+                self.errors.error_with_span(
+                    TranspilerError::InvalidSyntax {
+                        message: "`break` with a value is only allowed in `loop`".to_string(),
+                    },
+                    Some(value.span.into()),
+                );
+            }
+
+            let expected = self.loops[target].expected.clone();
+            let lowered = self.lower_expression(value, &expected);
+            let (lowered, ty) = if expected.is_free() {
+                let lowered = self.ensure_owned(lowered);
+                let ty = lowered.ty.clone();
+                (lowered, ty)
+            } else {
+                (lowered, expected.ty)
+            };
+
+            match &self.loops[target].value_ty {
+                Some(previous) if !types_compatible(previous, &ty) => {
+                    self.errors.error_with_span(
+                        TranspilerError::TypeMismatch {
+                            expected: previous.to_string(),
+                            found: ty.to_string(),
+                        },
+                        Some(value.span.into()),
+                    );
+                }
+                Some(_) => {}
+                None => self.loops[target].value_ty = Some(ty),
+            }
+            lowered
+        });
+
+        if let Some(target) = target {
+            self.loops[target].breaks = true;
+        }
+
+        HirBreak {
+            label: brk.label.clone(),
+            value,
+            span: brk.span,
+        }
+    }
+
+    pub(crate) fn lower_continue(&mut self, cont: &Continue) -> HirContinue {
+        self.loop_target(cont.label.as_ref(), "continue", cont.span);
+
+        HirContinue {
+            label: cont.label.clone(),
+            span: cont.span,
+        }
+    }
+
+    /// The index of the loop a `break` or `continue` refers to: the loop with
+    /// the given label, or the innermost loop without a label
+    fn loop_target(&mut self, label: Option<&Ident>, keyword: &str, span: Span) -> Option<usize> {
+        let target = match label {
+            Some(label) => self
+                .loops
+                .iter()
+                .rposition(|frame| frame.label.as_ref() == Some(label)),
+            None => self.loops.len().checked_sub(1),
+        };
+
+        if target.is_none() {
+            let error = match label {
+                Some(label) => TranspilerError::UnknownIdentifier {
+                    name: format!("loop label @{label}"),
+                },
+                None => TranspilerError::InvalidSyntax {
+                    message: format!("`{keyword}` outside of a loop"),
+                },
+            };
+            self.errors.error_with_span(error, Some(span.into()));
+        }
+        target
+    }
+}
+
+/// The optional or result of `while try opt |x| { ... }`, and the body if
+/// it was parsed as the trailing closure of `try`
+fn try_condition(argument: &FunctionCallArg) -> Option<(&Expression, Option<&Closure>)> {
+    match &argument.expression.kind {
+        ExpressionKind::FunctionCall(call)
+            if call.namespace.is_none() && call.identifier.as_str() == "try" =>
+        {
+            match call.arguments.as_slice() {
+                [condition] => Some((&condition.expression, None)),
+                [condition, body] => Some((&condition.expression, Some(closure_argument(body)?))),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -6,18 +6,38 @@
 //! through the returned [`HirExpression`]s, and [`Checker::coerce`] reconciles
 //! the two by attaching explicit [`Adjustment`](crate::hir::Adjustment)s.
 
+mod aliases;
 mod coerce;
+mod collections;
+mod destructuring;
+mod exhaustiveness;
 mod expr;
+mod generics;
+mod infix;
+mod loops;
+mod modules;
+mod operators;
+mod returns;
 mod scope;
+mod std_calls;
+mod traits;
+mod tuples;
+mod unary;
+mod union;
+
+use std::collections::HashMap;
 
 use galvan_ast::{
-    Assignment, AssignmentOperator, Body, DeclModifier, Declaration, FnDecl, Ident, MainKind,
-    Ownership, SegmentedAsts, Span, Statement, ToplevelItem, TypeElement,
+    Assignment, AssignmentOperator, Body, DeclModifier, Declaration, DeclarationPattern, FnDecl,
+    Ident, MainKind, Ownership, SegmentedAsts, Span, Statement, ToplevelItem, TypeDecl,
+    TypeElement, TypeIdent,
 };
-use galvan_resolver::{LookupContext, LookupError};
+use galvan_files::ModulePath;
+use galvan_resolver::rustdoc::RustdocCrate;
+use galvan_resolver::{LookupContext, LookupError, ModuleScopes};
 
 use crate::builtins::{builtin_fns, builtins, predefined_from, CheckBuiltins};
-use crate::error::ErrorCollector;
+use crate::error::{ErrorCollector, TranspilerWarning};
 use crate::hir::*;
 use crate::mapping::Mapping;
 
@@ -25,7 +45,9 @@ pub use scope::Variable;
 
 pub use coerce::types_compatible;
 pub(crate) use coerce::{concat_kind, Expected};
+pub(crate) use loops::LoopFrame;
 pub(crate) use scope::ScopeStack;
+pub use union::{is_union_ident, union_case, union_ident, union_members};
 
 /// Typechecks a segmented AST and lowers it into a [`HirModule`].
 ///
@@ -33,12 +55,47 @@ pub(crate) use scope::ScopeStack;
 /// the returned [`ErrorCollector`] so that callers can decide how to surface
 /// them.
 pub fn typecheck(asts: SegmentedAsts) -> Result<(HirModule, ErrorCollector), LookupError> {
+    typecheck_with(asts, &[])
+}
+
+/// Typechecks a segmented AST against the public APIs of Rust crates
+/// imported from their rustdoc JSON. The items of a crate are visible in the
+/// modules that `use` it. Imported items that were skipped are reported as
+/// warnings.
+pub fn typecheck_with(
+    asts: SegmentedAsts,
+    crates: &[RustdocCrate],
+) -> Result<(HirModule, ErrorCollector), LookupError> {
     let mapping = builtins();
     let predefined = predefined_from(&mapping, builtin_fns());
 
-    let (functions, tests, main, cmd_bodies, errors) = {
-        let lookup = LookupContext::new().with(&predefined)?.with(&asts)?;
-        let mut checker = Checker::new(&lookup, &mapping);
+    let (functions, tests, main, cmd_bodies, unions, operators, uses, modules, errors) = {
+        let prelude = LookupContext::new().with(&predefined)?;
+        let modules = ModuleScopes::with_crates(prelude, &asts, crates);
+        let mut checker = Checker::new(&modules, &mapping);
+        for error in &asts.syntax_errors {
+            checker.errors.syntax_error(error);
+        }
+        for duplicate in modules.duplicates() {
+            checker.errors.lookup_error(duplicate);
+        }
+        for skipped in crates.iter().flat_map(|imported| &imported.skipped) {
+            checker.errors.warning(
+                TranspilerWarning::SkippedImport {
+                    path: skipped.path.clone(),
+                    reason: skipped.reason.clone(),
+                },
+                None,
+            );
+        }
+        for import in modules.import_errors() {
+            checker.enter_module(&import.decl.source);
+            checker.report_path_error(&import.error, import.decl.item.span);
+        }
+        checker.register_declared_unions(&asts);
+        checker.check_aliases(&asts);
+        checker.check_traits(&asts);
+        checker.check_infixes(&asts);
 
         let functions = asts
             .functions
@@ -49,14 +106,18 @@ pub fn typecheck(asts: SegmentedAsts) -> Result<(HirModule, ErrorCollector), Loo
         let tests = asts
             .tests
             .iter()
-            .map(|test| HirTest {
-                name: test.item.name.clone(),
-                body: checker.lower_toplevel_body(&test.item.body),
-                source: test.source.clone(),
+            .map(|test| {
+                checker.enter_module(&test.source);
+                HirTest {
+                    name: test.item.name.clone(),
+                    body: checker.lower_toplevel_body(&test.item.body),
+                    source: test.source.clone(),
+                }
             })
             .collect::<Vec<_>>();
 
         let main = asts.main.as_ref().map(|main| {
+            checker.enter_module(&main.source);
             checker.scopes.push();
             let kind = match &main.item.kind {
                 MainKind::Command(signature) => {
@@ -102,6 +163,7 @@ pub fn typecheck(asts: SegmentedAsts) -> Result<(HirModule, ErrorCollector), Loo
             .cmds
             .iter()
             .map(|cmd| {
+                checker.enter_module(&cmd.source);
                 checker.scopes.push();
                 for param in &cmd.item.signature.parameters.params {
                     // CLI parameters are passed by value
@@ -118,12 +180,47 @@ pub fn typecheck(asts: SegmentedAsts) -> Result<(HirModule, ErrorCollector), Loo
             })
             .collect::<Vec<_>>();
 
-        (functions, tests, main, cmd_bodies, checker.errors)
+        let mut unions = checker.unions.into_values().collect::<Vec<_>>();
+        unions.sort_by(|a, b| a.ident.as_str().cmp(b.ident.as_str()));
+        checker.errors.leave_source();
+
+        // Paths into this crate are generated in their absolute form
+        let uses = asts
+            .uses
+            .iter()
+            .map(|decl| modules.canonical_path(decl.source.module(), &decl.item.path))
+            .collect::<Vec<_>>();
+
+        (
+            functions,
+            tests,
+            main,
+            cmd_bodies,
+            unions,
+            checker.operators,
+            uses,
+            modules.modules(),
+            checker.errors,
+        )
     };
 
     let SegmentedAsts {
-        uses, types, cmds, ..
+        uses: use_decls,
+        types,
+        traits,
+        cmds,
+        ..
     } = asts;
+    let uses = use_decls
+        .into_iter()
+        .zip(uses)
+        .map(|(mut decl, canonical)| {
+            if let Some(path) = canonical {
+                decl.item.path = path;
+            }
+            decl
+        })
+        .collect();
     let cmds = cmds
         .into_iter()
         .zip(cmd_bodies)
@@ -142,16 +239,24 @@ pub fn typecheck(asts: SegmentedAsts) -> Result<(HirModule, ErrorCollector), Loo
         HirModule {
             uses,
             types,
+            traits,
             functions,
             tests,
             main,
             cmds,
+            unions,
+            operators,
+            modules,
         },
         errors,
     ))
 }
 
 pub(crate) struct Checker<'a> {
+    pub(crate) modules: &'a ModuleScopes<'a>,
+    /// Module of the item currently being lowered
+    pub(crate) module: ModulePath,
+    /// Items visible in [`Self::module`]
     pub(crate) lookup: &'a LookupContext<'a>,
     pub(crate) mapping: &'a Mapping,
     pub(crate) scopes: ScopeStack,
@@ -159,25 +264,84 @@ pub(crate) struct Checker<'a> {
     /// Return type of the function currently being lowered
     pub(crate) fn_return: TypeElement,
     pub(crate) ref_self: bool,
+    /// Union types encountered so far, keyed by the name of their enum
+    pub(crate) unions: HashMap<TypeIdent, HirUnion>,
+    /// Trait bounds of the generic parameters of the function currently
+    /// being lowered
+    pub(crate) bounds: HashMap<Ident, Vec<TypeIdent>>,
+    /// Operators derived for struct types so far
+    pub(crate) operators: Vec<HirOperatorImpl>,
+    /// Loops enclosing the statement currently being lowered, innermost last
+    pub(crate) loops: Vec<LoopFrame>,
 }
 
 impl<'a> Checker<'a> {
-    fn new(lookup: &'a LookupContext<'a>, mapping: &'a Mapping) -> Self {
+    fn new(modules: &'a ModuleScopes<'a>, mapping: &'a Mapping) -> Self {
+        let module = ModulePath::root();
         Self {
-            lookup,
+            modules,
+            lookup: modules.scope(&module),
+            module,
             mapping,
             scopes: ScopeStack::new(),
             errors: ErrorCollector::new(),
             fn_return: TypeElement::void(),
             ref_self: false,
+            unions: HashMap::new(),
+            bounds: HashMap::new(),
+            operators: Vec::new(),
+            loops: Vec::new(),
+        }
+    }
+
+    /// Registers the unions used in type declarations and signatures up front,
+    /// so that their enums exist even if no expression mentions them
+    fn register_declared_unions(&mut self, asts: &SegmentedAsts) {
+        for decl in &asts.types {
+            match &decl.item {
+                TypeDecl::Tuple(def) => {
+                    for member in &def.members {
+                        self.register_unions(&member.r#type);
+                    }
+                }
+                TypeDecl::Struct(def) => {
+                    for member in &def.members {
+                        self.register_unions(&member.r#type);
+                    }
+                }
+                TypeDecl::Enum(def) => {
+                    for field in def.members.iter().flat_map(|member| &member.fields) {
+                        self.register_unions(&field.r#type);
+                    }
+                }
+                TypeDecl::Alias(def) => self.register_unions(&def.r#type),
+                TypeDecl::Empty(_) => {}
+            }
+        }
+
+        let params = asts
+            .functions
+            .iter()
+            .flat_map(|func| &func.item.signature.parameters.params)
+            .chain(
+                asts.cmds
+                    .iter()
+                    .flat_map(|cmd| &cmd.item.signature.parameters.params),
+            );
+        for param in params {
+            self.register_unions(&param.param_type);
+        }
+        for func in &asts.functions {
+            self.register_unions(&func.item.signature.return_type);
         }
     }
 
     pub(crate) fn is_copy(&self, ty: &TypeElement) -> bool {
-        self.mapping.is_copy(ty)
+        self.mapping.is_copy(&self.resolve_alias(ty))
     }
 
     fn lower_function(&mut self, func: &ToplevelItem<FnDecl>) -> HirFunction {
+        self.enter_module(&func.source);
         let signature = func.item.signature.clone();
 
         self.scopes.push();
@@ -206,19 +370,42 @@ impl<'a> Checker<'a> {
             .receiver()
             .is_some_and(|receiver| receiver.decl_modifier == Some(DeclModifier::Ref));
         self.fn_return = signature.return_type.clone();
+        for bound in signature
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.bounds)
+        {
+            for param in &bound.type_params {
+                self.bounds
+                    .entry(param.clone())
+                    .or_default()
+                    .extend(bound.bounds.iter().cloned());
+            }
+        }
+        let returns_borrow = self.returns_borrow(&func.item);
         let expected = if signature.return_type.is_void() || signature.return_type.is_infer() {
             Expected::void()
+        } else if returns_borrow {
+            Expected::with(signature.return_type.clone(), Ownership::Borrowed)
         } else {
             Expected::owned(signature.return_type.clone())
         };
-        let body = self.lower_block(&func.item.body, &expected);
+        let mut body = self.lower_block(&func.item.body, &expected);
+        if returns_borrow {
+            // The returned field path is a place inside `&self`
+            if let Some(HirStatement::Expression(returned)) = body.statements.last_mut() {
+                returned.adjustments.push(Adjustment::Borrow);
+            }
+        }
 
         self.scopes.pop();
         self.fn_return = TypeElement::void();
         self.ref_self = false;
+        self.bounds.clear();
 
         HirFunction {
             signature,
+            returns_borrow,
             body,
             source: func.source.clone(),
             span: func.item.span,
@@ -313,8 +500,8 @@ impl<'a> Checker<'a> {
                 expression: self.lower_expression(&throw.expression, &Expected::free()),
                 span: throw.span,
             }),
-            Statement::Break(brk) => HirStatement::Break(brk.span),
-            Statement::Continue(cont) => HirStatement::Continue(cont.span),
+            Statement::Break(brk) => HirStatement::Break(self.lower_break(brk)),
+            Statement::Continue(cont) => HirStatement::Continue(self.lower_continue(cont)),
         }
     }
 
@@ -322,6 +509,10 @@ impl<'a> Checker<'a> {
         let shares_ref = declaration.decl_modifier == DeclModifier::Ref
             && declaration.assignment_modifier == Some(DeclModifier::Ref);
 
+        if let Some(annotation) = &declaration.type_annotation {
+            self.register_unions(annotation);
+        }
+
         let (value, ty) = match (&declaration.type_annotation, &declaration.assignment) {
             (Some(annotation), Some(expression)) => {
                 let expected =
@@ -352,17 +543,28 @@ impl<'a> Checker<'a> {
             }
             (Some(annotation), None) => (None, annotation.clone()),
             (None, None) => {
-                self.errors.warning(
-                    format!(
-                        "Variable '{}' needs a type annotation or an initializer",
-                        declaration.identifier
-                    ),
-                    Some(declaration.span.into()),
-                );
+                let warning = match &declaration.pattern {
+                    DeclarationPattern::Ident(ident) => TranspilerWarning::MissingTypeAnnotation {
+                        name: ident.to_string(),
+                    },
+                    _ => TranspilerWarning::MissingInitializer,
+                };
+                self.errors.warning(warning, Some(declaration.span.into()));
                 (None, TypeElement::infer())
             }
         };
 
+        let DeclarationPattern::Ident(identifier) = &declaration.pattern else {
+            let pattern = self.lower_destructuring(declaration, &ty);
+            return HirDeclaration {
+                modifier: declaration.decl_modifier,
+                pattern,
+                ty,
+                value,
+                span: declaration.span,
+            };
+        };
+
         let ownership = match declaration.decl_modifier {
             DeclModifier::Let | DeclModifier::Mut => {
                 if self.is_copy(&ty) {
@@ -375,7 +577,7 @@ impl<'a> Checker<'a> {
         };
 
         self.scopes.declare(Variable {
-            ident: declaration.identifier.clone(),
+            ident: identifier.clone(),
             modifier: declaration.decl_modifier,
             ty: ty.clone(),
             ownership,
@@ -383,7 +585,7 @@ impl<'a> Checker<'a> {
 
         HirDeclaration {
             modifier: declaration.decl_modifier,
-            identifier: declaration.identifier.clone(),
+            pattern: HirDeclarationPattern::Ident(identifier.clone()),
             ty,
             value,
             span: declaration.span,
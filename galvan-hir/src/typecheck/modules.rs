@@ -0,0 +1,100 @@
+//! Paths into the modules of the crate.
+//!
+//! Items are looked up in the scope of the module that declares the code
+//! being lowered. Qualified calls such as `orders::place(order)` or
+//! `order.orders::ship()` are resolved among the items declared in the named
+//! module when the path names a module of this crate, and are left unchecked
+//! when it names another crate. Paths into this crate are stored in their
+//! absolute form, starting with `crate`.
+
+use galvan_ast::{FnDecl, Ident, Span, ToplevelItem, TypeElement, UsePath};
+use galvan_files::Source;
+use galvan_resolver::{is_visible, Lookup, PathError};
+
+use crate::error::TranspilerError;
+
+use super::expr::receiver_type_ident;
+use super::Checker;
+
+impl<'a> Checker<'a> {
+    /// Resolves names in the scope of the module `source` belongs to
+    pub(crate) fn enter_module(&mut self, source: &Source) {
+        let modules = self.modules;
+        self.module = source.module().clone();
+        self.lookup = modules.scope(&self.module);
+        self.errors.enter_source(source);
+    }
+
+    pub(crate) fn report_path_error(&mut self, error: &PathError, span: Span) {
+        let error = match error {
+            PathError::Unresolved { path } => {
+                TranspilerError::UnresolvedPath { path: path.clone() }
+            }
+            PathError::Private { item, module } => TranspilerError::PrivateItem {
+                item: item.clone(),
+                module: module.to_string(),
+            },
+        };
+        self.errors.error_with_span(error, Some(span.into()));
+    }
+
+    /// The absolute form of a namespace into this crate, or the namespace
+    /// itself if it names another crate
+    pub(crate) fn canonical_namespace(&self, namespace: &UsePath) -> UsePath {
+        self.modules
+            .canonical_path(&self.module, namespace)
+            .unwrap_or_else(|| namespace.clone())
+    }
+
+    /// Resolves the function called by `namespace::ident(...)`, or by
+    /// `receiver.namespace::ident(...)` if a receiver type is given.
+    ///
+    /// Returns `None` if the namespace does not name a module of this crate.
+    /// Otherwise returns the absolute namespace and the function, which is
+    /// `None` (and reported) if the module declares no such function.
+    pub(super) fn resolve_qualified_function(
+        &mut self,
+        namespace: &UsePath,
+        receiver: Option<&TypeElement>,
+        ident: &Ident,
+        labels: &[&str],
+        span: Span,
+    ) -> Option<(UsePath, Option<&'a ToplevelItem<FnDecl>>)> {
+        let modules = self.modules;
+        let module = modules.resolve_module(&self.module, namespace)?;
+        let canonical = self.canonical_namespace(namespace);
+        let declared = modules.declared(&module)?;
+
+        let receiver_ident = receiver.and_then(receiver_type_ident);
+        let function = receiver_ident
+            .and_then(|receiver| declared.resolve_function(Some(&receiver), ident, labels))
+            // Extension functions on collection or generic receivers are
+            // registered without a receiver type
+            .or_else(|| declared.resolve_function(None, ident, labels));
+        match function {
+            Some(function) => {
+                let signature = &function.item.signature;
+                if !is_visible(&signature.visibility, &module, &self.module) {
+                    self.report_path_error(
+                        &PathError::Private {
+                            item: ident.to_string(),
+                            module,
+                        },
+                        span,
+                    );
+                }
+            }
+            None => {
+                let path = namespace
+                    .segments
+                    .iter()
+                    .chain(std::iter::once(ident))
+                    .map(Ident::as_str)
+                    .collect::<Vec<_>>()
+                    .join("::");
+                self.report_path_error(&PathError::Unresolved { path }, span);
+            }
+        }
+        Some((canonical, function))
+    }
+}
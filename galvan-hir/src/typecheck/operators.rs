@@ -0,0 +1,143 @@
+//! Structural operators for struct types.
+//!
+//! `+ - * /` can be used on two values of the same struct type when every
+//! field supports the operator, either because it is a number or because it
+//! is a struct that derives the operator itself. The operation is applied
+//! member-wise by a generated `std::ops` impl.
+
+use galvan_ast::{
+    ArithmeticOperator, DeclModifier, InfixOperator, Ownership, Span, TypeDecl, TypeElement,
+    TypeIdent,
+};
+use galvan_resolver::Lookup;
+
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::expr::is_numeric_type;
+use super::{types_compatible, Checker};
+
+impl Checker<'_> {
+    /// The struct type of an operand, if operators on it are derived
+    /// structurally
+    pub(crate) fn struct_operand(&self, ty: &TypeElement) -> Option<TypeIdent> {
+        let TypeElement::Plain(plain) = ty else {
+            return None;
+        };
+
+        match self.lookup.resolve_type(&plain.ident) {
+            Some(decl) if matches!(decl.item, TypeDecl::Struct(_)) => Some(plain.ident.clone()),
+            _ => None,
+        }
+    }
+
+    /// Lowers an arithmetic operation on two values of the struct type `ty`
+    pub(crate) fn lower_struct_arithmetic(
+        &mut self,
+        ty: TypeIdent,
+        lhs: HirExpression,
+        operator: ArithmeticOperator,
+        rhs: HirExpression,
+        span: Span,
+    ) -> HirExpression {
+        if !types_compatible(&lhs.ty, &rhs.ty) {
+            self.errors.error_with_span(
+                TranspilerError::TypeMismatch {
+                    expected: lhs.ty.to_string(),
+                    found: rhs.ty.to_string(),
+                },
+                Some(span.into()),
+            );
+        } else if !is_derivable(&operator) {
+            self.errors.error_with_span(
+                TranspilerError::InvalidOperationOnType {
+                    operation: format!("`{}` on {ty}", operator.symbol()),
+                    allowed_types: "numbers, since structs only derive `+`, `-`, `*` and `/`"
+                        .to_string(),
+                },
+                Some(span.into()),
+            );
+        } else if !self.derive_operator(&ty, &operator, &mut Vec::new()) {
+            self.errors.error_with_span(
+                TranspilerError::InvalidOperationOnType {
+                    operation: format!("`{}` on {ty}", operator.symbol()),
+                    allowed_types: format!(
+                        "numbers and structs whose fields all support `{}`",
+                        operator.symbol()
+                    ),
+                },
+                Some(span.into()),
+            );
+        }
+
+        // The generated impls take both operands by value
+        let lhs = self.ensure_owned(lhs);
+        let rhs = self.ensure_owned(rhs);
+        let result_ty = lhs.ty.clone();
+
+        HirExpression::new(
+            HirExpressionKind::Arithmetic(Box::new(HirBinary { lhs, operator, rhs })),
+            result_ty,
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+
+    /// Registers the operator impl for the struct and all structs among its
+    /// fields. Returns false if a field does not support the operator.
+    fn derive_operator(
+        &mut self,
+        ty: &TypeIdent,
+        operator: &ArithmeticOperator,
+        visiting: &mut Vec<TypeIdent>,
+    ) -> bool {
+        let lookup = self.lookup;
+        let Some(decl) = lookup.resolve_type(ty) else {
+            return false;
+        };
+        let derived = HirOperatorImpl {
+            ty: ty.clone(),
+            operator: operator.clone(),
+            module: decl.source.module().clone(),
+        };
+        if self.operators.contains(&derived) {
+            return true;
+        }
+        if !is_derivable(operator) || visiting.contains(ty) {
+            return false;
+        }
+
+        let TypeDecl::Struct(def) = &decl.item else {
+            return false;
+        };
+        visiting.push(ty.clone());
+        let supported = def.members.iter().all(|member| {
+            // `ref` fields are shared and cannot be combined member-wise
+            if member.decl_modifier == Some(DeclModifier::Ref) {
+                return false;
+            }
+            // Generic fields are never plain, so generic structs are rejected
+            let TypeElement::Plain(field) = &member.r#type else {
+                return false;
+            };
+            is_numeric_type(&field.ident) || self.derive_operator(&field.ident, operator, visiting)
+        });
+        visiting.pop();
+
+        if supported {
+            self.operators.push(derived);
+        }
+        supported
+    }
+}
+
+/// Whether the operator is derived member-wise for struct types
+fn is_derivable(operator: &ArithmeticOperator) -> bool {
+    matches!(
+        operator,
+        ArithmeticOperator::Add
+            | ArithmeticOperator::Sub
+            | ArithmeticOperator::Mul
+            | ArithmeticOperator::Div
+    )
+}
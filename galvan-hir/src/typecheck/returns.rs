@@ -0,0 +1,159 @@
+//! Member functions that return a borrow of `self`.
+//!
+//! Accessors such as `fn name(self: Dog) -> String { self.name }` would clone
+//! the field on every call if returns were always owned. A member function
+//! returns a borrow instead when
+//!
+//! - its `self` receiver is borrowed (unmodified and not `Copy`),
+//! - its body is a single field path rooted at `self`, e.g. `self.owner.name`,
+//! - the field has exactly the declared return type, which is neither `Copy`
+//!   nor generic, and
+//! - it does not implement a trait requirement, whose signature returns an
+//!   owned value.
+//!
+//! The generated function returns `&T` with the lifetime elided to the one
+//! of `&self`, and calls produce `Borrowed` values that are cloned only where
+//! an owned value is needed.
+
+use std::collections::HashSet;
+
+use galvan_ast::{
+    DeclModifier, Expression, ExpressionKind, FnDecl, Ident, InfixExpression, MemberOperator,
+    Ownership, Statement, TypeDecl, TypeElement,
+};
+use galvan_resolver::Lookup;
+
+use crate::builtins::IsSame;
+
+use super::generics::Substitution;
+use super::Checker;
+
+/// The fields accessed by a path like `self.owner.name`, outermost first
+fn self_field_path(expression: &Expression) -> Option<Vec<&Ident>> {
+    let ExpressionKind::Infix(infix) = &expression.kind else {
+        return None;
+    };
+    let InfixExpression::Member(access) = infix.as_ref() else {
+        return None;
+    };
+    if access.operator != MemberOperator::Dot {
+        return None;
+    }
+    let field = access.field_ident()?;
+
+    let mut path = match &access.lhs.kind {
+        ExpressionKind::Ident(ident) if ident.is_self() => Vec::new(),
+        _ => self_field_path(&access.lhs)?,
+    };
+    path.push(field);
+    Some(path)
+}
+
+impl Checker<'_> {
+    /// Whether calls to the function produce a borrow of their receiver
+    pub(crate) fn returns_borrow(&self, decl: &FnDecl) -> bool {
+        let signature = &decl.signature;
+        let Some(receiver) = signature.receiver() else {
+            return false;
+        };
+        if !matches!(receiver.decl_modifier, None | Some(DeclModifier::Let))
+            || self.is_copy(&receiver.param_type)
+        {
+            return false;
+        }
+
+        let return_ty = &signature.return_type;
+        let mut generics = HashSet::new();
+        return_ty.collect_generics_recursive(&mut generics);
+        if matches!(
+            return_ty,
+            TypeElement::Infer(_) | TypeElement::Void(_) | TypeElement::Never(_)
+        ) || !generics.is_empty()
+            || self.is_copy(return_ty)
+        {
+            return false;
+        }
+
+        if self.implements_trait_member(&receiver.param_type, decl) {
+            return false;
+        }
+
+        let [Statement::Expression(body)] = decl.body.statements.as_slice() else {
+            return false;
+        };
+        let Some(path) = self_field_path(body) else {
+            return false;
+        };
+        let field_ty = path
+            .into_iter()
+            .try_fold(receiver.param_type.clone(), |ty, field| {
+                self.declared_field_type(&ty, field)
+            });
+        field_ty.is_some_and(|field_ty| field_ty.is_same(return_ty))
+    }
+
+    /// Ownership of the values produced by calls to the function
+    pub(crate) fn call_result_ownership(&self, decl: &FnDecl) -> Ownership {
+        if self.returns_borrow(decl) {
+            Ownership::Borrowed
+        } else {
+            Ownership::UniqueOwned
+        }
+    }
+
+    /// Whether the member function implements a requirement of a trait its
+    /// receiver type conforms to
+    fn implements_trait_member(&self, receiver: &TypeElement, decl: &FnDecl) -> bool {
+        let type_ident = match receiver {
+            TypeElement::Plain(plain) => &plain.ident,
+            TypeElement::Parametric(parametric) => &parametric.base_type,
+            _ => return false,
+        };
+        let Some(type_decl) = self.lookup.resolve_type(type_ident) else {
+            return false;
+        };
+
+        let labels = decl.signature.overload_labels();
+        let labels = labels
+            .iter()
+            .map(|label| label.as_str())
+            .collect::<Vec<_>>();
+        type_decl
+            .item
+            .conformances()
+            .iter()
+            .filter_map(|conformance| self.lookup.resolve_trait(conformance))
+            .any(|trait_decl| {
+                trait_decl
+                    .item
+                    .member(decl.signature.identifier.as_str(), &labels)
+                    .is_some()
+            })
+    }
+
+    /// The declared type of a struct field, without reporting errors
+    fn declared_field_type(&self, ty: &TypeElement, field: &Ident) -> Option<TypeElement> {
+        let ty = &self.resolve_alias(ty);
+        let (type_ident, type_args) = match ty {
+            TypeElement::Plain(plain) => (&plain.ident, None),
+            TypeElement::Parametric(parametric) => {
+                (&parametric.base_type, Some(&parametric.type_args))
+            }
+            _ => return None,
+        };
+        let decl = self.lookup.resolve_type(type_ident)?;
+        let TypeDecl::Struct(struct_decl) = &decl.item else {
+            return None;
+        };
+
+        let mut substitution = Substitution::default();
+        if let Some(type_args) = type_args {
+            substitution.bind_all(&decl.item.generic_params(), type_args);
+        }
+        struct_decl
+            .members
+            .iter()
+            .find(|member| member.ident == *field)
+            .map(|member| substitution.apply(&member.r#type))
+    }
+}
@@ -0,0 +1,141 @@
+//! Calls to Rust standard library methods on builtin receivers.
+//!
+//! Methods like `name.to_uppercase()` or `list.push(item)` have no Galvan
+//! declaration. Their signatures come from the table in
+//! [`std_method`](crate::builtins::std_method), so their arguments and
+//! results are checked like those of user functions. Receivers are passed
+//! to mutating methods without an explicit `mut`, as Rust borrows them
+//! automatically, but they must not belong to an immutable variable.
+
+use galvan_ast::{DeclModifier, FunctionCallArg, Ident, Ownership, Span};
+
+use crate::builtins::{StdMethod, StdPassing};
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::{Checker, Expected};
+
+fn passing_ownership(passing: StdPassing) -> Ownership {
+    match passing {
+        StdPassing::Owned => Ownership::UniqueOwned,
+        StdPassing::Borrowed => Ownership::Borrowed,
+        StdPassing::MutBorrowed => Ownership::MutBorrowed,
+    }
+}
+
+impl Checker<'_> {
+    pub(super) fn lower_std_call(
+        &mut self,
+        receiver: (HirExpression, Option<DeclModifier>),
+        method: StdMethod,
+        ident: &Ident,
+        arguments: &[FunctionCallArg],
+        span: Span,
+    ) -> HirExpression {
+        self.validate_no_argument_labels(arguments, "standard library methods");
+        if method.params.len() != arguments.len() {
+            self.errors.error_with_span(
+                TranspilerError::ArgumentCountMismatch {
+                    name: ident.to_string(),
+                    expected: method.params.len(),
+                    found: arguments.len(),
+                },
+                Some(span.into()),
+            );
+        }
+
+        let (receiver, modifier) = receiver;
+        let receiver = match (modifier, method.receiver) {
+            (None, StdPassing::Owned) => self.ensure_owned(receiver),
+            (None, StdPassing::Borrowed) => receiver,
+            (None, StdPassing::MutBorrowed) => {
+                self.validate_mutable_receiver(&receiver);
+                receiver
+            }
+            (modifier, _) => self.lower_unknown_receiver(receiver, modifier, span),
+        };
+
+        let args = method
+            .params
+            .iter()
+            .zip(arguments)
+            .map(|((param_ty, passing), argument)| {
+                let (lowered, expression_modifier) = self.lower_call_value(&argument.expression);
+                let modifier = self.merge_argument_modifiers(
+                    argument.modifier,
+                    expression_modifier,
+                    argument.expression.span,
+                );
+                match modifier {
+                    None => {
+                        let expected =
+                            Expected::with(param_ty.clone(), passing_ownership(*passing));
+                        self.coerce(lowered, &expected)
+                    }
+                    modifier => {
+                        self.lower_unknown_receiver(lowered, modifier, argument.expression.span)
+                    }
+                }
+            })
+            .collect();
+
+        let call = HirExpression::new(
+            HirExpressionKind::MethodCall(Box::new(HirMethodCall {
+                receiver,
+                receiver_modifier: modifier,
+                namespace: None,
+                ident: ident.clone(),
+                labels: Vec::new(),
+                args,
+            })),
+            method.return_type.clone(),
+            method.returns,
+            span,
+        );
+        if !method.cloned {
+            return call;
+        }
+
+        HirExpression::new(
+            HirExpressionKind::MethodCall(Box::new(HirMethodCall {
+                receiver: call,
+                receiver_modifier: None,
+                namespace: None,
+                ident: Ident::new("cloned"),
+                labels: Vec::new(),
+                args: Vec::new(),
+            })),
+            method.return_type,
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+
+    /// Reports mutating calls on places rooted in an immutable variable,
+    /// e.g. `guests.push(name)` for a `let guests` or a parameter without `mut`
+    fn validate_mutable_receiver(&mut self, receiver: &HirExpression) {
+        let mut place = receiver;
+        let ident = loop {
+            match &place.kind {
+                HirExpressionKind::Variable(ident) => break ident,
+                HirExpressionKind::FieldAccess(access) => place = &access.receiver,
+                HirExpressionKind::Index(index) => place = &index.base,
+                HirExpressionKind::Group(inner) => place = inner,
+                _ => return,
+            }
+        };
+
+        let immutable = self
+            .scopes
+            .get(ident)
+            .is_some_and(|variable| variable.modifier == DeclModifier::Let);
+        if immutable {
+            self.errors.error_with_span(
+                TranspilerError::ImmutableAssignment {
+                    name: ident.to_string(),
+                },
+                Some(receiver.span.into()),
+            );
+        }
+    }
+}
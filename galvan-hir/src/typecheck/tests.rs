@@ -1,11 +1,12 @@
-use galvan_ast::{Ownership, TypeElement};
-use galvan_files::Source;
+use galvan_ast::{ArithmeticOperator, Ownership, TypeElement};
+use galvan_files::{ModulePath, Source};
 use galvan_into_ast::{SegmentAst, SourceIntoAst};
+use galvan_resolver::rustdoc::import_rustdoc;
 
 use crate::builtins::CheckBuiltins;
 use crate::error::ErrorCollector;
 use crate::hir::*;
-use crate::typecheck::typecheck;
+use crate::typecheck::{typecheck, typecheck_with};
 
 fn lower_with_diagnostics(code: &str) -> (HirModule, ErrorCollector) {
     let ast = Source::from_string(code)
@@ -15,6 +16,20 @@ fn lower_with_diagnostics(code: &str) -> (HirModule, ErrorCollector) {
     typecheck(segmented).expect("test code should typecheck")
 }
 
+/// Lowers the given sources of a crate with several modules
+fn lower_crate(sources: Vec<Source>) -> (HirModule, ErrorCollector) {
+    let asts = sources
+        .into_iter()
+        .map(|source| source.try_into_ast().expect("test code should parse"))
+        .collect::<Vec<_>>();
+    let segmented = asts.segmented().expect("test code should segment");
+    typecheck(segmented).expect("test code should typecheck")
+}
+
+fn in_module(name: &str, code: &str) -> Source {
+    Source::in_module(ModulePath::root().child(name), code)
+}
+
 fn lower(code: &str) -> HirModule {
     let (module, errors) = lower_with_diagnostics(code);
     assert!(
@@ -106,6 +121,28 @@ fn owned_locals_are_cloned_when_stored() {
     assert_eq!(tail.adjustments, vec![Adjustment::ToOwned]);
 }
 
+#[test]
+fn field_accessors_return_borrows() {
+    let module = lower(
+        "type Dog { name: String, age: Int }
+         fn name(self: Dog) -> String { self.name }
+         fn age(self: Dog) -> Int { self.age }
+         fn shouted(self: Dog) -> String { self.name.to_uppercase() }
+         fn name_of(dog: Dog) -> String { dog.name() }",
+    );
+
+    let name = function(&module, "name");
+    assert!(name.returns_borrow);
+    assert_eq!(trailing(name).adjustments, vec![Adjustment::Borrow]);
+    assert!(!function(&module, "age").returns_borrow);
+    assert!(!function(&module, "shouted").returns_borrow);
+
+    // The borrowed call result is cloned where an owned value is needed
+    let tail = trailing(function(&module, "name_of"));
+    assert_eq!(tail.ownership, Ownership::Borrowed);
+    assert_eq!(tail.adjustments, vec![Adjustment::ToOwned]);
+}
+
 #[test]
 fn copy_fields_need_no_clone() {
     let module = lower(
@@ -809,6 +846,130 @@ fn concat_expression_owns_appended_elements() {
     assert_eq!(operation.rhs.adjustments, vec![Adjustment::ToOwned]);
 }
 
+#[test]
+fn remove_expression_classifies_removed_values() {
+    let module = lower(
+        "fn without(names: [String], name: String) -> [String] { names -- name }
+         fn without_all(names: {String}, removed: [String]) -> {String} { names -- removed }",
+    );
+
+    let tail = trailing(function(&module, "without"));
+    let HirExpressionKind::CollectionOp(operation) = &tail.kind else {
+        panic!("expected collection operation");
+    };
+    assert_eq!(
+        operation.operator,
+        CollectionOperator::Remove(RemoveKind::Element)
+    );
+    assert_eq!(operation.rhs.adjustments, vec![Adjustment::ToOwned]);
+
+    let tail = trailing(function(&module, "without_all"));
+    let HirExpressionKind::CollectionOp(operation) = &tail.kind else {
+        panic!("expected collection operation");
+    };
+    assert_eq!(
+        operation.operator,
+        CollectionOperator::Remove(RemoveKind::Collection)
+    );
+}
+
+#[test]
+fn remove_expression_rejects_non_collections() {
+    let (_module, errors) = lower_with_diagnostics("fn count(a: Int, b: Int) -> Int { a -- b }");
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: `--` on Int can only be used on arrays, sets and dictionaries"
+    }));
+}
+
+#[test]
+fn repeat_expression_keeps_string_and_array_types() {
+    let module = lower(
+        "fn line() -> String { \"-\" ** 10 }
+         fn rows(row: [Int]) -> [Int] { row ** 3 }",
+    );
+
+    for (name, ty) in [("line", "String"), ("rows", "[Int]")] {
+        let tail = trailing(function(&module, name));
+        let HirExpressionKind::CollectionOp(operation) = &tail.kind else {
+            panic!("expected collection operation, got {:?}", tail.kind);
+        };
+        assert_eq!(operation.operator, CollectionOperator::Repeat);
+        assert_eq!(tail.ty.to_string(), ty);
+    }
+}
+
+#[test]
+fn repeat_expression_rejects_other_types() {
+    let (_module, errors) = lower_with_diagnostics("fn twice(flag: Bool) -> Bool { flag ** 2 }");
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: `**` on Bool can only be used on strings and arrays"
+    }));
+}
+
+#[test]
+fn slice_expression_keeps_string_and_array_types() {
+    let module = lower(
+        "fn prefix(text: String) -> String { text[:5] }
+         fn middle(values: [Int]) -> [Int] { values[1:3] }",
+    );
+
+    let tail = trailing(function(&module, "prefix"));
+    let HirExpressionKind::Slice(slice) = &tail.kind else {
+        panic!("expected slice, got {:?}", tail.kind);
+    };
+    assert!(slice.start.is_none() && slice.end.is_some());
+    assert_eq!(tail.ty.to_string(), "String");
+
+    let tail = trailing(function(&module, "middle"));
+    let HirExpressionKind::Slice(slice) = &tail.kind else {
+        panic!("expected slice, got {:?}", tail.kind);
+    };
+    assert!(slice.start.is_some() && slice.end.is_some());
+    assert_eq!(tail.ty.to_string(), "[Int]");
+}
+
+#[test]
+fn slice_expression_rejects_other_types() {
+    let (_module, errors) = lower_with_diagnostics("fn part(count: Int) -> Int { count[1:2] }");
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: slicing Int can only be used on strings and arrays"
+    }));
+}
+
+#[test]
+fn unary_not_is_bool() {
+    let module = lower("fn pending(done: Bool) -> Bool { not done }");
+    let tail = trailing(function(&module, "pending"));
+
+    let HirExpressionKind::Unary(unary) = &tail.kind else {
+        panic!("expected unary expression");
+    };
+    assert_eq!(unary.operator, galvan_ast::UnaryOperator::Not);
+    assert_eq!(tail.ty, TypeElement::bool());
+}
+
+#[test]
+fn unary_operators_check_operand_types() {
+    let (_module, errors) = lower_with_diagnostics(
+        "fn negated(count: UInt) -> UInt { -count }
+         fn inverted(name: String) -> Bool { !name }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: `-` on UInt can only be used on signed integer and floating point types"
+    }));
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message == "Invalid operation: `not` on String can only be used on Bool"
+    }));
+}
+
 #[test]
 fn tuples_of_copy_types_are_copy() {
     use galvan_ast::{Span, TupleTypeItem};
@@ -856,3 +1017,1179 @@ fn ownership_matches_generated_rust_for_locals() {
     assert_eq!(value.ownership, Ownership::SharedOwned);
     assert_eq!(value.adjustments, vec![Adjustment::ToOwned]);
 }
+
+#[test]
+fn union_members_coerce_into_union() {
+    let module = lower(
+        "fn print_value(value: Int | String) {}
+         fn check() { print_value(42) }",
+    );
+    let check = function(&module, "check");
+
+    let HirStatement::Expression(call) = &check.body.statements[0] else {
+        panic!("expected call statement");
+    };
+    let HirExpressionKind::FunctionCall(call) = &call.kind else {
+        panic!("expected function call");
+    };
+    // The member is converted into the union enum, which is borrowed like
+    // every other non-copy argument
+    assert_eq!(
+        call.args[0].adjustments,
+        vec![Adjustment::IntoUnion, Adjustment::Borrow]
+    );
+
+    // `Int | String` and `String | Int` share one generated enum
+    assert_eq!(module.unions.len(), 1);
+    let cases = module.unions[0]
+        .variants
+        .iter()
+        .map(|variant| variant.case.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(cases, vec!["Int", "String"]);
+}
+
+#[test]
+fn match_narrows_union_to_member() {
+    let (module, errors) = lower_with_diagnostics(
+        "fn describe(id: Int | String) -> String {
+             match id {
+                 Int(number) { \"number\" }
+                 String(name) { name }
+                 Bool(flag) { \"flag\" }
+             }
+         }",
+    );
+    let tail = trailing(function(&module, "describe"));
+
+    let HirExpressionKind::Match(matched) = &tail.kind else {
+        panic!("expected match expression");
+    };
+    let string_arm = matched.arms[1]
+        .body
+        .trailing_expression()
+        .expect("arm should have a trailing expression");
+    let TypeElement::Plain(narrowed) = &string_arm.ty else {
+        panic!("expected narrowed member type, got {:?}", string_arm.ty);
+    };
+    assert_eq!(narrowed.ident.as_str(), "String");
+
+    assert!(errors
+        .errors()
+        .any(|diagnostic| diagnostic.message.contains("does not have member `Bool`")));
+}
+
+#[test]
+fn generic_return_types_are_instantiated_from_arguments() {
+    let module = lower(
+        "fn first(items: [t]) -> t { items[0] }
+         fn check(names: [String]) -> String { first(names) }",
+    );
+    let tail = trailing(function(&module, "check"));
+
+    let TypeElement::Plain(basic) = &tail.ty else {
+        panic!("expected instantiated return type, got {:?}", tail.ty);
+    };
+    assert_eq!(basic.ident.as_str(), "String");
+}
+
+#[test]
+fn generic_receivers_bind_type_arguments() {
+    let module = lower(
+        "type Container { value: t }
+         fn get_value(self: Container<t>) -> t { self.value }
+         fn check() {
+             let shipment = Container(value: \"box\")
+             let value = shipment.get_value()
+         }",
+    );
+    let check = function(&module, "check");
+
+    let HirStatement::Declaration(shipment) = &check.body.statements[0] else {
+        panic!("expected declaration");
+    };
+    assert!(matches!(shipment.ty, TypeElement::Parametric(_)));
+
+    let HirStatement::Declaration(value) = &check.body.statements[1] else {
+        panic!("expected declaration");
+    };
+    let TypeElement::Plain(basic) = &value.ty else {
+        panic!("expected instantiated return type, got {:?}", value.ty);
+    };
+    assert_eq!(basic.ident.as_str(), "String");
+}
+
+#[test]
+fn conflicting_generic_bindings_are_type_mismatches() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn pair(a: t, b: t) -> t { a }
+         fn check() { pair(true, \"text\") }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message == "Type mismatch: expected Bool for generic parameter `t`, found String"
+    }));
+}
+
+#[test]
+fn trait_bounds_resolve_required_member_functions() {
+    let module = lower(
+        "trait Shape {
+             fn area(self: Self) -> Float
+         }
+         type Square: Shape { side: Float }
+         fn area(self: Square) -> Float { self.side * self.side }
+         fn measure(shape: t) -> Float where t: Shape { shape.area() }",
+    );
+    let tail = trailing(function(&module, "measure"));
+
+    let HirExpressionKind::MethodCall(call) = &tail.kind else {
+        panic!("expected method call, got {:?}", tail.kind);
+    };
+    assert_eq!(call.ident.as_str(), "area");
+    let TypeElement::Plain(basic) = &tail.ty else {
+        panic!("expected plain type, got {:?}", tail.ty);
+    };
+    assert_eq!(basic.ident.as_str(), "Float");
+}
+
+#[test]
+fn types_must_provide_required_member_functions() {
+    let (_, errors) = lower_with_diagnostics(
+        "trait Shape {
+             fn area(self: Self) -> Float
+         }
+         type Square: Shape { side: Float }
+         type Circle: Shape { radius: Float }
+         fn area(self: Circle) -> Int { 3 }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Type Square does not conform to trait Shape: missing member function `area`"
+    }));
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Type Circle does not conform to trait Shape: member function `area` must return Float, found Int"
+    }));
+}
+
+#[test]
+fn struct_arithmetic_derives_member_wise_operators() {
+    let module = lower(
+        "type Vec2 { x: Float, y: Float }
+         type Segment { start: Vec2, end: Vec2 }
+         fn add(a: Vec2, b: Vec2) -> Vec2 { a + b }
+         fn shift(a: Segment, b: Segment) -> Segment { a - b }",
+    );
+    let tail = trailing(function(&module, "add"));
+
+    let HirExpressionKind::Arithmetic(operation) = &tail.kind else {
+        panic!("expected arithmetic, got {:?}", tail.kind);
+    };
+    // Borrowed struct parameters are cloned for the by-value operator impl
+    assert!(operation.lhs.adjustments.contains(&Adjustment::ToOwned));
+    assert!(operation.rhs.adjustments.contains(&Adjustment::ToOwned));
+
+    let derived = module
+        .operators
+        .iter()
+        .map(|operator| (operator.ty.as_str(), operator.operator.clone()))
+        .collect::<Vec<_>>();
+    assert!(derived.contains(&("Vec2", ArithmeticOperator::Add)));
+    assert!(derived.contains(&("Vec2", ArithmeticOperator::Sub)));
+    assert!(derived.contains(&("Segment", ArithmeticOperator::Sub)));
+}
+
+#[test]
+fn struct_arithmetic_requires_supporting_fields() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Named { name: String }
+         fn combine(a: Named, b: Named) -> Named { a + b }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: `+` on Named can only be used on numbers and structs whose fields all support `+`"
+    }));
+}
+
+#[test]
+fn struct_arithmetic_only_derives_basic_operators() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Vec2 { x: Float, y: Float }
+         fn wrap(a: Vec2, b: Vec2) -> Vec2 { a % b }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: `%` on Vec2 can only be used on numbers, since structs only derive `+`, `-`, `*` and `/`"
+    }));
+}
+
+#[test]
+fn custom_infix_operators_lower_to_calls_by_precedence() {
+    let module = lower(
+        "fn sub(a: Int, b: Int) -> Int { a - b }
+         fn mul(a: Int, b: Int) -> Int { a * b }
+         infix <-> = sub, precedence: 1, associativity: right
+         infix <*> = mul, precedence: 2
+         fn calc(a: Int, b: Int, c: Int) -> Int { a <*> b <-> c <-> a }",
+    );
+    let tail = trailing(function(&module, "calc"));
+
+    // (a <*> b) <-> (c <-> a)
+    let HirExpressionKind::FunctionCall(outer) = &tail.kind else {
+        panic!("expected function call, got {:?}", tail.kind);
+    };
+    assert_eq!(outer.ident.as_str(), "sub");
+    let [lhs, rhs] = outer.args.as_slice() else {
+        panic!("expected two arguments, got {:?}", outer.args);
+    };
+    assert!(
+        matches!(&lhs.kind, HirExpressionKind::FunctionCall(call) if call.ident.as_str() == "mul")
+    );
+    assert!(
+        matches!(&rhs.kind, HirExpressionKind::FunctionCall(call) if call.ident.as_str() == "sub")
+    );
+    assert_eq!(tail.ty.to_string(), "Int");
+}
+
+#[test]
+fn custom_infix_operators_require_binary_functions() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn clamp(value: Int, min: Int, max: Int) -> Int { value }
+         infix <~> = clamp",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Function clamp (bound to infix operator <~>) expects 2 arguments, found 3"
+    }));
+}
+
+#[test]
+fn custom_infix_operators_pass_argument_labels() {
+    let module = lower(
+        "fn shift(value: Int, by amount: Int) -> Int { value + amount }
+         infix <+> = shift
+         fn calc(a: Int, b: Int) -> Int { a <+> b }",
+    );
+    let tail = trailing(function(&module, "calc"));
+
+    let HirExpressionKind::FunctionCall(call) = &tail.kind else {
+        panic!("expected function call, got {:?}", tail.kind);
+    };
+    assert_eq!(call.ident.as_str(), "shift");
+    assert_eq!(tail.ty.to_string(), "Int");
+}
+
+#[test]
+fn match_must_cover_every_enum_variant() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Shape { Circle(Float), Square(Float), Point }
+         fn area(shape: Shape) -> Float {
+             match shape {
+                 Circle(radius) { radius * radius }
+             }
+         }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message == "Non-exhaustive match on Shape: missing `Square`, `Point`"
+    }));
+}
+
+#[test]
+fn match_on_other_types_needs_a_wildcard_arm() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn describe(count: Int, flag: Bool) -> String {
+             let size = match count {
+                 0 { \"none\" }
+                 1..=9 { \"few\" }
+             }
+             match flag {
+                 true { size }
+                 false { \"off\" }
+             }
+         }",
+    );
+
+    let messages = errors
+        .errors()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["Non-exhaustive match on Int: missing a `_` arm"]
+    );
+}
+
+#[test]
+fn match_must_cover_variants_matched_with_nested_patterns() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Shape { Circle(Float), Square(Float) }
+         type Slot { Empty, Filled(Shape) }
+         fn describe(slot: Slot) -> String {
+             match slot {
+                 Filled(Circle(_)) { \"circle\" }
+                 Empty { \"empty\" }
+             }
+         }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message == "Non-exhaustive match on Slot: missing `Filled`"
+    }));
+}
+
+#[test]
+fn match_warns_about_unreachable_arms() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Shape { Circle(Float), Square(Float) }
+         fn area(shape: Shape) -> Float {
+             match shape {
+                 Circle(radius) { radius * radius }
+                 Circle(_) { 0.0 }
+                 _ { 1.0 }
+                 Square(side) { side * side }
+             }
+         }",
+    );
+
+    assert!(
+        !errors.has_errors(),
+        "expected no type errors, got: {errors}"
+    );
+    let warnings = errors
+        .warnings()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>();
+    assert!(warnings.contains(&"Unreachable match arm: variant `Circle` is already covered"));
+    assert!(warnings.contains(&"Unreachable match arm: all cases are already covered"));
+}
+
+#[test]
+fn rich_match_patterns_bind_nested_values() {
+    let (module, errors) = lower_with_diagnostics(
+        "type Shape { Circle(Float), Square(Float), Point }
+         type Slot { Empty, Filled(Shape) }
+         fn describe(slot: Slot, size: Int) -> String {
+             match slot {
+                 Filled(Circle(radius)) if radius > 1.0 { \"large circle\" }
+                 Filled(Square(_) | Circle(_)) { \"shape\" }
+                 Filled(Point) { \"point\" }
+                 Empty { match size {
+                     0 { \"nothing\" }
+                     1..=9 { \"few\" }
+                     _ { \"many\" }
+                 } }
+             }
+         }",
+    );
+    assert!(
+        !errors.has_errors(),
+        "expected no type errors, got: {errors}"
+    );
+    let tail = trailing(function(&module, "describe"));
+
+    let HirExpressionKind::Match(matched) = &tail.kind else {
+        panic!("expected match expression, got {:?}", tail.kind);
+    };
+    assert!(matched.arms[0].guard.is_some());
+    let HirMatchPattern::EnumVariant(filled) = &matched.arms[0].pattern else {
+        panic!("expected variant pattern");
+    };
+    let HirMatchPatternArguments::Tuple(arguments) = &filled.arguments else {
+        panic!("expected positional arguments");
+    };
+    assert!(matches!(
+        &arguments[0],
+        HirMatchBindingPattern::Pattern(nested) if matches!(nested.as_ref(), HirMatchPattern::EnumVariant(_))
+    ));
+}
+
+#[test]
+fn string_patterns_match_on_str() {
+    let module = lower(
+        "fn greeting(name: String) -> String {
+             match name {
+                 \"Ferris\" | \"Galvan\" { \"hello friend\" }
+                 _ { \"hello\" }
+             }
+         }",
+    );
+    let tail = trailing(function(&module, "greeting"));
+
+    let HirExpressionKind::Match(matched) = &tail.kind else {
+        panic!("expected match expression, got {:?}", tail.kind);
+    };
+    assert_eq!(matched.scrutinee.adjustments, vec![Adjustment::AsStr]);
+}
+
+#[test]
+fn string_patterns_are_rejected_inside_nested_patterns() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn greeting(pair: (String, Int)) -> String {
+             match pair {
+                 (\"Ferris\", _) { \"hello crab\" }
+                 _ { \"hello\" }
+             }
+         }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid syntax: String literals can only be matched at the top level of a pattern, use a guard instead"
+    }));
+}
+
+#[test]
+fn match_alternatives_must_bind_variables_of_the_same_type() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Value { Count(Int), Label(String) }
+         fn describe(value: Value) -> Int {
+             match value {
+                 Count(x) | Label(x) { 1 }
+             }
+         }",
+    );
+
+    assert!(errors
+        .errors()
+        .any(|diagnostic| { diagnostic.message == "Type mismatch: expected Int, found String" }));
+}
+
+#[test]
+fn destructuring_declarations_bind_tuple_elements_and_fields() {
+    let module = lower(
+        "type Point { x: Float, y: Float }
+         fn check(point: Point, pair: (String, Int)) -> Float {
+             let (first, count) = pair
+             let Point(x:, y: vertical) = point
+             x + vertical
+         }",
+    );
+    let check = function(&module, "check");
+
+    let HirStatement::Declaration(tuple) = &check.body.statements[0] else {
+        panic!("expected declaration");
+    };
+    let HirDeclarationPattern::Tuple(elements) = &tuple.pattern else {
+        panic!("expected tuple pattern, got {:?}", tuple.pattern);
+    };
+    assert_eq!(elements.len(), 2);
+
+    let HirStatement::Declaration(point) = &check.body.statements[1] else {
+        panic!("expected declaration");
+    };
+    let HirDeclarationPattern::Struct(destructuring) = &point.pattern else {
+        panic!("expected struct pattern, got {:?}", point.pattern);
+    };
+    let fields = destructuring
+        .fields
+        .iter()
+        .map(|(field, binding)| match binding {
+            HirDeclarationPattern::Ident(ident) => (field.as_str(), ident.as_str()),
+            binding => panic!("expected binding, got {binding:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec![("x", "x"), ("y", "vertical")]);
+    assert!(!destructuring.has_rest);
+
+    let tail = trailing(check);
+    let TypeElement::Plain(sum) = &tail.ty else {
+        panic!("expected plain type, got {:?}", tail.ty);
+    };
+    assert_eq!(sum.ident.as_str(), "Float");
+}
+
+#[test]
+fn destructuring_generic_structs_instantiates_field_types() {
+    let module = lower(
+        "type Container { value: t }
+         fn unwrap(container: Container<Int>) -> Int {
+             let Container(value:) = container
+             value
+         }",
+    );
+    let tail = trailing(function(&module, "unwrap"));
+
+    let TypeElement::Plain(value) = &tail.ty else {
+        panic!("expected instantiated field type, got {:?}", tail.ty);
+    };
+    assert_eq!(value.ident.as_str(), "Int");
+}
+
+#[test]
+fn loops_produce_the_value_given_to_break() {
+    let module = lower(
+        "fn first_even(values: [Int]) -> Int {
+             mut index = 0
+             loop {
+                 if values[index] % 2 == 0 { break values[index] }
+                 index += 1
+             }
+         }",
+    );
+    let tail = trailing(function(&module, "first_even"));
+
+    let HirExpressionKind::Loop(loop_expr) = &tail.kind else {
+        panic!("expected loop expression, got {:?}", tail.kind);
+    };
+    assert!(matches!(loop_expr.kind, HirLoopKind::Infinite));
+    let TypeElement::Plain(value) = &tail.ty else {
+        panic!("expected plain type, got {:?}", tail.ty);
+    };
+    assert_eq!(value.ident.as_str(), "Int");
+}
+
+#[test]
+fn while_try_borrows_variable_conditions() {
+    let module = lower(
+        "fn greet(word: String) {
+             let name: String? = word
+             mut greetings = 0
+             while try name |value| {
+                 println(value)
+                 greetings += 1
+             }
+         }",
+    );
+    let greet = function(&module, "greet");
+
+    let HirStatement::Expression(expression) = &greet.body.statements[2] else {
+        panic!("expected loop statement");
+    };
+    let HirExpressionKind::Loop(loop_expr) = &expression.kind else {
+        panic!("expected loop expression, got {:?}", expression.kind);
+    };
+    let HirLoopKind::WhileTry { condition, .. } = &loop_expr.kind else {
+        panic!("expected while try, got {:?}", loop_expr.kind);
+    };
+    // Moving `name` would leave nothing to match on the next iteration
+    assert_eq!(condition.adjustments, vec![Adjustment::Borrow]);
+}
+
+#[test]
+fn labeled_break_must_refer_to_an_enclosing_loop() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn search(rows: [[Int]]) {
+             outer@ for rows |row| {
+                 for row |value| {
+                     if value == 0 { continue@outer }
+                     while value > 1 { break@inner }
+                 }
+             }
+             break
+         }",
+    );
+
+    let messages = errors
+        .errors()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown identifier: loop label @inner",
+            "Invalid syntax: `break` outside of a loop"
+        ]
+    );
+}
+
+#[test]
+fn numbered_closure_parameters_are_inferred_like_declared_ones() {
+    let module = lower(
+        "type Order { total: Float }
+         fn apply(order: Order, f: |Order| Float) -> Float { f(order) }
+         fn total_of(order: Order) -> Float {
+             apply order { #0.total }
+         }",
+    );
+    let tail = trailing(function(&module, "total_of"));
+
+    let HirExpressionKind::FunctionCall(call) = &tail.kind else {
+        panic!("expected function call, got {:?}", tail.kind);
+    };
+    let HirExpressionKind::Closure(closure) = &call.args[1].kind else {
+        panic!("expected closure, got {:?}", call.args[1].kind);
+    };
+    assert_eq!(closure.parameters.len(), 1);
+    assert_eq!(closure.parameters[0].ident.as_str(), "#0");
+    let TypeElement::Plain(order) = &closure.parameters[0].ty else {
+        panic!("expected plain type, got {:?}", closure.parameters[0].ty);
+    };
+    assert_eq!(order.ident.as_str(), "Order");
+}
+
+#[test]
+fn tuple_elements_are_accessed_by_position() {
+    let module = lower(
+        "type Couple(String, Int)
+         fn age(couple: Couple) -> Int { couple.1 }
+         fn swap(mut pair: (Int, Int)) {
+             let first = pair.0
+             pair.0 = pair.1
+             pair.1 = first
+         }",
+    );
+    let tail = trailing(function(&module, "age"));
+
+    let HirExpressionKind::FieldAccess(access) = &tail.kind else {
+        panic!("expected field access, got {:?}", tail.kind);
+    };
+    assert_eq!(access.field.as_str(), "1");
+    let TypeElement::Plain(age) = &tail.ty else {
+        panic!("expected plain type, got {:?}", tail.ty);
+    };
+    assert_eq!(age.ident.as_str(), "Int");
+}
+
+#[test]
+fn tuple_types_in_signatures_are_typed_by_element() {
+    let module = lower(
+        "fn label(entry: (String, Int)) -> String { entry.0 }
+         fn count(entry: (String, Int,)) -> Int { entry.1 }",
+    );
+
+    let label = function(&module, "label");
+    let entry = &label.signature.parameters.params[0].param_type;
+    let TypeElement::Tuple(entry) = entry else {
+        panic!("expected tuple parameter, got {entry:?}");
+    };
+    let elements = entry
+        .elements
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(elements, vec!["String", "Int"]);
+    assert_eq!(trailing(label).ty.to_string(), "String");
+    assert_eq!(trailing(function(&module, "count")).ty.to_string(), "Int");
+}
+
+#[test]
+fn iterating_mixed_tuples_is_rejected() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn show(pair: (String, Int)) {
+             for pair { print(it) }
+         }",
+    );
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message
+            == "Invalid operation: `for` over (String, Int) can only be used on tuples whose elements all have the same type"
+    }));
+}
+
+#[test]
+fn aliases_expose_fields_and_member_functions() {
+    let module = lower(
+        "type Person { name: String, age: Int }
+         type Human = Person
+         fn older(self: Person) -> Int { self.age + 1 }
+         fn name_of(human: Human) -> String { human.name }
+         fn older_of(human: Human) -> Int { human.older() }",
+    );
+
+    let name = trailing(function(&module, "name_of"));
+    let TypeElement::Plain(name_ty) = &name.ty else {
+        panic!("expected plain type, got {:?}", name.ty);
+    };
+    assert_eq!(name_ty.ident.as_str(), "String");
+
+    let older = trailing(function(&module, "older_of"));
+    assert!(matches!(older.kind, HirExpressionKind::MethodCall(_)));
+    let TypeElement::Plain(older_ty) = &older.ty else {
+        panic!("expected plain type, got {:?}", older.ty);
+    };
+    assert_eq!(older_ty.ident.as_str(), "Int");
+}
+
+#[test]
+fn collection_aliases_can_be_indexed_and_iterated() {
+    let module = lower(
+        "type Inventory = {String: Int}
+         fn count(inventory: Inventory, item: String) -> Int { inventory[item] }
+         fn stocks(inventory: Inventory, item: String) -> Bool { item in inventory }
+         fn total(inventory: Inventory) -> Int {
+             mut total = 0
+             for inventory |item, stock| { total += stock }
+             total
+         }",
+    );
+
+    let count = trailing(function(&module, "count"));
+    let HirExpressionKind::Index(index) = &count.kind else {
+        panic!("expected index access, got {:?}", count.kind);
+    };
+    assert!(matches!(index.base.ty, TypeElement::Dictionary(_)));
+
+    let stocks = trailing(function(&module, "stocks"));
+    let HirExpressionKind::CollectionOp(contains) = &stocks.kind else {
+        panic!("expected collection operation, got {:?}", stocks.kind);
+    };
+    assert!(matches!(contains.rhs.ty, TypeElement::Dictionary(_)));
+
+    let body = &function(&module, "total").body;
+    let HirStatement::Expression(for_expr) = &body.statements[1] else {
+        panic!("expected for statement");
+    };
+    let HirExpressionKind::For(for_expr) = &for_expr.kind else {
+        panic!("expected for expression");
+    };
+    assert_eq!(for_expr.bindings.len(), 2);
+}
+
+#[test]
+fn cyclic_aliases_are_rejected() {
+    let (_, errors) = lower_with_diagnostics(
+        "type Left = Right
+         type Right = Left
+         type Outer = Left",
+    );
+
+    let cycles = errors
+        .errors()
+        .filter(|diagnostic| diagnostic.code == Some("G0013"))
+        .filter(|diagnostic| {
+            diagnostic
+                .suggestion
+                .as_deref()
+                .is_some_and(|suggestion| suggestion.ends_with("refers to itself"))
+        })
+        .count();
+    assert_eq!(cycles, 2);
+}
+
+#[test]
+fn parametric_aliases_are_instantiated_with_their_arguments() {
+    let module = lower(
+        "type Entry { key: t, value: Int }
+         type Keyed = Entry<t>
+         fn key_of(entry: Keyed<String>) -> String { entry.key }",
+    );
+
+    let key = trailing(function(&module, "key_of"));
+    let TypeElement::Plain(key_ty) = &key.ty else {
+        panic!("expected plain type, got {:?}", key.ty);
+    };
+    assert_eq!(key_ty.ident.as_str(), "String");
+}
+
+#[test]
+fn std_methods_on_builtin_receivers_are_typed() {
+    let module = lower(
+        "fn shout(name: String) -> String { name.to_uppercase() }
+         fn has_guest(guests: [String], name: String) -> Bool { guests.contains(name) }
+         fn add_guest(mut guests: [String], name: String) { guests.push(name) }",
+    );
+
+    let shout = trailing(function(&module, "shout"));
+    let TypeElement::Plain(shout_ty) = &shout.ty else {
+        panic!("expected plain type, got {:?}", shout.ty);
+    };
+    assert_eq!(shout_ty.ident.as_str(), "String");
+
+    let has_guest = trailing(function(&module, "has_guest"));
+    assert_eq!(has_guest.ty, TypeElement::bool());
+    let HirExpressionKind::MethodCall(contains) = &has_guest.kind else {
+        panic!("expected method call, got {:?}", has_guest.kind);
+    };
+    // The borrowed parameter is passed on as a reference
+    assert!(contains.args[0].adjustments.is_empty());
+
+    let add_guest = trailing(function(&module, "add_guest"));
+    let HirExpressionKind::MethodCall(push) = &add_guest.kind else {
+        panic!("expected method call, got {:?}", add_guest.kind);
+    };
+    // `push` takes ownership of the borrowed name
+    assert_eq!(push.args[0].adjustments, vec![Adjustment::ToOwned]);
+}
+
+#[test]
+fn std_method_arguments_are_checked() {
+    let (_, errors) = lower_with_diagnostics("fn count(values: [Int]) -> USize { values.len(1) }");
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.code == Some("G0006")
+            && diagnostic.message == "Function len expects 0 arguments, found 1"
+    }));
+}
+
+#[test]
+fn mutating_std_methods_need_a_mutable_receiver() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn add_guest(guests: [String], name: String) { guests.push(name) }
+         fn add_host(mut guests: [String], name: String) { guests.push(name) }",
+    );
+
+    let immutable = errors
+        .errors()
+        .filter(|diagnostic| diagnostic.code == Some("G0004"))
+        .collect::<Vec<_>>();
+    assert_eq!(immutable.len(), 1);
+    assert_eq!(
+        immutable[0].message,
+        "Cannot assign to immutable variable: guests"
+    );
+}
+
+#[test]
+fn element_lookups_return_owned_optionals() {
+    let module = lower(
+        "fn first_guest(guests: [String]) -> String? { guests.first() }
+         fn stock(inventory: {String: Int}, item: String) -> Int? { inventory.get(item) }",
+    );
+
+    for name in ["first_guest", "stock"] {
+        let lookup = trailing(function(&module, name));
+        assert!(matches!(lookup.ty, TypeElement::Optional(_)));
+        let HirExpressionKind::MethodCall(cloned) = &lookup.kind else {
+            panic!("expected method call, got {:?}", lookup.kind);
+        };
+        assert_eq!(cloned.ident.as_str(), "cloned");
+        assert!(matches!(
+            cloned.receiver.kind,
+            HirExpressionKind::MethodCall(_)
+        ));
+    }
+}
+
+/// A trimmed-down rustdoc JSON for a crate with a struct, one of its methods,
+/// a free function and a generic function that cannot be imported
+const GEOMETRY_RUSTDOC: &str = r#"{
+    "root": 0,
+    "format_version": 39,
+    "index": {
+        "0": {"id": 0, "name": "geometry", "visibility": "public",
+              "inner": {"module": {"is_crate": true, "items": [1, 5, 6]}}},
+        "1": {"id": 1, "name": "Point", "visibility": "public",
+              "inner": {"struct": {
+                  "kind": {"plain": {"fields": [2, 3], "has_stripped_fields": false}},
+                  "generics": {"params": [], "where_predicates": []},
+                  "impls": [4]}}},
+        "2": {"id": 2, "name": "x", "visibility": "public",
+              "inner": {"struct_field": {"primitive": "f64"}}},
+        "3": {"id": 3, "name": "y", "visibility": "public",
+              "inner": {"struct_field": {"primitive": "f64"}}},
+        "4": {"id": 4, "name": null, "visibility": "default",
+              "inner": {"impl": {"trait": null, "items": [7]}}},
+        "5": {"id": 5, "name": "distance", "visibility": "public",
+              "inner": {"function": {
+                  "generics": {"params": [], "where_predicates": []},
+                  "sig": {
+                      "inputs": [
+                          ["a", {"borrowed_ref": {"lifetime": null, "is_mutable": false,
+                                 "type": {"resolved_path": {"path": "Point", "id": 1, "args": null}}}}],
+                          ["b", {"borrowed_ref": {"lifetime": null, "is_mutable": false,
+                                 "type": {"resolved_path": {"path": "Point", "id": 1, "args": null}}}}]
+                      ],
+                      "output": {"primitive": "f64"}}}}},
+        "6": {"id": 6, "name": "wrap", "visibility": "public",
+              "inner": {"function": {
+                  "generics": {"params": [{"name": "T", "kind": {"type": {"bounds": []}}}],
+                               "where_predicates": []},
+                  "sig": {"inputs": [["value", {"generic": "T"}]], "output": {"generic": "T"}}}}},
+        "7": {"id": 7, "name": "norm", "visibility": "public",
+              "inner": {"function": {
+                  "generics": {"params": [], "where_predicates": []},
+                  "sig": {
+                      "inputs": [["self", {"borrowed_ref": {"lifetime": null, "is_mutable": false,
+                                  "type": {"generic": "Self"}}}]],
+                      "output": {"primitive": "f64"}}}}}
+    }
+}"#;
+
+#[test]
+fn rustdoc_imports_are_typed() {
+    let geometry = import_rustdoc(GEOMETRY_RUSTDOC).expect("rustdoc JSON should import");
+    let ast = Source::from_string(
+        "use geometry
+
+         fn length(point: Point) -> Double { point.norm() }
+         fn gap(a: Point, b: Point) -> Double { distance(a, b) }
+         fn left(point: Point) -> Double { point.x }",
+    )
+    .try_into_ast()
+    .expect("test code should parse");
+    let segmented = vec![ast].segmented().expect("test code should segment");
+    let (module, errors) =
+        typecheck_with(segmented, &[geometry]).expect("test code should typecheck");
+
+    assert!(
+        !errors.has_errors(),
+        "expected no type errors, got: {errors}"
+    );
+    for name in ["length", "gap", "left"] {
+        let TypeElement::Plain(ty) = &trailing(function(&module, name)).ty else {
+            panic!("expected plain type for {name}");
+        };
+        assert_eq!(ty.ident.as_str(), "Double");
+    }
+    assert!(errors.warnings().any(|diagnostic| {
+        diagnostic.message == "Skipped `geometry::wrap`: generic functions are not supported"
+    }));
+}
+
+#[test]
+fn rustdoc_items_are_visible_only_in_modules_using_the_crate() {
+    let geometry = import_rustdoc(GEOMETRY_RUSTDOC).expect("rustdoc JSON should import");
+    let asts = vec![
+        in_module(
+            "shapes",
+            "use geometry
+
+             pub fn gap(a: Point, b: Point) -> Double { distance(a, b) }",
+        ),
+        Source::from_string("fn left(point: Point) -> Double { point.x }"),
+    ]
+    .into_iter()
+    .map(|source| source.try_into_ast().expect("test code should parse"))
+    .collect::<Vec<_>>();
+    let segmented = asts.segmented().expect("test code should segment");
+    let (module, errors) =
+        typecheck_with(segmented, &[geometry]).expect("test code should typecheck");
+
+    assert!(
+        !errors.has_errors(),
+        "expected no type errors, got: {errors}"
+    );
+    let TypeElement::Plain(gap) = &trailing(function(&module, "gap")).ty else {
+        panic!("expected plain type for gap");
+    };
+    assert_eq!(gap.ident.as_str(), "Double");
+    // Without a `use`, `Point` and its fields are left to rustc
+    assert!(trailing(function(&module, "left")).ty.is_infer());
+}
+
+#[test]
+fn clashing_rustdoc_items_are_reported_as_duplicates() {
+    let geometry = import_rustdoc(GEOMETRY_RUSTDOC).expect("rustdoc JSON should import");
+    let shapes =
+        import_rustdoc(&GEOMETRY_RUSTDOC.replace(r#""name": "geometry""#, r#""name": "shapes""#))
+            .expect("rustdoc JSON should import");
+    let ast = Source::from_string(
+        "use geometry
+         use shapes
+
+         fn left(point: Point) -> Double { point.x }",
+    )
+    .try_into_ast()
+    .expect("test code should parse");
+    let segmented = vec![ast].segmented().expect("test code should segment");
+    let (_, errors) =
+        typecheck_with(segmented, &[geometry, shapes]).expect("test code should typecheck");
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.code == Some("G0023") && diagnostic.message == "Duplicate type `Point`"
+    }));
+}
+
+#[test]
+fn qualified_calls_into_other_modules_are_typed() {
+    let (module, errors) = lower_crate(vec![
+        in_module("orders", "pub fn place(count: Int) -> Int { count }"),
+        Source::from_string("fn total() -> Int { orders::place(2) }"),
+    ]);
+
+    assert!(
+        !errors.has_errors(),
+        "expected no type errors, got: {errors}"
+    );
+    let tail = trailing(function(&module, "total"));
+    let HirExpressionKind::FunctionCall(call) = &tail.kind else {
+        panic!("expected function call, got {:?}", tail.kind);
+    };
+    let namespace = call.namespace.as_ref().expect("call should be qualified");
+    let segments = namespace
+        .segments
+        .iter()
+        .map(|segment| segment.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(segments, ["crate", "orders"]);
+    let TypeElement::Plain(ty) = &tail.ty else {
+        panic!("expected plain type, got {:?}", tail.ty);
+    };
+    assert_eq!(ty.ident.as_str(), "Int");
+}
+
+#[test]
+fn private_items_of_other_modules_are_rejected() {
+    let (_, errors) = lower_crate(vec![
+        in_module("orders", "fn secret() -> Int { 1 }"),
+        Source::from_string("fn peek() -> Int { orders::secret() }"),
+    ]);
+
+    assert!(errors.errors().any(|diagnostic| {
+        diagnostic.message == "Private item: secret is private to module crate::orders"
+    }));
+}
+
+#[test]
+fn modules_may_declare_types_of_the_same_name() {
+    let (module, errors) = lower_crate(vec![
+        in_module("orders", "pub type Order { id: Int }"),
+        in_module("returns", "pub type Order { reason: String }"),
+        Source::from_string(
+            "use crate::orders::Order
+
+             fn id(order: Order) -> Int { order.id }",
+        ),
+    ]);
+
+    assert!(
+        !errors.has_errors(),
+        "expected no type errors, got: {errors}"
+    );
+    assert_eq!(module.types.len(), 2);
+    let use_decl = &module.uses[0];
+    let segments = use_decl
+        .item
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(segments, ["crate", "orders", "Order"]);
+}
+
+#[test]
+fn unresolved_imports_are_reported() {
+    let (_, errors) = lower_crate(vec![
+        in_module("orders", "pub type Order { id: Int }"),
+        Source::from_string("use crate::orders::Invoice"),
+    ]);
+
+    assert!(errors
+        .errors()
+        .any(|diagnostic| { diagnostic.message == "Unresolved path: crate::orders::Invoice" }));
+}
+
+#[test]
+fn duplicate_functions_point_at_both_declarations() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn pick(value: Int) -> Int { value }
+         fn pick(other: Int) -> Int { other }",
+    );
+
+    let diagnostic = errors
+        .errors()
+        .next()
+        .expect("duplicate should be reported");
+    assert_eq!(diagnostic.message, "Duplicate function `pick`");
+    let span = diagnostic
+        .span
+        .as_ref()
+        .expect("duplicate should have a span");
+    let [original] = diagnostic.related.as_slice() else {
+        panic!(
+            "expected the original declaration, got {:?}",
+            diagnostic.related
+        );
+    };
+    assert_eq!(original.message, "first declared here");
+    assert!(original.span.end <= span.start);
+    let help = diagnostic
+        .suggestion
+        .as_ref()
+        .expect("overloads should be explained");
+    assert!(help.contains("have no argument labels"));
+}
+
+#[test]
+fn duplicate_overloads_name_their_shared_labels() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn pick(value: Int, plus increment: Int) -> Int { value + increment }
+         fn pick(value: Float, plus increment: Float) -> Float { value + increment }",
+    );
+
+    let diagnostic = errors
+        .errors()
+        .next()
+        .expect("duplicate should be reported");
+    let help = diagnostic
+        .suggestion
+        .as_ref()
+        .expect("overloads should be explained");
+    assert!(help.contains("have the argument labels `plus:`"));
+}
+
+#[test]
+fn duplicate_types_name_the_files_of_both_declarations() {
+    let (_, errors) = lower_crate(vec![
+        Source::in_module(ModulePath::root(), "type Order { id: Int }"),
+        Source::in_module(ModulePath::root(), "type Order { reason: String }"),
+    ]);
+
+    let diagnostic = errors
+        .errors()
+        .next()
+        .expect("duplicate should be reported");
+    assert_eq!(diagnostic.message, "Duplicate type `Order`");
+    let span = diagnostic
+        .span
+        .as_ref()
+        .expect("duplicate should have a span");
+    assert_eq!(span.file, "main.galvan");
+    assert_eq!(diagnostic.related.len(), 1);
+    assert_eq!(diagnostic.related[0].span.file, "main.galvan");
+}
+
+#[test]
+fn diagnostics_render_the_underlined_source_line() {
+    let (_, errors) = lower_with_diagnostics(
+        "fn pick() -> Int {
+    let counter = 1
+    countr
+}",
+    );
+
+    let rendered = errors.to_string();
+    assert!(rendered.contains("--> <input>:3:5"), "{rendered}");
+    assert!(rendered.contains("3 |     countr\n"), "{rendered}");
+    assert!(rendered.contains("  |     ^^^^^^\n"), "{rendered}");
+    assert!(
+        rendered.contains("= help: Did you mean 'counter'?"),
+        "{rendered}"
+    );
+}
+
+#[test]
+fn duplicate_diagnostics_render_both_declarations() {
+    let (_, errors) = lower_crate(vec![
+        in_module("orders", "type Order { id: Int }"),
+        in_module("orders", "\ntype Order { reason: String }"),
+    ]);
+
+    let rendered = errors.to_string();
+    assert!(rendered.contains("orders.galvan:2:1"), "{rendered}");
+    assert!(rendered.contains("note: first declared here"), "{rendered}");
+    assert!(rendered.contains("orders.galvan:1:1"), "{rendered}");
+}
+
+#[test]
+fn syntax_errors_skip_only_the_broken_items() {
+    let (module, errors) = lower_with_diagnostics(
+        "fn broken() -> Int {
+    1 + * 2
+}
+
+fn fine() -> Int { 2 }
+
+fn also_broken() {
+    let = 3
+}",
+    );
+
+    let syntax_errors = errors
+        .errors()
+        .filter(|diagnostic| diagnostic.code == Some("G0026"))
+        .collect::<Vec<_>>();
+    assert!(syntax_errors.len() >= 2, "{errors}");
+    assert!(syntax_errors
+        .iter()
+        .all(|diagnostic| diagnostic.span.is_some()));
+    function(&module, "fine");
+}
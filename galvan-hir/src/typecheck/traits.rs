@@ -0,0 +1,164 @@
+//! Trait declarations and the conformance of types to them.
+//!
+//! A type conforms to a trait by declaring it, e.g. `type Circle: Shape`, and
+//! providing a member function for every signature the trait requires.
+//! Inside generic functions, member functions required by the traits in the
+//! `where` clause can be called on values of the bounded type parameter.
+
+use galvan_ast::{
+    DeclModifier, FnSignature, Ident, SegmentedAsts, TypeDecl, TypeElement, TypeIdent,
+};
+use galvan_resolver::Lookup;
+
+use crate::error::TranspilerError;
+
+use super::expr::plain_type;
+use super::generics::Substitution;
+use super::{types_compatible, Checker};
+
+impl Checker<'_> {
+    /// Validates trait declarations and checks that every type provides the
+    /// member functions required by the traits it declares conformance to
+    pub(crate) fn check_traits(&mut self, asts: &SegmentedAsts) {
+        for trait_decl in &asts.traits {
+            for member in &trait_decl.item.members {
+                if member.receiver().is_none() {
+                    self.errors.error_with_span(
+                        TranspilerError::InvalidSyntax {
+                            message: format!(
+                                "Member function `{}` of trait {} must take `self` as its first parameter",
+                                member.identifier, trait_decl.item.ident
+                            ),
+                        },
+                        Some(member.span.into()),
+                    );
+                }
+            }
+        }
+
+        for decl in &asts.types {
+            self.enter_module(&decl.source);
+            self.check_conformances(&decl.item);
+        }
+    }
+
+    fn check_conformances(&mut self, decl: &TypeDecl) {
+        let lookup = self.lookup;
+        for conformance in decl.conformances() {
+            let Some(trait_decl) = lookup.resolve_trait(conformance) else {
+                self.errors.error_with_span(
+                    TranspilerError::UnknownType {
+                        name: conformance.to_string(),
+                    },
+                    Some(decl.span().into()),
+                );
+                continue;
+            };
+
+            for required in &trait_decl.item.members {
+                if let Err(message) = self.check_member(decl.ident(), required) {
+                    self.errors.error_with_span(
+                        TranspilerError::TraitConformance {
+                            ty: decl.ident().to_string(),
+                            trait_name: conformance.to_string(),
+                            message,
+                        },
+                        Some(decl.span().into()),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks that `ty` has a member function matching the required
+    /// signature with `Self` replaced by `ty`
+    fn check_member(&self, ty: &TypeIdent, required: &FnSignature) -> Result<(), String> {
+        let labels = required.overload_labels();
+        let labels = labels
+            .iter()
+            .map(|label| label.as_str())
+            .collect::<Vec<_>>();
+        let Some(found) = self
+            .lookup
+            .resolve_function(Some(ty), &required.identifier, &labels)
+        else {
+            return Err(format!("missing member function `{}`", required.identifier));
+        };
+        let found = &found.item.signature;
+
+        let required_params = &required.parameters.params;
+        let found_params = &found.parameters.params;
+        if required_params.len() != found_params.len() {
+            return Err(format!(
+                "member function `{}` takes {} parameters, expected {}",
+                required.identifier,
+                found_params.len(),
+                required_params.len()
+            ));
+        }
+
+        let substitution = Substitution::with_self(plain_type(ty.clone()));
+        for (required_param, found_param) in required_params.iter().zip(found_params) {
+            let expected = substitution.apply(&required_param.param_type);
+            let modifier = |modifier: Option<DeclModifier>| modifier.unwrap_or(DeclModifier::Let);
+            if modifier(required_param.decl_modifier) != modifier(found_param.decl_modifier)
+                || !types_compatible(&expected, &found_param.param_type)
+            {
+                return Err(format!(
+                    "parameter `{}` of member function `{}` must be `{}{}`",
+                    found_param.identifier,
+                    required.identifier,
+                    modifier_prefix(required_param.decl_modifier),
+                    expected
+                ));
+            }
+        }
+
+        let expected = substitution.apply(&required.return_type);
+        if !types_compatible(&expected, &found.return_type) {
+            return Err(format!(
+                "member function `{}` must return {expected}, found {}",
+                required.identifier, found.return_type
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Resolves a member function required by a trait bound of a generic
+    /// receiver, with `Self` replaced by the receiver type
+    pub(crate) fn trait_member(
+        &self,
+        receiver: &TypeElement,
+        ident: &Ident,
+        labels: &[&str],
+    ) -> Option<FnSignature> {
+        let TypeElement::Generic(generic) = receiver else {
+            return None;
+        };
+
+        let lookup = self.lookup;
+        let required = self
+            .bounds
+            .get(&generic.ident)?
+            .iter()
+            .filter_map(|bound| lookup.resolve_trait(bound))
+            .find_map(|trait_decl| trait_decl.item.member(ident.as_str(), labels))?;
+
+        let substitution = Substitution::with_self(receiver.clone());
+        let mut signature = required.clone();
+        for param in &mut signature.parameters.params {
+            param.param_type = substitution.apply(&param.param_type);
+        }
+        signature.return_type = substitution.apply(&signature.return_type);
+        Some(signature)
+    }
+}
+
+fn modifier_prefix(modifier: Option<DeclModifier>) -> &'static str {
+    match modifier {
+        Some(DeclModifier::Mut) => "mut ",
+        Some(DeclModifier::Ref) => "ref ",
+        Some(DeclModifier::Let) | None => "",
+    }
+}
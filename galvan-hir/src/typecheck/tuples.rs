@@ -0,0 +1,112 @@
+//! Positional access on tuples.
+//!
+//! Elements of tuple values and of tuple types such as `type Pair(Int, Int)`
+//! are accessed by position, e.g. `pair.0`. Chained access like `nested.0.1`
+//! reaches the number lexer as the single literal `0.1` and is split into its
+//! positions here.
+
+use galvan_ast::{Expression, Ident, NumberLiteral, Span, TypeDecl, TypeElement};
+use galvan_resolver::Lookup;
+
+use crate::builtins::CheckBuiltins;
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::generics::Substitution;
+use super::Checker;
+
+impl Checker<'_> {
+    /// Lowers `receiver.0`, or `receiver.0.1` for nested tuples
+    pub(crate) fn lower_tuple_access(
+        &mut self,
+        receiver: &Expression,
+        position: &NumberLiteral,
+        span: Span,
+    ) -> HirExpression {
+        let Some(indices) = position
+            .value
+            .split('.')
+            .map(|index| index.parse::<usize>().ok())
+            .collect::<Option<Vec<_>>>()
+        else {
+            self.errors.error_with_span(
+                TranspilerError::MemberAccessError {
+                    message: format!("`{}` is not a tuple position", position.value),
+                },
+                Some(span.into()),
+            );
+            return HirExpression::error("invalid tuple position", span);
+        };
+
+        let (mut access, mut locks_ref) = self.lower_access_base(receiver);
+        for index in indices {
+            let element_ty = self.tuple_element_type(&access.ty, index, span);
+            access = self.field_access(
+                access,
+                locks_ref,
+                Ident::new(index.to_string()),
+                element_ty,
+                span,
+            );
+            locks_ref = false;
+        }
+        access
+    }
+
+    /// The element types of a tuple value or of a declared tuple type
+    pub(crate) fn tuple_elements(&self, ty: &TypeElement) -> Option<Vec<TypeElement>> {
+        let ty = &self.resolve_alias(ty);
+        let (ident, type_args) = match ty {
+            TypeElement::Tuple(tuple) => return Some(tuple.elements.clone()),
+            TypeElement::Plain(plain) => (&plain.ident, None),
+            TypeElement::Parametric(parametric) => {
+                (&parametric.base_type, Some(&parametric.type_args))
+            }
+            _ => return None,
+        };
+
+        let decl = self.lookup.resolve_type(ident)?;
+        let TypeDecl::Tuple(tuple) = &decl.item else {
+            return None;
+        };
+        let mut substitution = Substitution::default();
+        if let Some(type_args) = type_args {
+            substitution.bind_all(&decl.item.generic_params(), type_args);
+        }
+        Some(
+            tuple
+                .members
+                .iter()
+                .map(|member| substitution.apply(&member.r#type))
+                .collect(),
+        )
+    }
+
+    fn tuple_element_type(&mut self, ty: &TypeElement, index: usize, span: Span) -> TypeElement {
+        if ty.is_infer() {
+            return TypeElement::infer();
+        }
+
+        let message = match self.tuple_elements(ty) {
+            Some(elements) => match elements.get(index) {
+                Some(element) => return element.clone(),
+                None => format!(
+                    "{ty} has {} elements, cannot access element {index}",
+                    elements.len()
+                ),
+            },
+            None => match ty {
+                TypeElement::Optional(_) | TypeElement::Result(_) => {
+                    "Should use safe-call operator '?.' or error forwarding '!' on optional and result types"
+                        .to_string()
+                }
+                _ => format!("Positional access `.{index}` requires a tuple, found {ty}"),
+            },
+        };
+        self.errors.error_with_span(
+            TranspilerError::MemberAccessError { message },
+            Some(span.into()),
+        );
+        TypeElement::infer()
+    }
+}
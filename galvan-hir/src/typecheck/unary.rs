@@ -0,0 +1,52 @@
+//! Prefix operators: logical `not`/`!`, bitwise not `~` and numeric
+//! negation `-`.
+
+use galvan_ast::{Ownership, Span, TypeElement, UnaryExpression, UnaryOperator};
+
+use crate::error::TranspilerError;
+use crate::hir::*;
+
+use super::coerce::Expected;
+use super::expr::{is_integer_type, is_signed_numeric_type};
+use super::Checker;
+
+impl Checker<'_> {
+    pub(crate) fn lower_unary(&mut self, unary: &UnaryExpression, span: Span) -> HirExpression {
+        let operand = self.lower_expression(&unary.operand, &Expected::free());
+        let (allowed, allowed_types) = match unary.operator {
+            UnaryOperator::Not => (
+                matches!(&operand.ty, TypeElement::Plain(plain) if plain.ident.as_str() == "Bool")
+                    || matches!(operand.ty, TypeElement::Infer(_) | TypeElement::Never(_)),
+                "Bool",
+            ),
+            UnaryOperator::BitwiseNot => (is_integer_type(&operand.ty), "integer types"),
+            UnaryOperator::Negate => (
+                is_signed_numeric_type(&operand.ty),
+                "signed integer and floating point types",
+            ),
+        };
+        if !allowed {
+            self.errors.error_with_span(
+                TranspilerError::InvalidOperationOnType {
+                    operation: format!("`{}` on {}", unary.operator.symbol(), operand.ty),
+                    allowed_types: allowed_types.to_string(),
+                },
+                Some(span.into()),
+            );
+        }
+
+        let ty = match unary.operator {
+            UnaryOperator::Not => TypeElement::bool(),
+            UnaryOperator::BitwiseNot | UnaryOperator::Negate => operand.ty.clone(),
+        };
+        HirExpression::new(
+            HirExpressionKind::Unary(Box::new(HirUnary {
+                operator: unary.operator,
+                operand,
+            })),
+            ty,
+            Ownership::UniqueOwned,
+            span,
+        )
+    }
+}
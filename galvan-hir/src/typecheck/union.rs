@@ -0,0 +1,217 @@
+//! Anonymous union types such as `Int | String`.
+//!
+//! Every distinct union is generated as a Rust enum with one variant per
+//! member type. Enum and variant names are derived from the member types, so
+//! `Int | String` and `String | Int` share the same enum.
+
+use std::collections::BTreeMap;
+
+use galvan_ast::{TypeElement, TypeIdent, UnionTypeItem};
+use itertools::Itertools;
+
+use crate::error::TranspilerError;
+use crate::hir::{HirUnion, HirUnionVariant};
+
+use super::{types_compatible, Checker};
+
+const UNION_PREFIX: &str = "__Union_";
+
+/// The member types of a union with nested unions flattened, ordered by
+/// their variant name and without duplicates
+pub fn union_members(union: &UnionTypeItem) -> Vec<TypeElement> {
+    fn collect(union: &UnionTypeItem, members: &mut BTreeMap<String, TypeElement>) {
+        for member in &union.members {
+            match member {
+                TypeElement::Union(nested) => collect(nested, members),
+                member => {
+                    members
+                        .entry(union_case(member).to_string())
+                        .or_insert_with(|| member.clone());
+                }
+            }
+        }
+    }
+
+    let mut members = BTreeMap::new();
+    collect(union, &mut members);
+    members.into_values().collect()
+}
+
+/// Name of the Rust enum generated for the given union
+pub fn union_ident(union: &UnionTypeItem) -> TypeIdent {
+    let cases = union_members(union).iter().map(union_case).join("_");
+    TypeIdent::new(format!("{UNION_PREFIX}{cases}"))
+}
+
+/// Whether the identifier names a generated union enum
+pub fn is_union_ident(ident: &TypeIdent) -> bool {
+    ident.as_str().starts_with(UNION_PREFIX)
+}
+
+/// Variant name of a member type in the generated union enum. Plain types
+/// keep their name, so `match` narrows a union with `Int(n) => ...`.
+pub fn union_case(member: &TypeElement) -> TypeIdent {
+    fn escaped_name(ty: &TypeElement) -> String {
+        match ty {
+            TypeElement::Plain(ty) => ty.ident.to_string(),
+            TypeElement::Generic(ty) => ty.ident.as_str().to_uppercase(),
+            TypeElement::Array(ty) => format!("Array_{}", escaped_name(&ty.elements)),
+            TypeElement::Set(ty) => format!("Set_{}", escaped_name(&ty.elements)),
+            TypeElement::Dictionary(ty) => {
+                format!("Dict_{}_{}", escaped_name(&ty.key), escaped_name(&ty.value))
+            }
+            TypeElement::OrderedDictionary(ty) => format!(
+                "OrderedDict_{}_{}",
+                escaped_name(&ty.key),
+                escaped_name(&ty.value)
+            ),
+            TypeElement::Tuple(ty) => {
+                format!("Tuple_{}", ty.elements.iter().map(escaped_name).join("_"))
+            }
+            TypeElement::Optional(ty) => format!("Option_{}", escaped_name(&ty.inner)),
+            TypeElement::Result(ty) => format!(
+                "Result_{}_{}",
+                escaped_name(&ty.success),
+                ty.error.as_ref().map_or(String::new(), escaped_name)
+            ),
+            TypeElement::Parametric(ty) => format!(
+                "{}_{}",
+                ty.base_type,
+                ty.type_args.iter().map(escaped_name).join("_")
+            ),
+            TypeElement::Closure(ty) => format!(
+                "Closure_{}_{}",
+                ty.parameters.iter().map(escaped_name).join("_"),
+                escaped_name(&ty.return_ty)
+            ),
+            TypeElement::Union(ty) => union_members(ty).iter().map(escaped_name).join("_"),
+            TypeElement::Void(_) => "Void".to_owned(),
+            TypeElement::Infer(_) => "Infer".to_owned(),
+            TypeElement::Never(_) => "Never".to_owned(),
+        }
+    }
+
+    TypeIdent::new(escaped_name(member))
+}
+
+/// The member of the union a value of the given type is wrapped in, if any
+pub(crate) fn union_member<'u>(
+    members: &'u [TypeElement],
+    actual: &TypeElement,
+) -> Option<&'u TypeElement> {
+    if matches!(actual, TypeElement::Union(_)) {
+        return None;
+    }
+
+    members
+        .iter()
+        .find(|member| types_compatible(member, actual))
+}
+
+impl Checker<'_> {
+    /// Registers every union contained in the given type so that its enum is
+    /// generated for the module
+    pub(crate) fn register_unions(&mut self, ty: &TypeElement) {
+        match ty {
+            TypeElement::Union(union) => {
+                self.register_union(union);
+            }
+            TypeElement::Array(array) => self.register_unions(&array.elements),
+            TypeElement::Set(set) => self.register_unions(&set.elements),
+            TypeElement::Dictionary(dict) => {
+                self.register_unions(&dict.key);
+                self.register_unions(&dict.value);
+            }
+            TypeElement::OrderedDictionary(dict) => {
+                self.register_unions(&dict.key);
+                self.register_unions(&dict.value);
+            }
+            TypeElement::Tuple(tuple) => {
+                for element in &tuple.elements {
+                    self.register_unions(element);
+                }
+            }
+            TypeElement::Optional(optional) => self.register_unions(&optional.inner),
+            TypeElement::Result(result) => {
+                self.register_unions(&result.success);
+                if let Some(error) = &result.error {
+                    self.register_unions(error);
+                }
+            }
+            TypeElement::Parametric(parametric) => {
+                for arg in &parametric.type_args {
+                    self.register_unions(arg);
+                }
+            }
+            TypeElement::Closure(closure) => {
+                for param in &closure.parameters {
+                    self.register_unions(param);
+                }
+                self.register_unions(&closure.return_ty);
+            }
+            TypeElement::Plain(_)
+            | TypeElement::Generic(_)
+            | TypeElement::Void(_)
+            | TypeElement::Infer(_)
+            | TypeElement::Never(_) => {}
+        }
+    }
+
+    /// Registers a single union and returns the name of its generated enum
+    pub(crate) fn register_union(&mut self, union: &UnionTypeItem) -> TypeIdent {
+        let ident = union_ident(union);
+        if self.unions.contains_key(&ident) {
+            return ident;
+        }
+
+        let members = union_members(union);
+        for member in &members {
+            self.register_unions(member);
+        }
+
+        let mut generics = std::collections::HashSet::new();
+        TypeElement::Union(Box::new(union.clone())).collect_generics_recursive(&mut generics);
+        if !generics.is_empty() {
+            self.errors.error_with_span(
+                TranspilerError::Unimplemented {
+                    feature: format!(
+                        "generic type parameters in union type {}",
+                        union_display(union)
+                    ),
+                },
+                Some(union.span.into()),
+            );
+        }
+        if members.len() < 2 {
+            self.errors.error_with_span(
+                TranspilerError::InvalidSyntax {
+                    message: format!(
+                        "Union type {} must have at least two distinct members",
+                        union_display(union)
+                    ),
+                },
+                Some(union.span.into()),
+            );
+        }
+
+        let variants = members
+            .into_iter()
+            .map(|ty| HirUnionVariant {
+                case: union_case(&ty),
+                ty,
+            })
+            .collect();
+        self.unions.insert(
+            ident.clone(),
+            HirUnion {
+                ident: ident.clone(),
+                variants,
+            },
+        );
+        ident
+    }
+}
+
+fn union_display(union: &UnionTypeItem) -> String {
+    TypeElement::Union(Box::new(union.clone())).to_string()
+}
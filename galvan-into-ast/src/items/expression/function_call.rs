@@ -3,7 +3,7 @@ use galvan_ast::{
     DeclModifier, EnumAccess, EnumConstructor, EnumConstructorArg, Expression, FunctionCall,
     FunctionCallArg, Ident, Return, Span, Statement, Throw, TypeElement, TypeIdent, UsePath,
 };
-use galvan_parse::TreeCursor;
+use galvan_parse::{Node, TreeCursor};
 
 use crate::{cursor_expect, result::CursorUtil, AstError, ReadCursor, SpanExt};
 
@@ -68,6 +68,10 @@ pub fn read_trailing_closure_call(
         cursor.next();
     }
 
+    if closure_arguments.is_empty() && !is_control_flow(identifier.as_str()) {
+        closure_arguments = numbered_closure_parameters(cursor.node(), source);
+    }
+
     let body = Body::read_cursor(cursor, source)?;
     let body_span = body.span;
     let block = Block {
@@ -98,6 +102,48 @@ pub fn read_trailing_closure_call(
     })
 }
 
+/// Control-flow calls take their body as a trailing closure, but numbered
+/// parameters used in the body belong to the enclosing closure
+fn is_control_flow(identifier: &str) -> bool {
+    matches!(identifier, "if" | "for" | "try" | "loop" | "while")
+}
+
+/// Synthesizes the parameters of a trailing closure that refers to them as
+/// `#0`, `#1`, ... instead of declaring them. The highest index used
+/// determines the number of parameters.
+fn numbered_closure_parameters(body: Node<'_>, source: &str) -> Vec<ClosureParameter> {
+    let count = highest_numbered_parameter(body, source).map_or(0, |index| index + 1);
+    (0..count)
+        .map(|index| ClosureParameter {
+            ident: Ident::new(format!("#{index}")),
+            ty: TypeElement::infer(),
+        })
+        .collect()
+}
+
+fn highest_numbered_parameter(node: Node<'_>, source: &str) -> Option<usize> {
+    let mut cursor = node.walk();
+    let children = node.children(&mut cursor).collect::<Vec<_>>();
+    let is_closure_call = node.kind() == "trailing_closure_expression"
+        && children
+            .first()
+            .is_some_and(|identifier| !is_control_flow(&source[identifier.byte_range()]));
+
+    children
+        .into_iter()
+        .filter_map(|child| match child.kind() {
+            "numbered_closure_parameter" => source[child.byte_range()]
+                .trim_start_matches('#')
+                .parse()
+                .ok(),
+            // Nested closures number their own parameters
+            "closure" => None,
+            "body" if is_closure_call => None,
+            _ => highest_numbered_parameter(child, source),
+        })
+        .max()
+}
+
 pub fn read_free_function_call(
     cursor: &mut TreeCursor<'_>,
     source: &str,
@@ -200,6 +200,7 @@ impl ReadCursor for CollectionOperator {
         let op = match cursor.kind()? {
             "concat" => Self::Concat,
             "remove" => Self::Remove,
+            "repeat" => Self::Repeat,
             "contains" => Self::Contains,
             unknown => unreachable!("Unknown collection operator: {unknown}"),
         };
@@ -227,8 +228,11 @@ impl ReadCursor for ComparisonOperator {
 }
 
 impl ReadCursor for CustomInfix {
-    fn read_cursor(_cursor: &mut TreeCursor<'_>, _source: &str) -> Result<Self, AstError> {
-        todo!()
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "custom_infix_operator");
+        Ok(CustomInfix::new(
+            &source[node.start_byte()..node.end_byte()],
+        ))
     }
 }
 
@@ -1,7 +1,8 @@
 use galvan_ast::{
-    Block, Body, Expression, Ident, MatchArm, MatchBindingPattern, MatchEnumPattern,
-    MatchExpression, MatchNamedPatternArg, MatchPattern, MatchPatternArg, MatchWildcardPattern,
-    Span, TypeIdent,
+    Block, Body, Expression, Ident, Literal, MatchAlternativesPattern, MatchArm,
+    MatchBindingPattern, MatchEnumPattern, MatchExpression, MatchLiteralPattern,
+    MatchNamedPatternArg, MatchPattern, MatchPatternArg, MatchRangePattern, MatchTuplePattern,
+    MatchWildcardPattern, RangeOperator, Span, TypeIdent,
 };
 use galvan_parse::TreeCursor;
 
@@ -42,33 +43,143 @@ impl ReadCursor for MatchArm {
         let pattern = MatchPattern::read_cursor(cursor, source)?;
 
         cursor.next();
+        let guard = if cursor.kind()? == "match_guard" {
+            cursor.child();
+            cursor_expect!(cursor, "if_keyword");
+            cursor.next();
+            let guard = Expression::read_cursor(cursor, source)?;
+            cursor.goto_parent();
+            cursor.next();
+            Some(guard)
+        } else {
+            None
+        };
+
         let body = Body::read_cursor(cursor, source)?;
         let span = body.span;
         let body = Block { body, span };
 
         cursor.goto_parent();
 
-        Ok(Self { pattern, body })
+        Ok(Self {
+            pattern,
+            guard,
+            body,
+        })
     }
 }
 
 impl ReadCursor for MatchPattern {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
-        cursor_expect!(cursor, "match_pattern");
+        let node = cursor_expect!(cursor, "match_pattern");
+        let span = Span::from_node(node);
 
         cursor.child();
-        let pattern = match cursor.kind()? {
-            "wildcard_match_pattern" => {
-                MatchPattern::Wildcard(MatchWildcardPattern::read_cursor(cursor, source)?)
+        let mut alternatives = vec![read_single_pattern(cursor, source)?];
+        while cursor.next() {
+            if cursor.kind()? == "pipe" {
+                continue;
             }
-            "enum_match_pattern" => {
-                MatchPattern::EnumVariant(MatchEnumPattern::read_cursor(cursor, source)?)
+            alternatives.push(read_single_pattern(cursor, source)?);
+        }
+        cursor.goto_parent();
+
+        if alternatives.len() == 1 {
+            Ok(alternatives.remove(0))
+        } else {
+            Ok(MatchPattern::Alternatives(MatchAlternativesPattern {
+                alternatives,
+                span,
+            }))
+        }
+    }
+}
+
+fn read_single_pattern(
+    cursor: &mut TreeCursor<'_>,
+    source: &str,
+) -> Result<MatchPattern, AstError> {
+    let pattern = match cursor.kind()? {
+        "wildcard_match_pattern" => {
+            MatchPattern::Wildcard(MatchWildcardPattern::read_cursor(cursor, source)?)
+        }
+        "enum_match_pattern" => {
+            MatchPattern::EnumVariant(MatchEnumPattern::read_cursor(cursor, source)?)
+        }
+        "literal_match_pattern" => {
+            MatchPattern::Literal(MatchLiteralPattern::read_cursor(cursor, source)?)
+        }
+        "range_match_pattern" => {
+            MatchPattern::Range(MatchRangePattern::read_cursor(cursor, source)?)
+        }
+        "tuple_match_pattern" => {
+            MatchPattern::Tuple(MatchTuplePattern::read_cursor(cursor, source)?)
+        }
+        unknown => unreachable!("Unknown match pattern: {unknown}"),
+    };
+
+    Ok(pattern)
+}
+
+impl ReadCursor for MatchLiteralPattern {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "literal_match_pattern");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        let literal = Literal::read_cursor(cursor, source)?;
+        cursor.goto_parent();
+
+        Ok(Self { literal, span })
+    }
+}
+
+impl ReadCursor for MatchRangePattern {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "range_match_pattern");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        let start = Literal::read_cursor(cursor, source)?;
+
+        cursor.next();
+        let operator = RangeOperator::read_cursor(cursor, source)?;
+
+        cursor.next();
+        let end = Literal::read_cursor(cursor, source)?;
+        cursor.goto_parent();
+
+        Ok(Self {
+            start,
+            operator,
+            end,
+            span,
+        })
+    }
+}
+
+impl ReadCursor for MatchTuplePattern {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "tuple_match_pattern");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        cursor_expect!(cursor, "paren_open");
+
+        cursor.next();
+        let mut elements = Vec::new();
+        while cursor.kind()? == "binding_match_pattern" {
+            elements.push(MatchBindingPattern::read_cursor(cursor, source)?);
+            cursor.next();
+            while cursor.kind()? == "," {
+                cursor.next();
             }
-            unknown => unreachable!("Unknown match pattern: {unknown}"),
-        };
+        }
+
+        cursor_expect!(cursor, "paren_close");
         cursor.goto_parent();
 
-        Ok(pattern)
+        Ok(Self { elements, span })
     }
 }
 
@@ -171,6 +282,9 @@ impl ReadCursor for MatchBindingPattern {
             "wildcard_match_pattern" => {
                 MatchBindingPattern::Wildcard(MatchWildcardPattern::read_cursor(cursor, source)?)
             }
+            "match_pattern" => {
+                MatchBindingPattern::Pattern(Box::new(MatchPattern::read_cursor(cursor, source)?))
+            }
             unknown => unreachable!("Unknown match binding pattern: {unknown}"),
         };
         cursor.goto_parent();
@@ -2,6 +2,8 @@ mod infix;
 
 mod postfix;
 
+mod unary;
+
 mod function_call;
 pub use function_call::*;
 
@@ -1,6 +1,6 @@
 use galvan_ast::{
     AccessExpression, Block, Body, ClosureParameter, ElseExpression, Expression, PostfixExpression,
-    Span, YeetExpression,
+    SliceExpression, Span, YeetExpression,
 };
 use galvan_parse::TreeCursor;
 
@@ -38,6 +38,39 @@ impl ReadCursor for PostfixExpression {
                 }
                 .into()
             }
+            "slice_operator" => {
+                cursor.child();
+                cursor_expect!(cursor, "bracket_open");
+
+                cursor.next();
+                let start = if cursor.kind()? == "expression" {
+                    let start = Expression::read_cursor(cursor, source)?;
+                    cursor.next();
+                    Some(start)
+                } else {
+                    None
+                };
+                cursor_expect!(cursor, "colon");
+
+                cursor.next();
+                let end = if cursor.kind()? == "expression" {
+                    let end = Expression::read_cursor(cursor, source)?;
+                    cursor.next();
+                    Some(end)
+                } else {
+                    None
+                };
+                cursor_expect!(cursor, "bracket_close");
+
+                cursor.goto_parent();
+                SliceExpression {
+                    base: inner,
+                    start,
+                    end,
+                    span,
+                }
+                .into()
+            }
             unknown => unreachable!("Unknown postfix operator: {unknown}"),
         };
         cursor.goto_parent();
@@ -0,0 +1,41 @@
+use galvan_ast::{Expression, Span, UnaryExpression, UnaryOperator};
+use galvan_parse::TreeCursor;
+
+use crate::{cursor_expect, result::CursorUtil, AstError, ReadCursor, SpanExt};
+
+impl ReadCursor for UnaryExpression {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "unary_expression");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        let operator = UnaryOperator::read_cursor(cursor, source)?;
+
+        cursor.next();
+        let operand = Expression::read_cursor(cursor, source)?;
+
+        cursor.goto_parent();
+        Ok(UnaryExpression {
+            operator,
+            operand,
+            span,
+        })
+    }
+}
+
+impl ReadCursor for UnaryOperator {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, _source: &str) -> Result<Self, AstError> {
+        cursor_expect!(cursor, "unary_operator");
+
+        cursor.child();
+        let op = match cursor.kind()? {
+            "not" | "exclamation_mark" => Self::Not,
+            "bitwise_not" => Self::BitwiseNot,
+            "minus" => Self::Negate,
+            unknown => unreachable!("Unknown unary operator: {unknown}"),
+        };
+        cursor.goto_parent();
+
+        Ok(op)
+    }
+}
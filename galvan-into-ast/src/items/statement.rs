@@ -1,8 +1,10 @@
 use galvan_ast::{
-    Assignment, AssignmentOperator, Closure, CollectionLiteral, ConstructorCall, DeclModifier,
-    Declaration, ElseExpression, EnumAccess, EnumConstructor, Expression, ExpressionKind,
-    FunctionCall, Group, Ident, InfixExpression, Literal, MatchExpression, ModifiedExpression,
-    PostfixExpression, Span, Statement, TypeElement,
+    Assignment, AssignmentOperator, Break, Closure, CollectionLiteral, ConstructorCall, Continue,
+    DeclModifier, Declaration, DeclarationPattern, ElseExpression, EnumAccess, EnumConstructor,
+    Expression, ExpressionKind, FieldDestructuring, FunctionCall, Group, Ident, InfixExpression,
+    LabeledExpression, Literal, MatchExpression, MatchWildcardPattern, ModifiedExpression,
+    PostfixExpression, Span, Statement, StructDestructuring, TupleDestructuring, TypeElement,
+    TypeIdent, UnaryExpression,
 };
 use galvan_parse::TreeCursor;
 
@@ -20,16 +22,8 @@ impl ReadCursor for Statement {
             "declaration" => Statement::Declaration(Declaration::read_cursor(cursor, source)?),
             "expression" => Statement::Expression(Expression::read_cursor(cursor, source)?),
             "free_function" => read_free_function_call(cursor, source)?,
-            "break_statement" => {
-                let node = cursor.node();
-                let span = Span::from_node(node);
-                Statement::Break(galvan_ast::Break { span })
-            }
-            "continue_statement" => {
-                let node = cursor.node();
-                let span = Span::from_node(node);
-                Statement::Continue(galvan_ast::Continue { span })
-            }
+            "break_statement" => Statement::Break(Break::read_cursor(cursor, source)?),
+            "continue_statement" => Statement::Continue(Continue::read_cursor(cursor, source)?),
             _ => unreachable!("Unknown statement kind: {:?}", cursor.kind()?),
         };
 
@@ -38,6 +32,80 @@ impl ReadCursor for Statement {
     }
 }
 
+impl ReadCursor for Break {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "break_statement");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        cursor_expect!(cursor, "break_keyword");
+        let mut has_next = cursor.next();
+        let label = if has_next && cursor.kind()? == "label_reference" {
+            let label = read_label_reference(cursor, source)?;
+            has_next = cursor.next();
+            Some(label)
+        } else {
+            None
+        };
+        let value = if has_next {
+            Some(Expression::read_cursor(cursor, source)?)
+        } else {
+            None
+        };
+
+        cursor.goto_parent();
+        Ok(Break { label, value, span })
+    }
+}
+
+impl ReadCursor for Continue {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "continue_statement");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        cursor_expect!(cursor, "continue_keyword");
+        let label = if cursor.next() {
+            Some(read_label_reference(cursor, source)?)
+        } else {
+            None
+        };
+
+        cursor.goto_parent();
+        Ok(Continue { label, span })
+    }
+}
+
+/// Reads the `@label` following a `break` or `continue` keyword
+fn read_label_reference(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Ident, AstError> {
+    cursor_expect!(cursor, "label_reference");
+
+    cursor.child();
+    cursor_expect!(cursor, "at");
+    cursor.next();
+    let label = Ident::read_cursor(cursor, source)?;
+
+    cursor.goto_parent();
+    Ok(label)
+}
+
+impl ReadCursor for LabeledExpression {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "labeled_expression");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        let label = Ident::read_cursor(cursor, source)?;
+        cursor.next();
+        cursor_expect!(cursor, "at");
+        cursor.next();
+        let inner = Expression::read_cursor(cursor, source)?;
+
+        cursor.goto_parent();
+        Ok(LabeledExpression { label, inner, span })
+    }
+}
+
 impl ReadCursor for Declaration {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
         let node = cursor_expect!(cursor, "declaration");
@@ -47,7 +115,7 @@ impl ReadCursor for Declaration {
 
         let decl_modifier = DeclModifier::read_cursor(cursor, source)?;
         cursor.next();
-        let identifier = Ident::read_cursor(cursor, source)?;
+        let pattern = DeclarationPattern::read_cursor(cursor, source)?;
         cursor.next();
 
         let type_annotation = match cursor.kind()? {
@@ -75,7 +143,7 @@ impl ReadCursor for Declaration {
 
         let decl = Declaration {
             decl_modifier,
-            identifier,
+            pattern,
             type_annotation,
             assignment_modifier,
             assignment,
@@ -86,6 +154,99 @@ impl ReadCursor for Declaration {
     }
 }
 
+impl ReadCursor for DeclarationPattern {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let pattern = match cursor.kind()? {
+            "ident" => DeclarationPattern::Ident(Ident::read_cursor(cursor, source)?),
+            "wildcard_match_pattern" => {
+                DeclarationPattern::Wildcard(MatchWildcardPattern::read_cursor(cursor, source)?)
+            }
+            "tuple_destructuring" => {
+                DeclarationPattern::Tuple(TupleDestructuring::read_cursor(cursor, source)?)
+            }
+            "struct_destructuring" => {
+                DeclarationPattern::Struct(StructDestructuring::read_cursor(cursor, source)?)
+            }
+            unknown => unreachable!("Unknown declaration pattern: {unknown}"),
+        };
+
+        Ok(pattern)
+    }
+}
+
+impl ReadCursor for TupleDestructuring {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "tuple_destructuring");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        cursor_expect!(cursor, "paren_open");
+
+        cursor.next();
+        let mut elements = Vec::new();
+        while cursor.kind()? != "paren_close" {
+            if cursor.kind()? != "," {
+                elements.push(DeclarationPattern::read_cursor(cursor, source)?);
+            }
+            cursor.next();
+        }
+        cursor.goto_parent();
+
+        Ok(Self { elements, span })
+    }
+}
+
+impl ReadCursor for StructDestructuring {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "struct_destructuring");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        let ty = TypeIdent::read_cursor(cursor, source)?;
+
+        cursor.next();
+        cursor_expect!(cursor, "paren_open");
+
+        cursor.next();
+        let mut fields = Vec::new();
+        while cursor.kind()? != "paren_close" {
+            if cursor.kind()? == "field_destructuring" {
+                fields.push(FieldDestructuring::read_cursor(cursor, source)?);
+            }
+            cursor.next();
+        }
+        cursor.goto_parent();
+
+        Ok(Self { ty, fields, span })
+    }
+}
+
+impl ReadCursor for FieldDestructuring {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let node = cursor_expect!(cursor, "field_destructuring");
+        let span = Span::from_node(node);
+
+        cursor.child();
+        let field = Ident::read_cursor(cursor, source)?;
+
+        cursor.next();
+        cursor_expect!(cursor, "colon");
+
+        let binding = if cursor.next() {
+            Some(DeclarationPattern::read_cursor(cursor, source)?)
+        } else {
+            None
+        };
+        cursor.goto_parent();
+
+        Ok(Self {
+            field,
+            binding,
+            span,
+        })
+    }
+}
+
 impl ReadCursor for Assignment {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
         let node = cursor_expect!(cursor, "assignment");
@@ -149,6 +310,9 @@ impl ReadCursor for Expression {
             "postfix_expression" => {
                 ExpressionKind::Postfix(PostfixExpression::read_cursor(cursor, source)?.into())
             }
+            "unary_expression" => {
+                ExpressionKind::Unary(UnaryExpression::read_cursor(cursor, source)?.into())
+            }
             "argument_modifier_expression" => {
                 ExpressionKind::Modified(ModifiedExpression::read_cursor(cursor, source)?.into())
             }
@@ -158,8 +322,12 @@ impl ReadCursor for Expression {
             "collection_literal" => CollectionLiteral::read_cursor(cursor, source)?.into(),
             "literal" => Literal::read_cursor(cursor, source)?.into(),
             "ident" => Ident::read_cursor(cursor, source)?.into(),
+            "numbered_closure_parameter" => Ident::new(&source[cursor.node().byte_range()]).into(),
             "closure" => Closure::read_cursor(cursor, source)?.into(),
             "group" => Group::read_cursor(cursor, source)?.into(),
+            "labeled_expression" => {
+                ExpressionKind::Labeled(LabeledExpression::read_cursor(cursor, source)?.into())
+            }
             _ => ExpressionKind::Infix(InfixExpression::read_cursor(cursor, source)?.into()),
         };
 
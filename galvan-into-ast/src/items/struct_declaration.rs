@@ -22,6 +22,7 @@ impl ReadCursor for StructTypeDecl {
         let ident = TypeIdent::read_cursor(cursor, source)?;
 
         cursor.next();
+        let conformances = read_conformances(cursor, source)?;
         cursor_expect!(cursor, "brace_open");
 
         cursor.next();
@@ -43,12 +44,39 @@ impl ReadCursor for StructTypeDecl {
         Ok(StructTypeDecl {
             visibility,
             ident,
+            conformances,
             members,
             span,
         })
     }
 }
 
+/// Reads the traits a type declares conformance to, e.g. `: Shape + Named`,
+/// and moves the cursor past them
+fn read_conformances(
+    cursor: &mut TreeCursor<'_>,
+    source: &str,
+) -> Result<Vec<TypeIdent>, AstError> {
+    if cursor.kind()? != "conformance_list" {
+        return Ok(vec![]);
+    }
+
+    cursor.child();
+    cursor_expect!(cursor, "colon");
+
+    let mut conformances = vec![];
+    while cursor.next() {
+        if cursor.kind()? == "type_ident" {
+            conformances.push(TypeIdent::read_cursor(cursor, source)?);
+        }
+    }
+
+    cursor.goto_parent();
+    cursor.next();
+
+    Ok(conformances)
+}
+
 impl ReadCursor for StructTypeMember {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
         let struct_field = cursor_expect!(cursor, "struct_field");
@@ -108,6 +136,7 @@ impl ReadCursor for EnumTypeDecl {
         let ident = TypeIdent::read_cursor(cursor, source)?;
 
         cursor.next();
+        let conformances = read_conformances(cursor, source)?;
         cursor_expect!(cursor, "brace_open");
 
         cursor.next();
@@ -129,6 +158,7 @@ impl ReadCursor for EnumTypeDecl {
         Ok(EnumTypeDecl {
             visibility,
             ident,
+            conformances,
             members,
             span,
         })
@@ -149,6 +179,7 @@ impl ReadCursor for TupleTypeDecl {
         let ident = TypeIdent::read_cursor(cursor, source)?;
 
         cursor.next();
+        let conformances = read_conformances(cursor, source)?;
         cursor_expect!(cursor, "paren_open");
 
         cursor.next();
@@ -170,6 +201,7 @@ impl ReadCursor for TupleTypeDecl {
         Ok(TupleTypeDecl {
             visibility,
             ident,
+            conformances,
             members,
             span,
         })
@@ -247,11 +279,18 @@ impl ReadCursor for EmptyTypeDecl {
         cursor.next();
         let ident = TypeIdent::read_cursor(cursor, source)?;
 
+        let conformances = if cursor.next() {
+            read_conformances(cursor, source)?
+        } else {
+            vec![]
+        };
+
         cursor.goto_parent();
 
         Ok(EmptyTypeDecl {
             visibility,
             ident,
+            conformances,
             span,
         })
     }
@@ -1,8 +1,8 @@
 use galvan_ast::{
-    AliasTypeDecl, Body, CmdDecl, CmdSignature, DeclModifier, EmptyTypeDecl, EnumTypeDecl, FnDecl,
-    FnSignature, Ident, Param, ParamList, RootItem, Span, Statement, StringLiteral, StructTypeDecl,
-    TestDecl, TupleTypeDecl, TypeDecl, TypeElement, TypeIdent, UseDecl, UsePath, Visibility,
-    WhereBound, WhereClause,
+    AliasTypeDecl, Associativity, Body, CmdDecl, CmdSignature, CustomInfix, DeclModifier,
+    EmptyTypeDecl, EnumTypeDecl, FnDecl, FnSignature, Ident, InfixDecl, Param, ParamList, RootItem,
+    Span, Statement, StringLiteral, StructTypeDecl, TestDecl, TraitDecl, TupleTypeDecl, TypeDecl,
+    TypeElement, TypeIdent, UseDecl, UsePath, Visibility, WhereBound, WhereClause,
 };
 use galvan_parse::TreeCursor;
 
@@ -12,12 +12,16 @@ impl ReadCursor for RootItem {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
         Ok(match cursor.kind()? {
             "use_declaration" => UseDecl::read_cursor(cursor, source)?.into(),
-            "build" => todo!("Implement build entry point!"),
+            // Build entry points are not supported yet and are reported
+            // like any other unexpected item
+            "build" => return Err(AstError::UnexpectedNode("build".to_owned())),
             "test" => TestDecl::read_cursor(cursor, source)?.into(),
             "function" => FnDecl::read_cursor(cursor, source)?.into(),
             "cmd" => CmdDecl::read_cursor(cursor, source)?.into(),
             "type_declaration" => TypeDecl::read_cursor(cursor, source)?.into(),
-            other => unreachable!("Unexpected node in root item: {other}"),
+            "trait_declaration" => TraitDecl::read_cursor(cursor, source)?.into(),
+            "infix_declaration" => InfixDecl::read_cursor(cursor, source)?.into(),
+            other => return Err(AstError::UnexpectedNode(other.to_owned())),
         })
     }
 }
@@ -84,6 +88,109 @@ impl ReadCursor for TestDecl {
     }
 }
 
+impl ReadCursor for TraitDecl {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let trait_decl = cursor_expect!(cursor, "trait_declaration");
+        let span = Span::from_node(trait_decl);
+        cursor.child();
+
+        let visibility = Visibility::read_cursor(cursor, source)?;
+        cursor_expect!(cursor, "trait_keyword");
+
+        cursor.next();
+        let ident = TypeIdent::read_cursor(cursor, source)?;
+
+        cursor.next();
+        cursor_expect!(cursor, "brace_open");
+
+        cursor.next();
+        let mut members = vec![];
+        while cursor.kind()? == "fn_signature" {
+            members.push(FnSignature::read_cursor(cursor, source)?);
+
+            cursor.next();
+            while cursor.kind()? == ";" {
+                cursor.next();
+            }
+        }
+
+        cursor_expect!(cursor, "brace_close");
+
+        cursor.goto_parent();
+
+        Ok(TraitDecl {
+            visibility,
+            ident,
+            members,
+            span,
+        })
+    }
+}
+
+impl ReadCursor for InfixDecl {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let infix_decl = cursor_expect!(cursor, "infix_declaration");
+        let span = Span::from_node(infix_decl);
+        cursor.child();
+
+        let visibility = Visibility::read_cursor(cursor, source)?;
+        cursor_expect!(cursor, "infix_keyword");
+
+        cursor.next();
+        let operator = CustomInfix::read_cursor(cursor, source)?;
+
+        cursor.next();
+        cursor_expect!(cursor, "assign");
+
+        cursor.next();
+        let function = Ident::read_cursor(cursor, source)?;
+
+        let mut precedence = 0;
+        let mut associativity = Associativity::default();
+        while cursor.next() {
+            match cursor.kind()? {
+                "infix_precedence" => {
+                    cursor.child();
+                    cursor_expect!(cursor, "precedence_keyword");
+                    cursor.next();
+                    cursor_expect!(cursor, "colon");
+                    cursor.next();
+                    let number = cursor_expect!(cursor, "number_literal");
+                    precedence = source[number.start_byte()..number.end_byte()]
+                        .parse()
+                        .map_err(|_| AstError::ConversionError)?;
+                    cursor.goto_parent();
+                }
+                "infix_associativity" => {
+                    cursor.child();
+                    cursor_expect!(cursor, "associativity_keyword");
+                    cursor.next();
+                    cursor_expect!(cursor, "colon");
+                    cursor.next();
+                    associativity = match cursor.kind()? {
+                        "left_keyword" => Associativity::Left,
+                        "right_keyword" => Associativity::Right,
+                        unknown => return Err(AstError::UnexpectedNode(unknown.to_owned())),
+                    };
+                    cursor.goto_parent();
+                }
+                _ => {}
+            }
+        }
+
+        cursor.goto_parent();
+
+        Ok(InfixDecl {
+            visibility,
+            operator,
+            function,
+            precedence,
+            associativity,
+            span,
+        })
+    }
+}
+
 impl ReadCursor for TypeDecl {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
         let _ty = cursor_expect!(cursor, "type_declaration");
@@ -96,7 +203,7 @@ impl ReadCursor for TypeDecl {
             "enum" => EnumTypeDecl::read_cursor(cursor, source)?.into(),
             "tuple_struct" => TupleTypeDecl::read_cursor(cursor, source)?.into(),
             "empty_struct" => EmptyTypeDecl::read_cursor(cursor, source)?.into(),
-            _ => unreachable!(),
+            unknown => return Err(AstError::UnexpectedNode(unknown.to_owned())),
         };
 
         cursor.goto_parent();
@@ -1,7 +1,7 @@
 use galvan_ast::{
     ArrayTypeItem, BasicTypeItem, ClosureTypeItem, DictionaryTypeItem, GenericTypeItem, Ident,
     OptionalTypeItem, OrderedDictionaryTypeItem, ParametricTypeItem, ResultTypeItem, SetTypeItem,
-    Span, TupleTypeItem, TypeElement, TypeIdent,
+    Span, TupleTypeItem, TypeElement, TypeIdent, UnionTypeItem,
 };
 use galvan_parse::TreeCursor;
 
@@ -37,10 +37,9 @@ impl ReadCursor for TypeElement {
             "closure_type" => {
                 TypeElement::Closure(ClosureTypeItem::read_cursor(cursor, source)?.into())
             }
+            "union_type" => TypeElement::Union(UnionTypeItem::read_cursor(cursor, source)?.into()),
             "basic_type" => TypeElement::Plain(BasicTypeItem::read_cursor(cursor, source)?),
-            unknown => {
-                unimplemented!("Encountered type element not known to AST converstion: {unknown}")
-            }
+            unknown => return Err(AstError::UnexpectedNode(unknown.to_owned())),
         };
 
         // TODO  Verify that there is no other child node
@@ -194,8 +193,27 @@ impl ReadCursor for SetTypeItem {
 }
 
 impl ReadCursor for TupleTypeItem {
-    fn read_cursor(_cursor: &mut TreeCursor<'_>, _source: &str) -> Result<Self, AstError> {
-        todo!()
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let tuple = cursor_expect!(cursor, "tuple_type");
+        let span = Span::from_node(tuple);
+
+        cursor.child();
+        let _paren = cursor_expect!(cursor, "paren_open");
+
+        cursor.next();
+        let mut elements = Vec::new();
+        while cursor.kind()? != "paren_close" {
+            if cursor.kind()? == "type_item" {
+                elements.push(TypeElement::read_cursor(cursor, source)?);
+            }
+            if !cursor.next() {
+                return Err(AstError::UnexpectedNode("tuple_type".to_owned()));
+            }
+        }
+
+        cursor.goto_parent();
+
+        Ok(Self { elements, span })
     }
 }
 
@@ -288,6 +306,30 @@ impl ReadCursor for ClosureTypeItem {
     }
 }
 
+impl ReadCursor for UnionTypeItem {
+    fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
+        let type_item = cursor_expect!(cursor, "union_type");
+        let span = Span::from_node(type_item);
+
+        cursor.child();
+        let mut members = Vec::new();
+        loop {
+            if cursor.kind()? == "pipe" {
+                cursor.goto_next_sibling();
+                continue;
+            }
+            members.push(TypeElement::read_cursor(cursor, source)?);
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+
+        cursor.goto_parent();
+
+        Ok(Self { members, span })
+    }
+}
+
 impl ReadCursor for BasicTypeItem {
     fn read_cursor(cursor: &mut TreeCursor<'_>, source: &str) -> Result<Self, AstError> {
         let type_item = cursor_expect!(cursor, "basic_type");
@@ -1,6 +1,6 @@
 use galvan_ast::{
-    Ast, FnDecl, MainDecl, MainKind, Point, RootItem, SegmentedAsts, Span, ToplevelItem,
-    TypeElement, VisibilityKind,
+    Ast, FnDecl, MainDecl, MainKind, Point, RootItem, SegmentedAsts, Span, SyntaxError,
+    ToplevelItem, TypeElement, VisibilityKind,
 };
 use galvan_files::Source;
 use galvan_parse::*;
@@ -8,8 +8,10 @@ use galvan_parse::*;
 mod cursor_macro;
 mod items;
 mod modifiers;
+mod recovery;
 mod result;
 
+use recovery::{collect_syntax_errors, ROOT_ITEM_KINDS};
 use result::CursorUtil;
 pub use result::{AstError, AstResult};
 
@@ -40,17 +42,15 @@ impl IntoAst for ParseTree {
         let mut ast = Ast {
             toplevel: vec![],
             source,
+            syntax_errors: vec![],
         };
 
         if cursor.child() {
-            let item = RootItem::read_cursor(&mut cursor, &ast.source)?;
-
-            ast.toplevel.push(item);
-
-            while cursor.next() {
-                let item = RootItem::read_cursor(&mut cursor, &ast.source)?;
-
-                ast.toplevel.push(item);
+            loop {
+                read_root_item(&mut cursor, &mut ast)?;
+                if !cursor.next() {
+                    break;
+                }
             }
         }
 
@@ -58,6 +58,28 @@ impl IntoAst for ParseTree {
     }
 }
 
+/// Reads the toplevel item at the cursor into the AST. Items that contain
+/// syntax errors are skipped and their errors collected instead.
+fn read_root_item(cursor: &mut TreeCursor<'_>, ast: &mut Ast) -> Result<(), AstError> {
+    let node = cursor.node();
+    if node.has_error() {
+        collect_syntax_errors(node, &ast.source, &mut ast.syntax_errors);
+        return Ok(());
+    }
+
+    match RootItem::read_cursor(cursor, &ast.source) {
+        Ok(item) => ast.toplevel.push(item),
+        Err(AstError::UnexpectedNode(kind)) => ast.syntax_errors.push(SyntaxError {
+            source: ast.source.clone(),
+            span: Span::from_node(node),
+            found: Some(kind),
+            expected: ROOT_ITEM_KINDS.map(str::to_owned).to_vec(),
+        }),
+        Err(error) => return Err(error),
+    }
+    Ok(())
+}
+
 pub trait SegmentAst {
     fn segmented(self) -> Result<SegmentedAsts, AstError>;
 }
@@ -66,6 +88,8 @@ impl SegmentAst for Ast {
     fn segmented(self) -> Result<SegmentedAsts, AstError> {
         let mut uses = Vec::new();
         let mut types = Vec::new();
+        let mut traits = Vec::new();
+        let mut infixes = Vec::new();
         let mut functions = Vec::new();
         let mut tests = Vec::new();
         let mut cmds = Vec::new();
@@ -81,6 +105,14 @@ impl SegmentAst for Ast {
                     item,
                     source: self.source.clone(),
                 }),
+                RootItem::Trait(item) => traits.push(ToplevelItem {
+                    item,
+                    source: self.source.clone(),
+                }),
+                RootItem::Infix(item) => infixes.push(ToplevelItem {
+                    item,
+                    source: self.source.clone(),
+                }),
                 RootItem::Fn(item) if item.signature.identifier.as_str() == "main" => {
                     if main.is_some() {
                         return Err(AstError::DuplicateMain);
@@ -123,10 +155,13 @@ impl SegmentAst for Ast {
         Ok(SegmentedAsts {
             uses,
             types,
+            traits,
+            infixes,
             functions,
             tests,
             main,
             cmds,
+            syntax_errors: self.syntax_errors,
         })
     }
 }
@@ -173,9 +208,12 @@ impl SegmentAst for Vec<Ast> {
     fn segmented(self) -> Result<SegmentedAsts, AstError> {
         let mut uses = Vec::new();
         let mut types = Vec::new();
+        let mut traits = Vec::new();
+        let mut infixes = Vec::new();
         let mut functions = Vec::new();
         let mut tests = Vec::new();
         let mut cmds = Vec::new();
+        let mut syntax_errors = Vec::new();
         let mut main = None;
         let segmented = self.into_iter().map(SegmentAst::segmented);
 
@@ -183,9 +221,12 @@ impl SegmentAst for Vec<Ast> {
             let ast = ast?;
             uses.extend(ast.uses);
             types.extend(ast.types);
+            traits.extend(ast.traits);
+            infixes.extend(ast.infixes);
             functions.extend(ast.functions);
             tests.extend(ast.tests);
             cmds.extend(ast.cmds);
+            syntax_errors.extend(ast.syntax_errors);
             if let Some(main_decl) = ast.main {
                 if main.is_some() {
                     return Err(AstError::DuplicateMain);
@@ -198,10 +239,13 @@ impl SegmentAst for Vec<Ast> {
         Ok(SegmentedAsts {
             uses,
             types,
+            traits,
+            infixes,
             functions,
             tests,
             main,
             cmds,
+            syntax_errors,
         })
     }
 }
@@ -0,0 +1,84 @@
+//! Recovery from syntax errors: toplevel items that contain syntax errors
+//! are skipped, and every error in them is collected with the kinds of nodes
+//! the parser expected instead
+
+use galvan_ast::{Span, SyntaxError};
+use galvan_files::Source;
+use galvan_parse::Node;
+
+use crate::SpanExt;
+
+/// The kinds of nodes that can appear at the root of a source file
+pub(crate) const ROOT_ITEM_KINDS: [&str; 7] = [
+    "cmd",
+    "function",
+    "infix_declaration",
+    "test",
+    "trait_declaration",
+    "type_declaration",
+    "use_declaration",
+];
+
+/// Collects the syntax errors in the subtree of `node`
+pub(crate) fn collect_syntax_errors(
+    node: Node<'_>,
+    source: &Source,
+    errors: &mut Vec<SyntaxError>,
+) {
+    if node.is_missing() {
+        errors.push(SyntaxError {
+            source: source.clone(),
+            span: Span::from_node(node),
+            found: None,
+            expected: vec![node.kind().to_owned()],
+        });
+    } else if node.is_error() {
+        // Nested errors are part of the same mistake
+        let unexpected = first_leaf(node);
+        errors.push(SyntaxError {
+            source: source.clone(),
+            span: Span::from_node(unexpected),
+            found: unexpected
+                .utf8_text(source.content().as_bytes())
+                .ok()
+                .map(str::to_owned),
+            expected: expected_kinds(node),
+        });
+    } else if node.has_error() {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            collect_syntax_errors(child, source, errors);
+        }
+    }
+}
+
+fn first_leaf(node: Node<'_>) -> Node<'_> {
+    let mut leaf = node;
+    while let Some(child) = leaf.child(0) {
+        leaf = child;
+    }
+    leaf
+}
+
+/// The kinds of visible nodes the parser would have accepted where the error
+/// node starts
+fn expected_kinds(error: Node<'_>) -> Vec<String> {
+    let state = match error.prev_sibling() {
+        Some(previous) => previous.next_parse_state(),
+        None => error.parse_state(),
+    };
+    let language = error.language();
+    let Some(lookahead) = language.lookahead_iterator(state) else {
+        return Vec::new();
+    };
+
+    let mut kinds = lookahead
+        .filter(|&symbol| language.node_kind_is_visible(symbol))
+        .filter_map(|symbol| language.node_kind_for_id(symbol))
+        .filter(|kind| !kind.is_empty() && *kind != "ERROR" && *kind != "comment")
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    kinds.sort();
+    kinds.dedup();
+    kinds
+}
@@ -13,6 +13,8 @@ pub enum AstError {
     ConversionError,
     #[error("Error when parsing item")]
     NodeError,
+    #[error("Unexpected `{0}` node")]
+    UnexpectedNode(String),
     #[error("Duplicate main function")]
     DuplicateMain,
     #[error("Main must have signature `fn main()` or `fn main(args: [String])`")]
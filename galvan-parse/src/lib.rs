@@ -23,11 +23,7 @@ pub fn parse_source(source: &Source) -> ParseResult {
     let content = source.content();
     parser.parse(content, None).ok_or_else(|| {
         let file = match source {
-            Source::File {
-                path,
-                content: _,
-                canonical_name: _,
-            } => path.to_string_lossy(),
+            Source::File { path, .. } => path.to_string_lossy(),
             Source::Str(_) => "{input string}".into(),
             Source::Missing => "{missing}".into(),
             Source::Builtin => "{builtin}".into(),
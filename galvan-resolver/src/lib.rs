@@ -1,5 +1,8 @@
 mod lookup;
+mod module;
+pub mod rustdoc;
 mod scope;
 
 pub use lookup::*;
+pub use module::*;
 pub use scope::*;
@@ -1,4 +1,9 @@
-use galvan_ast::{FnDecl, Ident, SegmentedAsts, ToplevelItem, TypeDecl, TypeElement, TypeIdent};
+use galvan_ast::{
+    CustomInfix, FnDecl, Ident, InfixDecl, InfixOperator, RootItemMarker, SegmentedAsts, Span,
+    ToplevelItem, TraitDecl, TypeDecl, TypeElement, TypeIdent,
+};
+use galvan_files::Source;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -6,38 +11,121 @@ use thiserror::Error;
 pub struct LookupContext<'a> {
     /// Types are resolved by their name
     pub types: HashMap<TypeIdent, &'a ToplevelItem<TypeDecl>>,
+    /// Traits share the namespace of types
+    pub traits: HashMap<TypeIdent, &'a ToplevelItem<TraitDecl>>,
     /// Functions are resolved by their name and - if present - named arguments and their receiver type
     ///
     /// `fn foo(a: i32, b: i32) -> i32` is identified as `foo`
     /// `fn foo(bar a: i32, b: i32) -> i32` is identified as `foo:bar`
     /// `fn foo(self: i32, b: i32) -> i32` is identified as `i32::foo`
     pub functions: HashMap<FunctionId, &'a ToplevelItem<FnDecl>>,
-    // TODO: Nested contexts for resolving names from imported modules
-    // pub imports: HashMap<String, LookupContext<'a>>,
+    /// Custom infix operators are resolved by their symbol
+    pub infixes: HashMap<CustomInfix, &'a ToplevelItem<InfixDecl>>,
 }
 
 pub trait Lookup {
     fn resolve_type(&self, name: &TypeIdent) -> Option<&ToplevelItem<TypeDecl>>;
 
+    fn resolve_trait(&self, name: &TypeIdent) -> Option<&ToplevelItem<TraitDecl>>;
+
     fn resolve_function(
         &self,
         receiver: Option<&TypeIdent>,
         name: &Ident,
         labels: &[&str],
     ) -> Option<&ToplevelItem<FnDecl>>;
+
+    fn resolve_infix(&self, operator: &CustomInfix) -> Option<&ToplevelItem<InfixDecl>>;
 }
 
-// TODO: Include spans in errors
 #[derive(Debug, Error)]
 pub enum LookupError {
     #[error("Type not found")]
     TypeNotFound,
     #[error("Function not found")]
     FunctionNotFound,
-    #[error("Duplicate type")]
-    DuplicateType(TypeIdent),
-    #[error("Duplicate function")]
-    DuplicateFunction,
+    #[error("Duplicate type `{ident}`")]
+    DuplicateType {
+        ident: TypeIdent,
+        original: Box<Declaration>,
+        duplicate: Box<Declaration>,
+    },
+    #[error("Duplicate function `{ident}`")]
+    DuplicateFunction {
+        ident: Ident,
+        /// The overload labels both declarations share
+        labels: Vec<Ident>,
+        original: Box<Declaration>,
+        duplicate: Box<Declaration>,
+    },
+    #[error("Duplicate infix operator `{}`", .operator.symbol())]
+    DuplicateInfix {
+        operator: CustomInfix,
+        original: Box<Declaration>,
+        duplicate: Box<Declaration>,
+    },
+}
+
+impl LookupError {
+    /// The first declaration of a duplicate item and the declaration that
+    /// clashes with it
+    pub fn declarations(&self) -> Option<(&Declaration, &Declaration)> {
+        match self {
+            LookupError::TypeNotFound | LookupError::FunctionNotFound => None,
+            LookupError::DuplicateType {
+                original,
+                duplicate,
+                ..
+            }
+            | LookupError::DuplicateFunction {
+                original,
+                duplicate,
+                ..
+            }
+            | LookupError::DuplicateInfix {
+                original,
+                duplicate,
+                ..
+            } => Some((original.as_ref(), duplicate.as_ref())),
+        }
+    }
+
+    /// Explains how to resolve the error
+    pub fn help(&self) -> Option<String> {
+        let LookupError::DuplicateFunction { ident, labels, .. } = self else {
+            return None;
+        };
+        let labels = if labels.is_empty() {
+            "no argument labels".to_owned()
+        } else {
+            let labels = labels
+                .iter()
+                .map(|label| format!("`{label}:`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("the argument labels {labels}")
+        };
+        Some(format!(
+            "overloads are told apart by their argument labels only, not by parameter names or types; both declarations of `{ident}` have {labels}"
+        ))
+    }
+}
+
+/// Where an item is declared
+#[derive(Clone, Debug)]
+pub struct Declaration {
+    pub source: Source,
+    pub span: Span,
+}
+
+impl Declaration {
+    /// Declarations are boxed to keep `LookupError` small
+    fn of<T: RootItemMarker>(item: &ToplevelItem<T>, span: Span) -> Box<Self> {
+        Box::new(Self {
+            source: item.source.clone(),
+            span,
+        })
+    }
 }
 
 impl<'a> LookupContext<'a> {
@@ -46,39 +134,179 @@ impl<'a> LookupContext<'a> {
     }
     pub fn add_from(&mut self, asts: &'a SegmentedAsts) -> Result<(), LookupError> {
         for func in &asts.functions {
-            let receiver = func.item.signature.parameters.params.first().and_then(|p| {
-                if p.identifier.is_self() {
-                    // TODO: We should allow implementing something on Vec, etc. as well
-                    match p.param_type {
-                        TypeElement::Plain(ref ty) => Some(&ty.ident),
-                        _ => None,
-                    }
-                } else {
-                    None
-                }
-            });
-            let labels = func.item.signature.overload_labels();
-            let labels = labels
-                .iter()
-                .map(|label| label.as_str())
-                .collect::<Vec<_>>();
-            let func_id = FunctionId::new(receiver, &func.signature.identifier, &labels);
-            if self.functions.insert(func_id, func).is_some() {
-                return Err(LookupError::DuplicateFunction);
-            }
+            self.add_function(func)?;
+        }
+        for type_decl in &asts.types {
+            self.add_type(type_decl)?;
+        }
+        for trait_decl in &asts.traits {
+            self.add_trait(trait_decl)?;
         }
+        for infix_decl in &asts.infixes {
+            self.add_infix(infix_decl)?;
+        }
+
+        Ok(())
+    }
 
+    /// Adds all items of `asts` that do not clash with an earlier item and
+    /// returns the clashes
+    pub fn add_each(&mut self, asts: &'a SegmentedAsts) -> Vec<LookupError> {
+        let mut clashes = Vec::new();
+        for func in &asts.functions {
+            clashes.extend(self.add_function(func).err());
+        }
         for type_decl in &asts.types {
-            if self
+            clashes.extend(self.add_type(type_decl).err());
+        }
+        for trait_decl in &asts.traits {
+            clashes.extend(self.add_trait(trait_decl).err());
+        }
+        for infix_decl in &asts.infixes {
+            clashes.extend(self.add_infix(infix_decl).err());
+        }
+        clashes
+    }
+
+    pub fn add_function(&mut self, func: &'a ToplevelItem<FnDecl>) -> Result<(), LookupError> {
+        match self.functions.entry(function_id(func)) {
+            Entry::Occupied(entry) => Err(LookupError::DuplicateFunction {
+                ident: func.signature.identifier.clone(),
+                labels: func
+                    .signature
+                    .overload_labels()
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+                original: Declaration::of(entry.get(), entry.get().signature.span),
+                duplicate: Declaration::of(func, func.signature.span),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(func);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn add_type(&mut self, type_decl: &'a ToplevelItem<TypeDecl>) -> Result<(), LookupError> {
+        let ident = type_decl.ident();
+        let original = match (self.types.get(ident), self.traits.get(ident)) {
+            (Some(original), _) => Declaration::of(original, original.span()),
+            (None, Some(original)) => Declaration::of(original, original.span),
+            (None, None) => {
+                self.types.insert(ident.clone(), type_decl);
+                return Ok(());
+            }
+        };
+        Err(LookupError::DuplicateType {
+            ident: ident.clone(),
+            original,
+            duplicate: Declaration::of(type_decl, type_decl.span()),
+        })
+    }
+
+    pub fn add_trait(
+        &mut self,
+        trait_decl: &'a ToplevelItem<TraitDecl>,
+    ) -> Result<(), LookupError> {
+        let ident = &trait_decl.item.ident;
+        let original = match (self.types.get(ident), self.traits.get(ident)) {
+            (Some(original), _) => Declaration::of(original, original.span()),
+            (None, Some(original)) => Declaration::of(original, original.span),
+            (None, None) => {
+                self.traits.insert(ident.clone(), trait_decl);
+                return Ok(());
+            }
+        };
+        Err(LookupError::DuplicateType {
+            ident: ident.clone(),
+            original,
+            duplicate: Declaration::of(trait_decl, trait_decl.span),
+        })
+    }
+
+    pub fn add_infix(
+        &mut self,
+        infix_decl: &'a ToplevelItem<InfixDecl>,
+    ) -> Result<(), LookupError> {
+        match self.infixes.entry(infix_decl.item.operator.clone()) {
+            Entry::Occupied(entry) => Err(LookupError::DuplicateInfix {
+                operator: infix_decl.item.operator.clone(),
+                original: Declaration::of(entry.get(), entry.get().span),
+                duplicate: Declaration::of(infix_decl, infix_decl.span),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(infix_decl);
+                Ok(())
+            }
+        }
+    }
+
+    /// Adds all items of `other` that do not clash with an item of a
+    /// different declaration and returns the clashes
+    pub fn add_all(&mut self, other: &LookupContext<'a>) -> Vec<LookupError> {
+        let mut clashes = Vec::new();
+        for (id, &func) in &other.functions {
+            if !self
+                .functions
+                .get(id)
+                .is_some_and(|&f| std::ptr::eq(f, func))
+            {
+                clashes.extend(self.add_function(func).err());
+            }
+        }
+        for (ident, &type_decl) in &other.types {
+            if !self
                 .types
-                .insert(type_decl.ident().clone(), type_decl)
-                .is_some()
+                .get(ident)
+                .is_some_and(|&t| std::ptr::eq(t, type_decl))
+            {
+                clashes.extend(self.add_type(type_decl).err());
+            }
+        }
+        for (ident, &trait_decl) in &other.traits {
+            if !self
+                .traits
+                .get(ident)
+                .is_some_and(|&t| std::ptr::eq(t, trait_decl))
             {
-                return Err(LookupError::DuplicateType(type_decl.ident().clone()));
+                clashes.extend(self.add_trait(trait_decl).err());
             }
         }
+        for (operator, &infix_decl) in &other.infixes {
+            if !self
+                .infixes
+                .get(operator)
+                .is_some_and(|&i| std::ptr::eq(i, infix_decl))
+            {
+                clashes.extend(self.add_infix(infix_decl).err());
+            }
+        }
+        clashes
+    }
 
-        Ok(())
+    /// Adds all items of `other`, replacing items of the same name
+    pub fn extend(&mut self, other: &LookupContext<'a>) {
+        self.types.extend(
+            other
+                .types
+                .iter()
+                .map(|(ident, decl)| (ident.clone(), *decl)),
+        );
+        self.traits.extend(
+            other
+                .traits
+                .iter()
+                .map(|(ident, decl)| (ident.clone(), *decl)),
+        );
+        self.functions
+            .extend(other.functions.iter().map(|(id, decl)| (id.clone(), *decl)));
+        self.infixes.extend(
+            other
+                .infixes
+                .iter()
+                .map(|(operator, decl)| (operator.clone(), *decl)),
+        );
     }
 
     pub fn with(mut self, asts: &'a SegmentedAsts) -> Result<Self, LookupError> {
@@ -92,6 +320,10 @@ impl Lookup for LookupContext<'_> {
         self.types.get(&name).copied()
     }
 
+    fn resolve_trait(&self, name: &TypeIdent) -> Option<&ToplevelItem<TraitDecl>> {
+        self.traits.get(name).copied()
+    }
+
     fn resolve_function(
         &self,
         receiver: Option<&TypeIdent>,
@@ -101,6 +333,31 @@ impl Lookup for LookupContext<'_> {
         let func_id = FunctionId::new(receiver, name, labels);
         self.functions.get(&func_id).copied()
     }
+
+    fn resolve_infix(&self, operator: &CustomInfix) -> Option<&ToplevelItem<InfixDecl>> {
+        self.infixes.get(operator).copied()
+    }
+}
+
+/// The id of a function: receiver type, name and argument labels
+fn function_id(func: &ToplevelItem<FnDecl>) -> FunctionId {
+    let receiver = func.item.signature.parameters.params.first().and_then(|p| {
+        if p.identifier.is_self() {
+            // TODO: We should allow implementing something on Vec, etc. as well
+            match p.param_type {
+                TypeElement::Plain(ref ty) => Some(&ty.ident),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    });
+    let labels = func.item.signature.overload_labels();
+    let labels = labels
+        .iter()
+        .map(|label| label.as_str())
+        .collect::<Vec<_>>();
+    FunctionId::new(receiver, &func.signature.identifier, &labels)
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
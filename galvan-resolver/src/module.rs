@@ -0,0 +1,402 @@
+//! Modules of a crate and the names that are visible in each of them.
+//!
+//! Every Galvan file forms a module (see [`ModulePath`]). Without
+//! qualification, a module sees
+//!
+//! - its own items,
+//! - items imported by its `use` declarations, either by name
+//!   (`use crate::orders::Order`) or all at once (`use crate::orders`),
+//! - items of other modules that are visible to it and whose name is not
+//!   declared by any other module of the crate,
+//! - items of Rust crates it `use`s, either all at once (`use geometry`) or
+//!   by name (`use geometry::Point`), and
+//! - builtin items.
+//!
+//! Items declared in the module itself take precedence over imported items,
+//! which take precedence over the rest. `pub` items are visible everywhere;
+//! items without a visibility modifier only in their own module and the
+//! modules nested in it.
+
+use std::collections::{BTreeSet, HashMap};
+
+use galvan_ast::{
+    Ident, SegmentedAsts, ToplevelItem, TypeElement, TypeIdent, UseDecl, UsePath, Visibility,
+    VisibilityKind,
+};
+use galvan_files::ModulePath;
+use thiserror::Error;
+
+use crate::rustdoc::RustdocCrate;
+use crate::{FunctionId, LookupContext, LookupError};
+
+/// Whether an item declared in `declared_in` can be used from `from`
+pub fn is_visible(visibility: &Visibility, declared_in: &ModulePath, from: &ModulePath) -> bool {
+    visibility.kind == VisibilityKind::Public || from.is_within(declared_in)
+}
+
+#[derive(Debug, Error)]
+pub enum PathError {
+    #[error("Unresolved path `{path}`")]
+    Unresolved { path: String },
+    #[error("`{item}` is private to module `{module}`")]
+    Private { item: String, module: ModulePath },
+}
+
+/// A `use` declaration that names a module of this crate but cannot be
+/// resolved
+#[derive(Debug)]
+pub struct ImportError<'a> {
+    pub decl: &'a ToplevelItem<UseDecl>,
+    pub error: PathError,
+}
+
+#[derive(Debug)]
+pub struct ModuleScopes<'a> {
+    /// The items declared in each module
+    declared: HashMap<ModulePath, LookupContext<'a>>,
+    /// The items visible without qualification in each module
+    scopes: HashMap<ModulePath, LookupContext<'a>>,
+    import_errors: Vec<ImportError<'a>>,
+    duplicates: Vec<LookupError>,
+}
+
+impl<'a> ModuleScopes<'a> {
+    /// Sorts the items of a crate into their modules. Items of `prelude` are
+    /// visible in every module.
+    ///
+    /// Items that clash with an earlier item of the same module are left out
+    /// and reported by [`Self::duplicates`].
+    pub fn new(prelude: LookupContext<'a>, asts: &'a SegmentedAsts) -> Self {
+        Self::with_crates(prelude, asts, &[])
+    }
+
+    /// Like [`Self::new`], with the items of Rust crates visible in the
+    /// modules that `use` them.
+    ///
+    /// Items that clash with an earlier item of the same crate, or with an
+    /// item of another crate used by the same module, are left out and
+    /// reported by [`Self::duplicates`] as well.
+    pub fn with_crates(
+        prelude: LookupContext<'a>,
+        asts: &'a SegmentedAsts,
+        crates: &'a [RustdocCrate],
+    ) -> Self {
+        let mut duplicates = Vec::new();
+        let mut declared: HashMap<ModulePath, LookupContext<'a>> = HashMap::new();
+        for type_decl in &asts.types {
+            let items = declared
+                .entry(type_decl.source.module().clone())
+                .or_default();
+            duplicates.extend(items.add_type(type_decl).err());
+        }
+        for trait_decl in &asts.traits {
+            let items = declared
+                .entry(trait_decl.source.module().clone())
+                .or_default();
+            duplicates.extend(items.add_trait(trait_decl).err());
+        }
+        for func in &asts.functions {
+            let items = declared.entry(func.source.module().clone()).or_default();
+            duplicates.extend(items.add_function(func).err());
+        }
+
+        let mut crate_items = HashMap::new();
+        for imported in crates {
+            let mut items = LookupContext::new();
+            duplicates.extend(items.add_each(&imported.items));
+            crate_items.insert(imported.name.as_str(), items);
+        }
+
+        // Infix operators cannot be named by paths and apply to the whole crate
+        let mut prelude = prelude;
+        for infix_decl in &asts.infixes {
+            duplicates.extend(prelude.add_infix(infix_decl).err());
+        }
+
+        let mut modules = BTreeSet::from([ModulePath::root()]);
+        let sources = asts
+            .uses
+            .iter()
+            .map(|item| &item.source)
+            .chain(asts.types.iter().map(|item| &item.source))
+            .chain(asts.traits.iter().map(|item| &item.source))
+            .chain(asts.functions.iter().map(|item| &item.source))
+            .chain(asts.tests.iter().map(|item| &item.source))
+            .chain(asts.cmds.iter().map(|item| &item.source))
+            .chain(asts.main.iter().map(|item| &item.source));
+        for source in sources {
+            let mut module = Some(source.module().clone());
+            // Directories form modules as well
+            while let Some(current) = module {
+                module = current.parent();
+                modules.insert(current);
+            }
+        }
+        for module in &modules {
+            declared.entry(module.clone()).or_default();
+        }
+
+        let mut scopes = Self {
+            declared,
+            scopes: HashMap::new(),
+            import_errors: Vec::new(),
+            duplicates,
+        };
+        let shared = scopes.shared_items();
+        for module in modules {
+            let mut scope = prelude.clone();
+            for (declared_in, items) in &shared {
+                scope.extend(&visible_items(items, declared_in, &module));
+            }
+            let mut from_crates = LookupContext::new();
+            let mut from_modules = Vec::new();
+            for use_decl in asts
+                .uses
+                .iter()
+                .filter(|decl| decl.source.module() == &module)
+            {
+                match scopes.resolve_import(&module, &use_decl.item.path) {
+                    Ok(Some(imported)) => from_modules.push(imported),
+                    Ok(None) => {
+                        if let Some(imported) = crate_import(&crate_items, &use_decl.item.path) {
+                            let clashes = from_crates.add_all(&imported);
+                            scopes.duplicates.extend(clashes);
+                        }
+                    }
+                    Err(error) => scopes.import_errors.push(ImportError {
+                        decl: use_decl,
+                        error,
+                    }),
+                }
+            }
+            scope.extend(&from_crates);
+            for imported in &from_modules {
+                scope.extend(imported);
+            }
+            scope.extend(&scopes.declared[&module]);
+            scopes.scopes.insert(module, scope);
+        }
+
+        scopes
+    }
+
+    /// The items visible without qualification in `module`
+    pub fn scope(&self, module: &ModulePath) -> &LookupContext<'a> {
+        self.scopes
+            .get(module)
+            .unwrap_or_else(|| &self.scopes[&ModulePath::root()])
+    }
+
+    /// The items declared in `module`, regardless of their visibility
+    pub fn declared(&self, module: &ModulePath) -> Option<&LookupContext<'a>> {
+        self.declared.get(module)
+    }
+
+    /// All modules of the crate, each preceded by its parent
+    pub fn modules(&self) -> Vec<ModulePath> {
+        let mut modules = self.scopes.keys().cloned().collect::<Vec<_>>();
+        modules.sort();
+        modules
+    }
+
+    pub fn import_errors(&self) -> &[ImportError<'a>] {
+        &self.import_errors
+    }
+
+    /// Items declared more than once in the same module
+    pub fn duplicates(&self) -> &[LookupError] {
+        &self.duplicates
+    }
+
+    /// Resolves a path naming a module of this crate, e.g. `crate::orders`,
+    /// `super::orders` or `orders`, as used from `from`. Returns `None` if
+    /// the path names no module, e.g. because it refers to another crate.
+    pub fn resolve_module(&self, from: &ModulePath, path: &UsePath) -> Option<ModulePath> {
+        self.resolve_module_segments(from, &path.segments)
+    }
+
+    /// The absolute form of a path into this crate, starting with `crate`.
+    /// Returns `None` for paths into other crates.
+    pub fn canonical_path(&self, from: &ModulePath, path: &UsePath) -> Option<UsePath> {
+        let (module, item) = match self.resolve_module(from, path) {
+            Some(module) => (module, None),
+            None => {
+                let (item, prefix) = path.segments.split_last()?;
+                (self.resolve_module_segments(from, prefix)?, Some(item))
+            }
+        };
+
+        let segments = std::iter::once("crate")
+            .chain(module.segments())
+            .map(Ident::new)
+            .chain(item.cloned())
+            .collect();
+        Some(UsePath {
+            segments,
+            span: path.span,
+        })
+    }
+
+    fn resolve_module_segments(&self, from: &ModulePath, segments: &[Ident]) -> Option<ModulePath> {
+        let (first, rest) = segments.split_first()?;
+        let mut module = match first.as_str() {
+            "crate" => ModulePath::root(),
+            "self" => from.clone(),
+            "super" => from.parent()?,
+            name => [from.child(name), ModulePath::root().child(name)]
+                .into_iter()
+                .find(|module| self.declared.contains_key(module))?,
+        };
+        for segment in rest {
+            module = match segment.as_str() {
+                "super" => module.parent()?,
+                name => module.child(name),
+            };
+            if !self.declared.contains_key(&module) {
+                return None;
+            }
+        }
+        Some(module)
+    }
+
+    /// The items imported into `from` by a `use` of `path`, or `None` if the
+    /// path refers to another crate
+    fn resolve_import(
+        &self,
+        from: &ModulePath,
+        path: &UsePath,
+    ) -> Result<Option<LookupContext<'a>>, PathError> {
+        if let Some(module) = self.resolve_module(from, path) {
+            return Ok(Some(visible_items(&self.declared[&module], &module, from)));
+        }
+
+        let unresolved = || PathError::Unresolved {
+            path: path_string(path),
+        };
+        let Some((item, prefix)) = path.segments.split_last() else {
+            return Ok(None);
+        };
+        let Some(module) = self.resolve_module_segments(from, prefix) else {
+            return match path.segments.first().map(Ident::as_str) {
+                Some("crate" | "self" | "super") => Err(unresolved()),
+                _ => Ok(None),
+            };
+        };
+
+        let declared = &self.declared[&module];
+        let mut named = LookupContext::new();
+        let type_ident = TypeIdent::new(item.as_str());
+        if let Some(&decl) = declared.types.get(&type_ident) {
+            named.types.insert(type_ident.clone(), decl);
+        }
+        if let Some(&decl) = declared.traits.get(&type_ident) {
+            named.traits.insert(type_ident, decl);
+        }
+        named.functions = declared
+            .functions
+            .iter()
+            .filter(|(_, decl)| decl.item.signature.identifier == *item)
+            .map(|(id, decl)| (id.clone(), *decl))
+            .collect();
+        if named.types.is_empty() && named.traits.is_empty() && named.functions.is_empty() {
+            return Err(unresolved());
+        }
+
+        let visible = visible_items(&named, &module, from);
+        if visible.types.is_empty() && visible.traits.is_empty() && visible.functions.is_empty() {
+            return Err(PathError::Private {
+                item: item.to_string(),
+                module,
+            });
+        }
+        Ok(Some(visible))
+    }
+
+    /// The items of each module whose name no other module declares
+    fn shared_items(&self) -> Vec<(ModulePath, LookupContext<'a>)> {
+        let mut type_counts: HashMap<&TypeIdent, usize> = HashMap::new();
+        let mut function_counts: HashMap<&FunctionId, usize> = HashMap::new();
+        for items in self.declared.values() {
+            for ident in items.types.keys().chain(items.traits.keys()) {
+                *type_counts.entry(ident).or_default() += 1;
+            }
+            for id in items.functions.keys() {
+                *function_counts.entry(id).or_default() += 1;
+            }
+        }
+
+        self.declared
+            .iter()
+            .map(|(module, items)| {
+                let mut shared = items.clone();
+                shared.types.retain(|ident, _| type_counts[ident] == 1);
+                shared.traits.retain(|ident, _| type_counts[ident] == 1);
+                shared.functions.retain(|id, _| function_counts[id] == 1);
+                (module.clone(), shared)
+            })
+            .collect()
+    }
+}
+
+/// The items of a Rust crate imported by a `use` of `path`: all of them for
+/// the crate itself, or a type with its member functions or the functions
+/// of a name, e.g. `geometry::Point`
+fn crate_import<'a>(
+    crates: &HashMap<&str, LookupContext<'a>>,
+    path: &UsePath,
+) -> Option<LookupContext<'a>> {
+    let (first, rest) = path.segments.split_first()?;
+    let items = crates.get(first.as_str())?;
+    let [item] = rest else {
+        return rest.is_empty().then(|| items.clone());
+    };
+
+    let type_ident = TypeIdent::new(item.as_str());
+    let mut named = LookupContext::new();
+    if let Some(&decl) = items.types.get(&type_ident) {
+        named.types.insert(type_ident.clone(), decl);
+    }
+    if let Some(&decl) = items.traits.get(&type_ident) {
+        named.traits.insert(type_ident.clone(), decl);
+    }
+    named.functions = items
+        .functions
+        .iter()
+        .filter(|(_, decl)| {
+            decl.item.signature.identifier == *item
+                || decl.item.signature.parameters.params.first().is_some_and(|param| {
+                    param.identifier.is_self()
+                        && matches!(&param.param_type, TypeElement::Plain(ty) if ty.ident == type_ident)
+                })
+        })
+        .map(|(id, decl)| (id.clone(), *decl))
+        .collect();
+    Some(named)
+}
+
+/// The items of a module declared in `declared_in` that `from` can see
+fn visible_items<'a>(
+    items: &LookupContext<'a>,
+    declared_in: &ModulePath,
+    from: &ModulePath,
+) -> LookupContext<'a> {
+    let mut visible = items.clone();
+    visible
+        .types
+        .retain(|_, decl| is_visible(decl.item.visibility(), declared_in, from));
+    visible
+        .traits
+        .retain(|_, decl| is_visible(&decl.item.visibility, declared_in, from));
+    visible
+        .functions
+        .retain(|_, decl| is_visible(&decl.item.signature.visibility, declared_in, from));
+    visible
+}
+
+fn path_string(path: &UsePath) -> String {
+    path.segments
+        .iter()
+        .map(Ident::as_str)
+        .collect::<Vec<_>>()
+        .join("::")
+}
@@ -0,0 +1,640 @@
+//! Imports the public API of a Rust crate from its rustdoc JSON, as produced
+//! by `cargo +nightly rustdoc -- -Z unstable-options --output-format json`.
+//!
+//! Public functions, structs and enums at the crate root and the inherent
+//! member functions of those types are mapped to Galvan declarations, so that
+//! they can be added to a [`LookupContext`](crate::LookupContext) like items
+//! declared in Galvan. Items whose signatures have no Galvan equivalent are
+//! left out and listed as [`SkippedItem`]s.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+use galvan_ast::{
+    ArrayTypeItem, BasicTypeItem, DeclModifier, DictionaryTypeItem, EmptyTypeDecl, EnumTypeDecl,
+    EnumTypeMember, EnumVariantField, FnDecl, FnSignature, Ident, OptionalTypeItem,
+    OrderedDictionaryTypeItem, Param, ParamList, ResultTypeItem, SegmentedAsts, SetTypeItem, Span,
+    StructTypeDecl, StructTypeMember, ToplevelItem, TupleTypeDecl, TupleTypeItem, TupleTypeMember,
+    TypeDecl, TypeElement, TypeIdent, Visibility,
+};
+use galvan_files::Source;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RustdocError {
+    #[error("Could not read rustdoc JSON: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid rustdoc JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Unsupported rustdoc JSON: {0}")]
+    Format(String),
+}
+
+/// The part of a Rust crate's public API that can be used from Galvan
+#[derive(Debug)]
+pub struct RustdocCrate {
+    pub name: String,
+    pub items: SegmentedAsts,
+    pub skipped: Vec<SkippedItem>,
+}
+
+/// A public item that was not imported because Galvan cannot express it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedItem {
+    pub path: String,
+    pub reason: String,
+}
+
+impl fmt::Display for SkippedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Skipped `{}`: {}", self.path, self.reason)
+    }
+}
+
+/// Reads the rustdoc JSON file of a crate
+pub fn read_rustdoc(path: impl AsRef<Path>) -> Result<RustdocCrate, RustdocError> {
+    let json = std::fs::read_to_string(path)?;
+    import_rustdoc(&json)
+}
+
+/// Maps the public items at the root of a crate to Galvan declarations
+pub fn import_rustdoc(json: &str) -> Result<RustdocCrate, RustdocError> {
+    let doc: Value = serde_json::from_str(json)?;
+    let index = doc
+        .get("index")
+        .and_then(Value::as_object)
+        .ok_or_else(|| RustdocError::Format("missing item index".to_string()))?;
+    let root = doc
+        .get("root")
+        .and_then(|root| index.get(&id_key(root)))
+        .ok_or_else(|| RustdocError::Format("missing root module".to_string()))?;
+    let name = root
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RustdocError::Format("root module has no name".to_string()))?;
+    let root_items = root
+        .pointer("/inner/module/items")
+        .and_then(Value::as_array)
+        .ok_or_else(|| RustdocError::Format("root item is not a module".to_string()))?;
+
+    let items = root_items
+        .iter()
+        .filter_map(|id| index.get(&id_key(id)))
+        .filter(|item| item.get("visibility").and_then(Value::as_str) == Some("public"))
+        .collect::<Vec<_>>();
+
+    // Types can only be referred to once they are known to be importable
+    let type_names = items
+        .iter()
+        .filter(|item| match item_kind(item) {
+            Some(("struct" | "enum", inner)) => !has_type_params(inner),
+            _ => false,
+        })
+        .filter_map(|item| item_name(item))
+        .map(str::to_owned)
+        .collect();
+
+    let mut importer = Importer {
+        crate_name: name,
+        index,
+        type_names,
+        types: Vec::new(),
+        functions: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for item in items {
+        importer.import_item(item);
+    }
+
+    Ok(RustdocCrate {
+        name: name.to_owned(),
+        items: SegmentedAsts {
+            uses: vec![],
+            types: importer.types,
+            traits: vec![],
+            infixes: vec![],
+            functions: importer.functions,
+            tests: vec![],
+            main: None,
+            cmds: vec![],
+            syntax_errors: vec![],
+        },
+        skipped: importer.skipped,
+    })
+}
+
+/// Item ids are strings in older format versions and integers in newer ones
+fn id_key(id: &Value) -> String {
+    match id {
+        Value::String(id) => id.clone(),
+        id => id.to_string(),
+    }
+}
+
+fn item_name(item: &Value) -> Option<&str> {
+    item.get("name").and_then(Value::as_str)
+}
+
+/// The kind of an item and its kind-specific data, e.g. `("function", {...})`
+fn item_kind(item: &Value) -> Option<(&str, &Value)> {
+    let (kind, inner) = item.get("inner")?.as_object()?.iter().next()?;
+    Some((kind.as_str(), inner))
+}
+
+/// Whether the generics of an item declare type or const parameters.
+/// Lifetime parameters are irrelevant to Galvan.
+fn has_type_params(inner: &Value) -> bool {
+    inner
+        .pointer("/generics/params")
+        .and_then(Value::as_array)
+        .is_some_and(|params| {
+            params
+                .iter()
+                .any(|param| param.pointer("/kind/lifetime").is_none())
+        })
+}
+
+fn plain(name: &str) -> TypeElement {
+    TypeElement::Plain(BasicTypeItem {
+        ident: TypeIdent::new(name),
+        span: Span::default(),
+    })
+}
+
+fn primitive_type(name: &str) -> Option<TypeElement> {
+    let galvan = match name {
+        "bool" => "Bool",
+        "char" => "Char",
+        "i8" => "I8",
+        "i16" => "I16",
+        "i32" => "I32",
+        "i64" => "Int",
+        "i128" => "I128",
+        "isize" => "ISize",
+        "u8" => "U8",
+        "u16" => "U16",
+        "u32" => "U32",
+        "u64" => "UInt",
+        "u128" => "U128",
+        "usize" => "USize",
+        "f32" => "Float",
+        "f64" => "Double",
+        _ => return None,
+    };
+    Some(plain(galvan))
+}
+
+struct Importer<'j> {
+    crate_name: &'j str,
+    index: &'j Map<String, Value>,
+    /// Types at the crate root that signatures may refer to
+    type_names: HashSet<String>,
+    types: Vec<ToplevelItem<TypeDecl>>,
+    functions: Vec<ToplevelItem<FnDecl>>,
+    skipped: Vec<SkippedItem>,
+}
+
+impl<'j> Importer<'j> {
+    fn skip(&mut self, path: String, reason: impl Into<String>) {
+        self.skipped.push(SkippedItem {
+            path,
+            reason: reason.into(),
+        });
+    }
+
+    fn path(&self, name: &str) -> String {
+        format!("{}::{name}", self.crate_name)
+    }
+
+    fn resolve(&self, id: &Value) -> Option<&'j Value> {
+        self.index.get(&id_key(id))
+    }
+
+    fn import_item(&mut self, item: &'j Value) {
+        let (Some(name), Some((kind, inner))) = (item_name(item), item_kind(item)) else {
+            return;
+        };
+        let path = self.path(name);
+
+        let imported = match kind {
+            "function" => self.function(name, inner, None).map(|decl| {
+                self.functions.push(ToplevelItem {
+                    item: decl,
+                    source: Source::Missing,
+                })
+            }),
+            "struct" | "enum" => self.type_decl(name, kind, inner).map(|decl| {
+                self.types.push(ToplevelItem {
+                    item: decl,
+                    source: Source::Missing,
+                });
+                self.import_methods(name, inner);
+            }),
+            "use" => Err("re-exports are not supported".to_string()),
+            kind => Err(format!("{kind} items are not supported")),
+        };
+        if let Err(reason) = imported {
+            self.skip(path, reason);
+        }
+    }
+
+    fn type_decl(&self, name: &str, kind: &str, inner: &Value) -> Result<TypeDecl, String> {
+        if has_type_params(inner) {
+            return Err("generic types are not supported".to_string());
+        }
+        let ident = TypeIdent::new(name);
+        match kind {
+            "struct" => self.struct_decl(ident, inner),
+            _ => self.enum_decl(ident, inner),
+        }
+    }
+
+    fn struct_decl(&self, ident: TypeIdent, inner: &Value) -> Result<TypeDecl, String> {
+        let kind = inner.get("kind").ok_or("struct has no kind")?;
+        if kind.as_str() == Some("unit") {
+            return Ok(TypeDecl::Empty(EmptyTypeDecl {
+                visibility: Visibility::public(),
+                ident,
+                conformances: vec![],
+                span: Span::default(),
+            }));
+        }
+
+        if let Some(fields) = kind.get("tuple").and_then(Value::as_array) {
+            // Private tuple fields are listed as `null`
+            let members = fields
+                .iter()
+                .map(|field| {
+                    let field = self
+                        .resolve(field)
+                        .ok_or("tuple struct has private fields")?;
+                    Ok(TupleTypeMember {
+                        r#type: self.field_type(field)?,
+                        span: Span::default(),
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            return Ok(TypeDecl::Tuple(TupleTypeDecl {
+                visibility: Visibility::public(),
+                ident,
+                conformances: vec![],
+                members,
+                span: Span::default(),
+            }));
+        }
+
+        let fields = kind
+            .pointer("/plain/fields")
+            .and_then(Value::as_array)
+            .ok_or("unsupported struct kind")?;
+        let members = fields
+            .iter()
+            .filter_map(|field| self.resolve(field))
+            .map(|field| {
+                Ok(StructTypeMember {
+                    decl_modifier: None,
+                    ident: Ident::new(item_name(field).ok_or("struct field has no name")?),
+                    r#type: self.field_type(field)?,
+                    default_value: None,
+                    span: Span::default(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(TypeDecl::Struct(StructTypeDecl {
+            visibility: Visibility::public(),
+            ident,
+            conformances: vec![],
+            members,
+            span: Span::default(),
+        }))
+    }
+
+    fn enum_decl(&self, ident: TypeIdent, inner: &Value) -> Result<TypeDecl, String> {
+        let variants = inner
+            .get("variants")
+            .and_then(Value::as_array)
+            .ok_or("enum has no variants")?;
+        let members = variants
+            .iter()
+            .filter_map(|variant| self.resolve(variant))
+            .map(|variant| self.enum_variant(variant))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(TypeDecl::Enum(EnumTypeDecl {
+            visibility: Visibility::public(),
+            ident,
+            conformances: vec![],
+            members,
+            span: Span::default(),
+        }))
+    }
+
+    fn enum_variant(&self, variant: &Value) -> Result<EnumTypeMember, String> {
+        let name = item_name(variant).ok_or("enum variant has no name")?;
+        let kind = variant
+            .pointer("/inner/variant/kind")
+            .ok_or("unsupported enum variant")?;
+
+        let fields = if kind.as_str() == Some("plain") {
+            vec![]
+        } else if let Some(fields) = kind.get("tuple").and_then(Value::as_array) {
+            fields
+                .iter()
+                .map(|field| {
+                    let field = self
+                        .resolve(field)
+                        .ok_or("enum variant has private fields")?;
+                    Ok(EnumVariantField {
+                        name: None,
+                        r#type: self.field_type(field)?,
+                        span: Span::default(),
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?
+        } else if let Some(fields) = kind.pointer("/struct/fields").and_then(Value::as_array) {
+            fields
+                .iter()
+                .filter_map(|field| self.resolve(field))
+                .map(|field| {
+                    Ok(EnumVariantField {
+                        name: item_name(field).map(Ident::new),
+                        r#type: self.field_type(field)?,
+                        span: Span::default(),
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?
+        } else {
+            return Err(format!("unsupported kind of enum variant `{name}`"));
+        };
+
+        Ok(EnumTypeMember {
+            ident: TypeIdent::new(name),
+            fields,
+            span: Span::default(),
+        })
+    }
+
+    fn field_type(&self, field: &Value) -> Result<TypeElement, String> {
+        let ty = field
+            .pointer("/inner/struct_field")
+            .ok_or("field has no type")?;
+        self.owned_type(ty)
+    }
+
+    /// Imports the inherent member functions of a type. Trait implementations
+    /// are not imported.
+    fn import_methods(&mut self, type_name: &str, inner: &Value) {
+        let impls = inner
+            .get("impls")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let impl_items = impls
+            .iter()
+            .filter_map(|id| self.resolve(id))
+            .collect::<Vec<_>>();
+        for impl_item in impl_items {
+            let Some(("impl", impl_inner)) = item_kind(impl_item) else {
+                continue;
+            };
+            if !impl_inner.get("trait").is_some_and(Value::is_null) {
+                continue;
+            }
+            let members = impl_inner
+                .get("items")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let members = members
+                .iter()
+                .filter_map(|id| self.resolve(id))
+                .collect::<Vec<_>>();
+            for member in members {
+                if member.get("visibility").and_then(Value::as_str) != Some("public") {
+                    continue;
+                }
+                let (Some(name), Some(("function", function))) =
+                    (item_name(member), item_kind(member))
+                else {
+                    continue;
+                };
+                let path = self.path(&format!("{type_name}::{name}"));
+                match self.function(name, function, Some(type_name)) {
+                    Ok(decl) => self.functions.push(ToplevelItem {
+                        item: decl,
+                        source: Source::Missing,
+                    }),
+                    Err(reason) => self.skip(path, reason),
+                }
+            }
+        }
+    }
+
+    /// Maps a function signature. Member functions of `receiver` must take
+    /// `self` by reference.
+    fn function(
+        &self,
+        name: &str,
+        function: &Value,
+        receiver: Option<&str>,
+    ) -> Result<FnDecl, String> {
+        if has_type_params(function) {
+            return Err("generic functions are not supported".to_string());
+        }
+        // Format versions before 0.27 call the signature `decl`
+        let sig = function
+            .get("sig")
+            .or_else(|| function.get("decl"))
+            .ok_or("function has no signature")?;
+        let inputs = sig
+            .get("inputs")
+            .and_then(Value::as_array)
+            .ok_or("function has no parameters")?;
+
+        let mut params = Vec::new();
+        for input in inputs {
+            let [param_name, ty] = input.as_array().map(Vec::as_slice).unwrap_or_default() else {
+                return Err("malformed parameter".to_string());
+            };
+            let param_name = param_name.as_str().unwrap_or("_");
+            let (param_type, decl_modifier) = match (param_name, receiver) {
+                ("self", Some(receiver)) => (plain(receiver), self.receiver_modifier(ty)?),
+                _ => self.param_type(ty)?,
+            };
+            params.push(Param {
+                decl_modifier,
+                short_name: None,
+                identifier: Ident::new(param_name),
+                param_type,
+                span: Span::default(),
+            });
+        }
+        if receiver.is_some()
+            && !params
+                .first()
+                .is_some_and(|param| param.identifier.is_self())
+        {
+            return Err("associated functions without `self` are not supported".to_string());
+        }
+
+        let return_type = match sig.get("output") {
+            None | Some(Value::Null) => TypeElement::void(),
+            Some(output) => self
+                .owned_type(output)
+                .map_err(|reason| format!("return type: {reason}"))?,
+        };
+
+        Ok(FnSignature {
+            visibility: Visibility::public(),
+            identifier: Ident::new(name),
+            parameters: ParamList {
+                params,
+                span: Span::default(),
+            },
+            return_type,
+            where_clause: None,
+            span: Span::default(),
+        }
+        .into())
+    }
+
+    /// `&self` is an unmodified receiver and `&mut self` a `mut self` one
+    fn receiver_modifier(&self, ty: &Value) -> Result<Option<DeclModifier>, String> {
+        let reference = ty
+            .get("borrowed_ref")
+            .ok_or("member functions taking `self` by value are not supported")?;
+        Ok(is_mutable(reference).then_some(DeclModifier::Mut))
+    }
+
+    /// Galvan passes `Copy` arguments by value and all others by reference,
+    /// so only parameters following that convention can be mapped
+    fn param_type(&self, ty: &Value) -> Result<(TypeElement, Option<DeclModifier>), String> {
+        let Some(reference) = ty.get("borrowed_ref") else {
+            let mapped = self.owned_type(ty)?;
+            return match ty.get("primitive") {
+                Some(_) => Ok((mapped, None)),
+                None => Err(format!(
+                    "parameters taking an owned {mapped} are not supported"
+                )),
+            };
+        };
+
+        let referent = reference.get("type").ok_or("reference has no type")?;
+        if referent.get("primitive").and_then(Value::as_str) == Some("str") {
+            if is_mutable(reference) {
+                return Err("`&mut str` parameters are not supported".to_string());
+            }
+            return Ok((plain("String"), None));
+        }
+        if referent.get("primitive").is_some() && !is_mutable(reference) {
+            return Err("references to primitive values are not supported".to_string());
+        }
+
+        let mapped = match referent.get("slice") {
+            Some(element) => TypeElement::Array(Box::new(ArrayTypeItem {
+                elements: self.owned_type(element)?,
+                span: Span::default(),
+            })),
+            None => self.owned_type(referent)?,
+        };
+        Ok((mapped, is_mutable(reference).then_some(DeclModifier::Mut)))
+    }
+
+    /// Maps a type that is stored or returned by value
+    fn owned_type(&self, ty: &Value) -> Result<TypeElement, String> {
+        if let Some(name) = ty.get("primitive").and_then(Value::as_str) {
+            return primitive_type(name)
+                .ok_or_else(|| format!("`{name}` has no Galvan equivalent"));
+        }
+        if let Some(elements) = ty.get("tuple").and_then(Value::as_array) {
+            if elements.is_empty() {
+                return Ok(TypeElement::void());
+            }
+            let elements = elements
+                .iter()
+                .map(|element| self.owned_type(element))
+                .collect::<Result<Vec<_>, String>>()?;
+            return Ok(TypeElement::Tuple(Box::new(TupleTypeItem {
+                elements,
+                span: Span::default(),
+            })));
+        }
+        if let Some(path) = ty.get("resolved_path") {
+            return self.path_type(path);
+        }
+        if ty.get("borrowed_ref").is_some() {
+            return Err("references cannot be stored or returned".to_string());
+        }
+        if let Some(generic) = ty.get("generic").and_then(Value::as_str) {
+            return Err(format!("generic type `{generic}` is not supported"));
+        }
+        Err("type has no Galvan equivalent".to_string())
+    }
+
+    fn path_type(&self, path: &Value) -> Result<TypeElement, String> {
+        // Format versions before 0.37 call the path `name`
+        let full_name = path
+            .get("path")
+            .or_else(|| path.get("name"))
+            .and_then(Value::as_str)
+            .ok_or("type path has no name")?;
+        let name = full_name.rsplit("::").next().unwrap_or(full_name);
+        let args = path
+            .pointer("/args/angle_bracketed/args")
+            .and_then(Value::as_array)
+            .map(|args| {
+                args.iter()
+                    .filter_map(|arg| arg.get("type"))
+                    .map(|arg| self.owned_type(arg))
+                    .collect::<Result<Vec<_>, String>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let ty = match (name, args.as_slice()) {
+            ("String", []) => plain("String"),
+            ("Vec", [elements]) => TypeElement::Array(Box::new(ArrayTypeItem {
+                elements: elements.clone(),
+                span: Span::default(),
+            })),
+            ("HashSet", [elements]) => TypeElement::Set(Box::new(SetTypeItem {
+                elements: elements.clone(),
+                span: Span::default(),
+            })),
+            ("HashMap", [key, value]) => TypeElement::Dictionary(Box::new(DictionaryTypeItem {
+                key: key.clone(),
+                value: value.clone(),
+                span: Span::default(),
+            })),
+            ("IndexMap", [key, value]) => {
+                TypeElement::OrderedDictionary(Box::new(OrderedDictionaryTypeItem {
+                    key: key.clone(),
+                    value: value.clone(),
+                    span: Span::default(),
+                }))
+            }
+            ("Option", [inner]) => TypeElement::Optional(Box::new(OptionalTypeItem {
+                inner: inner.clone(),
+                span: Span::default(),
+            })),
+            ("Result", [success, error]) => TypeElement::Result(Box::new(ResultTypeItem {
+                success: success.clone(),
+                error: Some(error.clone()),
+                span: Span::default(),
+            })),
+            (name, []) if self.type_names.contains(name) => plain(name),
+            _ => return Err(format!("`{full_name}` has no Galvan equivalent")),
+        };
+        Ok(ty)
+    }
+}
+
+/// Format versions before 0.36 call the flag `mutable`
+fn is_mutable(reference: &Value) -> bool {
+    reference
+        .get("is_mutable")
+        .or_else(|| reference.get("mutable"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
 use galvan_ast::{
-    DeclModifier, FnDecl, Ident, Ownership, ToplevelItem, TypeDecl, TypeElement, TypeIdent,
+    CustomInfix, DeclModifier, FnDecl, Ident, InfixDecl, Ownership, ToplevelItem, TraitDecl,
+    TypeDecl, TypeElement, TypeIdent,
 };
 
 use crate::{FunctionId, Lookup, LookupContext};
@@ -80,6 +81,13 @@ impl Lookup for Scope<'_> {
             .or_else(|| self.parent.and_then(|parent| parent.resolve_type(name)))
     }
 
+    fn resolve_trait(&self, name: &TypeIdent) -> Option<&ToplevelItem<TraitDecl>> {
+        self.lookup
+            .as_ref()
+            .and_then(|lookup| lookup.resolve_trait(name))
+            .or_else(|| self.parent.and_then(|parent| parent.resolve_trait(name)))
+    }
+
     fn resolve_function(
         &self,
         receiver: Option<&TypeIdent>,
@@ -94,6 +102,16 @@ impl Lookup for Scope<'_> {
                     .and_then(|parent| parent.resolve_function(receiver, name, labels))
             })
     }
+
+    fn resolve_infix(&self, operator: &CustomInfix) -> Option<&ToplevelItem<InfixDecl>> {
+        self.lookup
+            .as_ref()
+            .and_then(|lookup| lookup.resolve_infix(operator))
+            .or_else(|| {
+                self.parent
+                    .and_then(|parent| parent.resolve_infix(operator))
+            })
+    }
 }
 
 #[derive(Clone, Debug)]
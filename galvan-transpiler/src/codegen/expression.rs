@@ -2,13 +2,14 @@ use itertools::Itertools;
 
 use galvan_ast::{
     ArithmeticOperator, BitwiseOperator, ComparisonOperator, Ident, LogicalOperator, RangeOperator,
-    TypeElement,
+    TypeElement, UnaryOperator,
 };
 use galvan_hir::hir::*;
 
 use crate::context::Context;
 use crate::macros::transpile;
 use crate::sanitize::{mangle_function_name, sanitize_name, sanitize_path};
+use crate::source_map::escape_markers;
 use crate::ErrorCollector;
 use crate::Transpile;
 
@@ -21,6 +22,7 @@ impl Transpile for HirExpressionKind {
             HirExpressionKind::ElseUnwrap(unwrap) => unwrap.transpile(ctx, errors),
             HirExpressionKind::Try(try_expr) => try_expr.transpile(ctx, errors),
             HirExpressionKind::For(for_expr) => for_expr.transpile(ctx, errors),
+            HirExpressionKind::Loop(loop_expr) => loop_expr.transpile(ctx, errors),
             HirExpressionKind::Match(match_expr) => match_expr.transpile(ctx, errors),
             HirExpressionKind::Assert(assert) => assert.transpile(ctx, errors),
             HirExpressionKind::Print(print) => print.transpile(ctx, errors),
@@ -41,7 +43,9 @@ impl Transpile for HirExpressionKind {
             HirExpressionKind::Comparison(operation) => operation.transpile(ctx, errors),
             HirExpressionKind::CollectionOp(operation) => operation.transpile(ctx, errors),
             HirExpressionKind::Range(operation) => operation.transpile(ctx, errors),
+            HirExpressionKind::Unary(unary) => unary.transpile(ctx, errors),
             HirExpressionKind::Index(index) => index.transpile(ctx, errors),
+            HirExpressionKind::Slice(slice) => slice.transpile(ctx, errors),
             HirExpressionKind::Yeet(inner) => {
                 transpile!(ctx, errors, "{}?", inner)
             }
@@ -137,6 +141,7 @@ fn fallback_binding(binding: &Option<Ident>) -> String {
 impl Transpile for HirFor {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         let iterable = self.iterable.transpile(ctx, errors);
+        let label = label_prefix(&self.label);
         let element = for_pattern(&self.bindings);
 
         let mut statements: Vec<String> = self
@@ -151,6 +156,7 @@ impl Transpile for HirFor {
                 let block = statements.join(";\n");
                 render_for_loop(
                     self.iterable_kind,
+                    &label,
                     iterable,
                     element,
                     format!("{{ {block}; }}"),
@@ -164,7 +170,8 @@ impl Transpile for HirFor {
                 let block = statements.join(";\n");
                 let elem_ty = elem_ty.transpile(ctx, errors);
                 let loop_body = format!("{{ {block} }}");
-                let loop_expr = render_for_loop(self.iterable_kind, iterable, element, loop_body);
+                let loop_expr =
+                    render_for_loop(self.iterable_kind, &label, iterable, element, loop_body);
                 format!(
                     "{{
                 let mut __result: ::std::vec::Vec<{elem_ty}> = ::std::vec::Vec::new(); 
@@ -179,19 +186,61 @@ impl Transpile for HirFor {
 
 fn render_for_loop(
     kind: HirForIterableKind,
+    label: &str,
     iterable: String,
     element: String,
     body: String,
 ) -> String {
     match kind {
-        HirForIterableKind::Normal => format!("for {element} in {iterable} {body}"),
+        HirForIterableKind::Normal => format!("{label}for {element} in {iterable} {body}"),
         HirForIterableKind::Tuple { len } => {
-            let fields = (0..len).map(|i| format!("__iterable.{i}")).join(", ");
-            format!("{{ let __iterable = {iterable}; for {element} in [{fields}] {body} }}")
+            let fields = (0..len).map(|i| format!("&__iterable.{i}")).join(", ");
+            format!("{{ let __iterable = {iterable}; {label}for {element} in [{fields}] {body} }}")
         }
     }
 }
 
+impl Transpile for HirLoop {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        let label = label_prefix(&self.label);
+        let body = self.body.transpile(ctx, errors);
+        match &self.kind {
+            HirLoopKind::Infinite => format!("{label}loop {body}"),
+            HirLoopKind::While(condition) => {
+                let condition = condition.transpile(ctx, errors);
+                format!("{label}while {condition} {body}")
+            }
+            HirLoopKind::WhileTry {
+                condition,
+                kind,
+                bindings,
+            } => {
+                let condition = condition.transpile(ctx, errors);
+                let bindings = bindings
+                    .iter()
+                    .map(|binding| sanitize_name(binding.as_str()))
+                    .join(", ");
+                match kind {
+                    TryKind::Optional => {
+                        format!("{label}while let Some(({bindings})) = {condition} {body}")
+                    }
+                    TryKind::Result => {
+                        format!("{label}while let Ok({bindings}) = {condition} {body}")
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `'label: ` in front of a labeled loop
+fn label_prefix(label: &Option<Ident>) -> String {
+    match label {
+        Some(label) => format!("'{}: ", label.as_str()),
+        None => String::new(),
+    }
+}
+
 fn for_pattern(bindings: &[HirForBinding]) -> String {
     let parts = bindings
         .iter()
@@ -223,7 +272,17 @@ impl Transpile for HirMatch {
 
 impl Transpile for HirMatchArm {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
-        transpile!(ctx, errors, "{} => {}", self.pattern, self.body)
+        match &self.guard {
+            Some(guard) => transpile!(
+                ctx,
+                errors,
+                "{} if {} => {}",
+                self.pattern,
+                guard,
+                self.body
+            ),
+            None => transpile!(ctx, errors, "{} => {}", self.pattern, self.body),
+        }
     }
 }
 
@@ -232,7 +291,43 @@ impl Transpile for HirMatchPattern {
         match self {
             HirMatchPattern::Wildcard => "_".to_string(),
             HirMatchPattern::EnumVariant(pattern) => pattern.transpile(ctx, errors),
+            HirMatchPattern::Literal(literal) => pattern_literal(literal, ctx, errors),
+            HirMatchPattern::Range(range) => {
+                let start = pattern_literal(&range.start, ctx, errors);
+                let end = pattern_literal(&range.end, ctx, errors);
+                if range.inclusive {
+                    format!("{start}..={end}")
+                } else {
+                    format!("{start}..{end}")
+                }
+            }
+            HirMatchPattern::Tuple(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| element.transpile(ctx, errors))
+                    .collect::<Vec<_>>();
+                match elements.as_slice() {
+                    [single] => format!("({single},)"),
+                    elements => format!("({})", elements.join(", ")),
+                }
+            }
+            HirMatchPattern::Alternatives(alternatives) => alternatives
+                .iter()
+                .map(|alternative| alternative.transpile(ctx, errors))
+                .join(" | "),
+        }
+    }
+}
+
+/// Literals in patterns must be plain Rust literals, so strings are rendered
+/// as `&str` literals instead of `format!` calls
+fn pattern_literal(literal: &HirLiteral, ctx: &Context, errors: &mut ErrorCollector) -> String {
+    match literal {
+        HirLiteral::String(string) => {
+            let value = string.value.replace("{{", "{").replace("}}", "}");
+            escape_markers(&value).into_owned()
         }
+        literal => literal.transpile(ctx, errors),
     }
 }
 
@@ -272,10 +367,11 @@ impl Transpile for HirNamedMatchBinding {
 }
 
 impl Transpile for HirMatchBindingPattern {
-    fn transpile(&self, _ctx: &Context, _errors: &mut ErrorCollector) -> String {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         match self {
             HirMatchBindingPattern::Binding(ident) => sanitize_name(ident.as_str()).into_owned(),
             HirMatchBindingPattern::Wildcard => "_".to_string(),
+            HirMatchBindingPattern::Pattern(pattern) => pattern.transpile(ctx, errors),
         }
     }
 }
@@ -517,15 +613,16 @@ impl Transpile for HirLiteral {
 
 impl Transpile for HirStringLiteral {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        let value = escape_markers(&self.value);
         if self.interpolations.is_empty() {
-            format!("format!({})", self.value)
+            format!("format!({value})")
         } else {
             let args = self
                 .interpolations
                 .iter()
                 .map(|interpolation| interpolation.transpile(ctx, errors))
                 .join(", ");
-            format!("format!({}, {})", self.value, args)
+            format!("format!({value}, {args})")
         }
     }
 }
@@ -694,20 +791,41 @@ impl Transpile for HirBinary<RangeOperator> {
     }
 }
 
+impl Transpile for HirUnary {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        match self.operator {
+            UnaryOperator::Not | UnaryOperator::BitwiseNot => {
+                transpile!(ctx, errors, "!({})", self.operand)
+            }
+            UnaryOperator::Negate => transpile!(ctx, errors, "-({})", self.operand),
+        }
+    }
+}
+
 impl Transpile for HirBinary<CollectionOperator> {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         match self.operator {
             CollectionOperator::Concat(kind) => transpile_concat(self, kind, ctx, errors),
-            CollectionOperator::Remove => {
-                errors.warning(
-                    "The remove operator '--' is not implemented yet".to_string(),
-                    None,
-                );
-                "/* unsupported remove operator */".to_string()
-            }
-            CollectionOperator::Contains => {
-                transpile!(ctx, errors, "({}).contains(&({}))", self.rhs, self.lhs)
-            }
+            CollectionOperator::Remove(kind) => transpile_remove(self, kind, ctx, errors),
+            CollectionOperator::Repeat => match &self.lhs.ty {
+                TypeElement::Plain(basic) if basic.ident.as_str() == "String" => {
+                    transpile!(ctx, errors, "({}).repeat(({}) as usize)", self.lhs, self.rhs)
+                }
+                _ => transpile!(
+                    ctx,
+                    errors,
+                    "::std::iter::repeat(({}).iter()).take(({}) as usize).flatten().cloned().collect::<::std::vec::Vec<_>>()",
+                    self.lhs,
+                    self.rhs
+                ),
+            },
+            // `key in dict` tests the keys of dictionaries
+            CollectionOperator::Contains => match &self.rhs.ty {
+                TypeElement::Dictionary(_) | TypeElement::OrderedDictionary(_) => {
+                    transpile!(ctx, errors, "({}).contains_key(&({}))", self.rhs, self.lhs)
+                }
+                _ => transpile!(ctx, errors, "({}).contains(&({}))", self.rhs, self.lhs),
+            },
         }
     }
 }
@@ -779,6 +897,61 @@ fn transpile_concat(
     }
 }
 
+/// `--` removal; the shape was decided by the typechecker, the collection
+/// kind comes from the stored left-hand side type. Dictionaries remove by key.
+fn transpile_remove(
+    operation: &HirBinary<CollectionOperator>,
+    kind: RemoveKind,
+    ctx: &Context,
+    errors: &mut ErrorCollector,
+) -> String {
+    match (&operation.lhs.ty, kind) {
+        (
+            TypeElement::Set(_) | TypeElement::Dictionary(_) | TypeElement::OrderedDictionary(_),
+            RemoveKind::Element,
+        ) => {
+            transpile!(
+                ctx,
+                errors,
+                "{{ let mut temp = ({}).to_owned(); temp.remove(&({})); temp }}",
+                operation.lhs,
+                operation.rhs
+            )
+        }
+        (
+            TypeElement::Set(_) | TypeElement::Dictionary(_) | TypeElement::OrderedDictionary(_),
+            RemoveKind::Collection,
+        ) => {
+            transpile!(
+                ctx,
+                errors,
+                "{{ let mut temp = ({}).to_owned(); for __removed in ({}).iter() {{ temp.remove(__removed); }} temp }}",
+                operation.lhs,
+                operation.rhs
+            )
+        }
+        // Arrays and unknown collection types remove as arrays
+        (_, RemoveKind::Element) => {
+            transpile!(
+                ctx,
+                errors,
+                "{{ let __removed = {}; ({}).iter().filter(|__element| **__element != __removed).cloned().collect::<::std::vec::Vec<_>>() }}",
+                operation.rhs,
+                operation.lhs
+            )
+        }
+        (_, RemoveKind::Collection) => {
+            transpile!(
+                ctx,
+                errors,
+                "{{ let __removed = &({}); ({}).iter().filter(|__element| !__removed.contains(*__element)).cloned().collect::<::std::vec::Vec<_>>() }}",
+                operation.rhs,
+                operation.lhs
+            )
+        }
+    }
+}
+
 impl Transpile for HirIndex {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         match &self.base.ty {
@@ -800,3 +973,39 @@ impl Transpile for HirIndex {
         }
     }
 }
+
+impl Transpile for HirSlice {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        let start = self
+            .start
+            .as_ref()
+            .map(|start| transpile!(ctx, errors, "({}) as usize", start));
+        let end = self
+            .end
+            .as_ref()
+            .map(|end| transpile!(ctx, errors, "({}) as usize", end));
+        let base = self.base.transpile(ctx, errors);
+        match &self.base.ty {
+            // Strings are sliced by characters, not bytes
+            TypeElement::Plain(basic) if basic.ident.as_str() == "String" => {
+                let start = start.unwrap_or_else(|| "0".to_string());
+                let take = end
+                    .map(|end| format!(".take(({end}).saturating_sub(__start))"))
+                    .unwrap_or_default();
+                format!(
+                    "{{ let __start = {start}; ({base}).chars().skip(__start){take}.collect::<::std::string::String>() }}"
+                )
+            }
+            // Bounds are clamped like `skip`/`take` clamp them for strings
+            _ => {
+                let start = start.unwrap_or_else(|| "0".to_string());
+                let end = end.unwrap_or_else(|| "__items.len()".to_string());
+                format!(
+                    "{{ let __items = &({base}); let __start = ({start}).min(__items.len()); \
+                     let __end = ({end}).clamp(__start, __items.len()); \
+                     __items[__start..__end].to_vec() }}"
+                )
+            }
+        }
+    }
+}
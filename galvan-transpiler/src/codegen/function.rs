@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use galvan_ast::{CmdSignature, DeclModifier, FnSignature, Ident, Param, TypeElement};
+use galvan_ast::{CmdSignature, DeclModifier, FnSignature, Ident, Param, TypeElement, Visibility};
 use galvan_hir::builtins::CheckBuiltins;
 use galvan_hir::hir::{HirFunction, HirMain, HirMainKind, HirTest};
 use itertools::Itertools;
@@ -20,13 +20,46 @@ pub(crate) fn transpile_function(
     errors: &mut ErrorCollector,
     skip_generics: &HashSet<Ident>,
 ) -> String {
-    let signature = transpile_signature(&function.signature, ctx, errors, skip_generics);
+    transpile_function_with(function, &function.signature, ctx, errors, skip_generics)
+}
+
+/// Transpiles a member function that implements a trait requirement. Items in
+/// trait impls inherit the visibility of the trait and cannot declare one.
+pub(crate) fn transpile_trait_function(
+    function: &HirFunction,
+    ctx: &Context,
+    errors: &mut ErrorCollector,
+    skip_generics: &HashSet<Ident>,
+) -> String {
+    let signature = FnSignature {
+        visibility: Visibility::private(),
+        ..function.signature.clone()
+    };
+    transpile_function_with(function, &signature, ctx, errors, skip_generics)
+}
+
+fn transpile_function_with(
+    function: &HirFunction,
+    signature: &FnSignature,
+    ctx: &Context,
+    errors: &mut ErrorCollector,
+    skip_generics: &HashSet<Ident>,
+) -> String {
+    let rendered_signature = render_signature(
+        signature,
+        function.returns_borrow,
+        ctx,
+        errors,
+        skip_generics,
+    );
+    errors.enter_source(&function.source);
     let block = function.body.transpile(ctx, errors);
+    errors.leave_source();
 
-    if !function.signature.return_type.is_void() {
-        format!("{signature} {block}")
+    if !signature.return_type.is_void() {
+        format!("{rendered_signature} {block}")
     } else {
-        format!("{signature} {{ {block}; }}")
+        format!("{rendered_signature} {{ {block}; }}")
     }
 }
 
@@ -35,6 +68,18 @@ pub(crate) fn transpile_signature(
     ctx: &Context,
     errors: &mut ErrorCollector,
     skip_generics: &HashSet<Ident>,
+) -> String {
+    render_signature(signature, false, ctx, errors, skip_generics)
+}
+
+/// Renders a signature; functions returning a borrow of `self` return `&T`
+/// with the lifetime elided to the one of the receiver
+fn render_signature(
+    signature: &FnSignature,
+    returns_borrow: bool,
+    ctx: &Context,
+    errors: &mut ErrorCollector,
+    skip_generics: &HashSet<Ident>,
 ) -> String {
     let visibility = signature.visibility.transpile(ctx, errors);
     let identifier =
@@ -64,6 +109,7 @@ pub(crate) fn transpile_signature(
 
     let return_type = match &signature.return_type {
         TypeElement::Infer(_) | TypeElement::Void(_) => String::new(),
+        ty if returns_borrow => format!(" -> &{}", ty.transpile(ctx, errors)),
         ty => format!(" -> {}", ty.transpile(ctx, errors)),
     };
 
@@ -174,12 +220,16 @@ pub(crate) fn transpile_test(
     ctx: &Context,
     errors: &mut ErrorCollector,
 ) -> String {
+    errors.enter_source(&test.source);
     let body = test.body.transpile(ctx, errors);
+    errors.leave_source();
     format!("#[test]\nfn {name}() {{\n{body};\n}}")
 }
 
 pub(crate) fn transpile_main(main: &HirMain, ctx: &Context, errors: &mut ErrorCollector) -> String {
+    errors.enter_source(&main.source);
     let body = main.body.transpile(ctx, errors);
+    errors.leave_source();
     match &main.kind {
         HirMainKind::Function { argument: None } => {
             format!("pub(crate) fn __main__() {body}")
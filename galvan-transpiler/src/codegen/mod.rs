@@ -10,18 +10,21 @@ mod statement;
 
 pub(crate) use function::{
     transpile_function, transpile_main, transpile_signature, transpile_test,
+    transpile_trait_function,
 };
 
 use galvan_hir::hir::{Adjustment, HirExpression, HirExpressionKind};
 
 use crate::context::Context;
+use crate::source_map;
 use crate::ErrorCollector;
 use crate::Transpile;
 
 impl Transpile for HirExpression {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         let rendered = self.kind.transpile(ctx, errors);
-        apply_adjustments(rendered, &self.kind, &self.adjustments)
+        let rendered = apply_adjustments(rendered, &self.kind, &self.adjustments);
+        source_map::mark(rendered, self.span, errors)
     }
 }
 
@@ -57,6 +60,10 @@ fn apply_adjustments(
             Adjustment::WrapErr => format!("Err({result})"),
             Adjustment::LockRef => format!("{result}.lock().unwrap()"),
             Adjustment::ArcClone => format!("::std::sync::Arc::clone(&{result})"),
+            Adjustment::IntoUnion if parenthesize => format!("({result}).into()"),
+            Adjustment::IntoUnion => format!("{result}.into()"),
+            Adjustment::AsStr if parenthesize => format!("({result}).as_str()"),
+            Adjustment::AsStr => format!("{result}.as_str()"),
         };
     }
     result
@@ -69,8 +76,11 @@ fn needs_parens(kind: &HirExpressionKind) -> bool {
     match kind {
         Variable(_) | Literal(_) | FunctionCall(_) | MethodCall(_) | FieldAccess(_)
         | SafeAccess(_) | ConstructorCall(_) | EnumAccess(_) | EnumConstructor(_)
-        | Collection(_) | Index(_) | Group(_) | Yeet(_) | Print(_) | Assert(_) | Error(_) => false,
-        If(_) | ElseUnwrap(_) | Try(_) | For(_) | Match(_) | Closure(_) | Logical(_)
-        | Arithmetic(_) | Bitwise(_) | Comparison(_) | CollectionOp(_) | Range(_) => true,
+        | Collection(_) | Index(_) | Slice(_) | Group(_) | Yeet(_) | Print(_) | Assert(_)
+        | Error(_) => false,
+        If(_) | ElseUnwrap(_) | Try(_) | For(_) | Loop(_) | Match(_) | Closure(_) | Logical(_)
+        | Arithmetic(_) | Bitwise(_) | Comparison(_) | CollectionOp(_) | Range(_) | Unary(_) => {
+            true
+        }
     }
 }
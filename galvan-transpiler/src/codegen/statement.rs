@@ -1,4 +1,4 @@
-use galvan_ast::{DeclModifier, TypeElement};
+use galvan_ast::{DeclModifier, Ident, TypeElement};
 use galvan_hir::hir::*;
 use itertools::Itertools;
 
@@ -58,21 +58,35 @@ impl Transpile for HirStatement {
             HirStatement::Throw(throw) => {
                 transpile!(ctx, errors, "return Err({})", throw.expression)
             }
-            HirStatement::Break(_) => "break".to_string(),
-            HirStatement::Continue(_) => "continue".to_string(),
+            HirStatement::Break(brk) => {
+                let label = label_reference(&brk.label);
+                match &brk.value {
+                    Some(value) => transpile!(ctx, errors, "break{label} {}", value),
+                    None => format!("break{label}"),
+                }
+            }
+            HirStatement::Continue(cont) => format!("continue{}", label_reference(&cont.label)),
         }
     }
 }
 
+/// ` 'label` after `break` or `continue`
+fn label_reference(label: &Option<Ident>) -> String {
+    match label {
+        Some(label) => format!(" '{}", label.as_str()),
+        None => String::new(),
+    }
+}
+
 impl Transpile for HirDeclaration {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
-        let keyword = match self.modifier {
-            DeclModifier::Let => "let",
-            DeclModifier::Ref => "let mut",
-            DeclModifier::Mut => "let mut",
+        // Destructured bindings carry `mut` themselves
+        let keyword = match (&self.pattern, self.modifier) {
+            (HirDeclarationPattern::Ident(_), DeclModifier::Ref | DeclModifier::Mut) => "let mut",
+            _ => "let",
         };
 
-        let identifier = sanitize_name(self.identifier.as_str());
+        let identifier = transpile_declaration_pattern(&self.pattern, self.modifier, ctx, errors);
 
         let ty = self.ty.transpile(ctx, errors);
         let ty = match self.modifier {
@@ -95,6 +109,61 @@ impl Transpile for HirDeclaration {
     }
 }
 
+fn transpile_declaration_pattern(
+    pattern: &HirDeclarationPattern,
+    modifier: DeclModifier,
+    ctx: &Context,
+    errors: &mut ErrorCollector,
+) -> String {
+    match pattern {
+        HirDeclarationPattern::Ident(ident) => sanitize_name(ident.as_str()).into_owned(),
+        HirDeclarationPattern::Wildcard => "_".to_string(),
+        HirDeclarationPattern::Tuple(elements) => {
+            let elements = elements
+                .iter()
+                .map(|element| destructured_binding(element, modifier, ctx, errors))
+                .collect::<Vec<_>>();
+            match elements.as_slice() {
+                [single] => format!("({single},)"),
+                elements => format!("({})", elements.join(", ")),
+            }
+        }
+        HirDeclarationPattern::Struct(destructuring) => {
+            let mut fields = destructuring
+                .fields
+                .iter()
+                .map(|(field, binding)| {
+                    let binding = destructured_binding(binding, modifier, ctx, errors);
+                    format!("{}: {binding}", sanitize_name(field.as_str()))
+                })
+                .collect::<Vec<_>>();
+            if destructuring.has_rest {
+                fields.push("..".to_string());
+            }
+            format!(
+                "{} {{ {} }}",
+                destructuring.ty.transpile(ctx, errors),
+                fields.join(", ")
+            )
+        }
+    }
+}
+
+/// Renders a variable bound inside a destructuring pattern, which has to be
+/// marked `mut` individually
+fn destructured_binding(
+    pattern: &HirDeclarationPattern,
+    modifier: DeclModifier,
+    ctx: &Context,
+    errors: &mut ErrorCollector,
+) -> String {
+    let rendered = transpile_declaration_pattern(pattern, modifier, ctx, errors);
+    match (pattern, modifier) {
+        (HirDeclarationPattern::Ident(_), DeclModifier::Mut) => format!("mut {rendered}"),
+        _ => rendered,
+    }
+}
+
 impl Transpile for HirAssignment {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         let prefix = if self.deref_target { "*" } else { "" };
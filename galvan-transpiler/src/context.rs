@@ -1,4 +1,4 @@
-use galvan_ast::SegmentedAsts;
+use galvan_ast::{SegmentedAsts, ToplevelItem, TypeDecl};
 use galvan_hir::mapping::Mapping;
 use galvan_resolver::{LookupContext, LookupError};
 
@@ -22,4 +22,17 @@ impl<'a> Context<'a> {
         self.lookup = self.lookup.with(asts)?;
         Ok(self)
     }
+
+    /// A copy of this context in which `types` shadow types of the same
+    /// name, e.g. the types declared by the module being generated
+    pub fn with_types(&self, types: impl IntoIterator<Item = &'a ToplevelItem<TypeDecl>>) -> Self {
+        let mut lookup = self.lookup.clone();
+        for ty in types {
+            lookup.types.insert(ty.item.ident().clone(), ty);
+        }
+        Self {
+            lookup,
+            mapping: self.mapping.clone(),
+        }
+    }
 }
@@ -1,14 +1,124 @@
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
 use std::path::Path;
 
 use galvan_files::read_sources;
+use galvan_resolver::rustdoc::{read_rustdoc, RustdocCrate, RustdocError};
+use serde_json::Value;
 
-use crate::{transpile, TranspileError, TranspileOutput};
+use crate::source_map::diagnostic_mut;
+use crate::{
+    report_diagnostics, transpile_sources, DiagnosticOutput, ErrorCollector, SourceMap, SourceMaps,
+    TranspileError, TranspileOutput,
+};
 
+/// Transpiles the sources in the directory and returns the generated files
+/// with the warnings found in them
 pub fn transpile_dir(
     path: impl AsRef<Path>,
     filter: Vec<String>,
-) -> Result<Vec<TranspileOutput>, TranspileError> {
-    transpile(read_sources(path, filter)?)
+) -> Result<(Vec<TranspileOutput>, ErrorCollector), TranspileError> {
+    transpile_sources(read_sources(path, filter)?, &[])
+}
+
+/// Reads the rustdoc JSON of every `.json` file in the directory. A missing
+/// directory imports no crates.
+pub fn read_rustdoc_dir(path: impl AsRef<Path>) -> Result<Vec<RustdocCrate>, TranspileError> {
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = std::fs::read_dir(path)
+        .map_err(RustdocError::from)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+        .into_iter()
+        .map(|file| read_rustdoc(file).map_err(TranspileError::from))
+        .collect()
+}
+
+/// Rewrites the JSON diagnostics of rustc or `cargo --message-format=json`
+/// read from `input` to point at the Galvan code that the reported Rust code
+/// was generated from, and writes them to `output`. The source maps are read
+/// from the `.rs.map` files the build script writes next to the generated
+/// files. With `rendered`, only the rendered text of the diagnostics is
+/// written.
+pub fn rewrite_rustc_diagnostics(
+    input: impl BufRead,
+    mut output: impl Write,
+    rendered: bool,
+) -> io::Result<()> {
+    let mut maps = SourceMaps::new();
+    let mut read = HashSet::new();
+    for line in input.lines() {
+        let line = line?;
+        if let Ok(mut value) = serde_json::from_str::<Value>(&line) {
+            if let Some(diagnostic) = diagnostic_mut(&mut value) {
+                for file in span_files(diagnostic) {
+                    if read.insert(file.clone()) {
+                        load_source_map(&mut maps, file);
+                    }
+                }
+            }
+        }
+
+        let line = maps.rewrite_json(&line);
+        if !rendered {
+            writeln!(output, "{line}")?;
+            continue;
+        }
+        let Ok(mut value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if let Some(text) = diagnostic_mut(&mut value)
+            .and_then(|diagnostic| diagnostic.get("rendered"))
+            .and_then(Value::as_str)
+        {
+            write!(output, "{text}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds the source map of a generated file and the Galvan files it points
+/// into, if the file has one
+fn load_source_map(maps: &mut SourceMaps, file: String) {
+    let Some(map) = std::fs::read_to_string(format!("{file}.map"))
+        .ok()
+        .and_then(|json| SourceMap::from_json(&json))
+    else {
+        return;
+    };
+    for source in map.mappings().iter().map(|mapping| &mapping.file) {
+        if let Ok(content) = std::fs::read_to_string(source) {
+            maps.add_source(source.clone(), content);
+        }
+    }
+    maps.insert(file, map);
+}
+
+/// The files the spans of a rustc diagnostic point into
+fn span_files(diagnostic: &Value) -> Vec<String> {
+    let mut files = diagnostic
+        .get("spans")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|span| span.get("file_name")?.as_str().map(str::to_owned))
+        .collect::<Vec<_>>();
+    for child in diagnostic
+        .get("children")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        files.extend(span_files(child));
+    }
+    files
 }
 
 /// This is for use in macros and should not be used directly
@@ -18,21 +128,56 @@ pub mod __private {
 
     use std::path::PathBuf;
 
-    pub fn __setup_galvan() -> String {
-        let transpiled = match transpile_dir("src", vec![]) {
-            Ok(output) => output,
-            Err(e) => return e.to_string(),
+    /// Transpiles the crate and writes the generated files to `OUT_DIR`.
+    /// Errors are reported as `cargo::error=` lines, which fail the build
+    /// once the build script finishes.
+    pub fn __setup_galvan() {
+        // CI annotators set `GALVAN_ERROR_FORMAT=json` to get machine-readable
+        // diagnostics on stderr
+        println!("cargo::rerun-if-env-changed=GALVAN_ERROR_FORMAT");
+        let output = match std::env::var("GALVAN_ERROR_FORMAT").as_deref() {
+            Ok("json") => DiagnosticOutput::Json,
+            _ => DiagnosticOutput::Cargo,
+        };
+        let transpiled = read_rustdoc_dir("rustdoc")
+            .and_then(|crates| transpile_sources(read_sources("src", vec![])?, &crates));
+        let files = match transpiled {
+            Ok((files, warnings)) => {
+                report_diagnostics(&warnings, output);
+                files
+            }
+            Err(TranspileError::Diagnostics(errors)) => {
+                report_diagnostics(&errors, output);
+                // JSON diagnostics go to stderr, so Cargo still has to be
+                // told that the build failed
+                if output != DiagnosticOutput::Cargo {
+                    let count = errors.error_count();
+                    cargo_error(&format!(
+                        "could not transpile due to {count} previous error(s)"
+                    ));
+                }
+                return;
+            }
+            Err(e) => return cargo_error(&e.to_string()),
         };
 
-        let out_dir: PathBuf = std::env::var_os("OUT_DIR").unwrap().into();
+        if let Err(e) = write_files(files) {
+            cargo_error(&format!("Failed to write the generated code: {e}"));
+        }
+    }
+
+    fn write_files(files: Vec<TranspileOutput>) -> io::Result<()> {
+        let out_dir: PathBuf = std::env::var_os("OUT_DIR")
+            .ok_or_else(|| io::Error::other("OUT_DIR is not set, call setup! from a build script"))?
+            .into();
         let mod_dir = out_dir.join(galvan_module!());
         if let Err(e) = fs::create_dir(&mod_dir) {
-            if e.kind() != std::io::ErrorKind::AlreadyExists {
-                panic!("Failed to create module directory: {}", e);
+            if e.kind() != io::ErrorKind::AlreadyExists {
+                return Err(e);
             }
         }
 
-        for file in transpiled {
+        for file in files {
             let dir = if file.file_name.as_ref() == galvan_module!("rs") {
                 &out_dir
             } else {
@@ -40,10 +185,24 @@ pub mod __private {
             };
 
             let path = dir.join(file.file_name.as_ref());
-            fs::write(path, file.content.as_ref()).unwrap();
+            // Files of nested modules are placed in directories named after them
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // Lets `galvan-transpile rustc-diagnostics` point rustc errors in
+            // the generated code at the Galvan code
+            if !file.source_map.is_empty() {
+                fs::write(path.with_extension("rs.map"), file.source_map.to_json())?;
+            }
+            fs::write(path, file.content.as_ref())?;
         }
+        Ok(())
+    }
 
-        // TODO: Output warnings here
-        "".to_string()
+    /// Cargo only takes single-line directives
+    fn cargo_error(message: &str) {
+        for line in message.lines() {
+            println!("cargo::error={line}");
+        }
     }
 }
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::iter;
 
 use convert_case::{Case, Casing};
@@ -8,13 +9,19 @@ use itertools::Itertools;
 use thiserror::Error;
 
 use galvan_ast::*;
-use galvan_files::{FileError, Source};
-use galvan_hir::hir::{HirCmd, HirFunction, HirMain, HirMainKind, HirModule, HirTest};
-use galvan_hir::typecheck::typecheck;
+use galvan_files::{FileError, ModulePath, Source};
+use galvan_hir::hir::{
+    HirCmd, HirFunction, HirMain, HirMainKind, HirModule, HirOperatorImpl, HirTest,
+};
+use galvan_hir::typecheck::{typecheck_with, union_members};
 use galvan_into_ast::{AstError, SegmentAst, SourceIntoAst};
+use galvan_resolver::rustdoc::{RustdocCrate, RustdocError};
 use galvan_resolver::LookupError;
 
-use crate::codegen::{transpile_function, transpile_main, transpile_signature, transpile_test};
+use crate::codegen::{
+    transpile_function, transpile_main, transpile_signature, transpile_test,
+    transpile_trait_function,
+};
 
 static SUPPRESS_WARNINGS: &str = "#![allow(warnings, unused)]";
 
@@ -157,7 +164,9 @@ fn generate_cli_structure(
 
         // Generate the command function
         let signature = cmd.signature.transpile(ctx, errors);
+        errors.enter_source(&cmd.source);
         let body = cmd.body.transpile(ctx, errors);
+        errors.leave_source();
         command_functions.push(format!("{signature} {body}"));
 
         // Generate args struct for this command
@@ -308,9 +317,13 @@ pub mod exec;
 
 mod context;
 mod sanitize;
+mod source_map;
+
+pub use source_map::{Mapping, SourceMap, SourceMaps};
 
 pub use galvan_hir::error::{
-    Diagnostic, DiagnosticSeverity, ErrorCollector, Span, TranspilerError,
+    explain, Diagnostic, DiagnosticSeverity, ErrorCollector, Span, TranspilerError,
+    TranspilerWarning,
 };
 
 #[derive(Debug, Error)]
@@ -321,35 +334,66 @@ pub enum TranspileError {
     Lookup(#[from] LookupError),
     #[error(transparent)]
     File(#[from] FileError),
+    #[error(transparent)]
+    Rustdoc(#[from] RustdocError),
+    /// The collected diagnostics, of which at least one is an error
+    #[error("{0}")]
+    Diagnostics(ErrorCollector),
+}
+
+/// Where and in which format collected diagnostics are reported
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticOutput {
+    /// As `cargo::error=` and `cargo::warning=` lines of a build script
+    Cargo,
+    /// To stderr, when run from the command line
+    Terminal,
+    /// To stderr as JSON lines in the shape of rustc's `--error-format=json`
+    Json,
 }
 
-fn transpile_sources(sources: Vec<Source>) -> Result<Vec<TranspileOutput>, TranspileError> {
+fn transpile_sources(
+    sources: Vec<Source>,
+    crates: &[RustdocCrate],
+) -> Result<(Vec<TranspileOutput>, ErrorCollector), TranspileError> {
     let asts = sources
         .into_iter()
         .map(|s| s.try_into_ast())
         .collect::<Result<Vec<_>, _>>()?;
 
-    transpile_asts(asts)
+    transpile_asts(asts, crates)
 }
 
-fn transpile_asts(asts: Vec<Ast>) -> Result<Vec<TranspileOutput>, TranspileError> {
+fn transpile_asts(
+    asts: Vec<Ast>,
+    crates: &[RustdocCrate],
+) -> Result<(Vec<TranspileOutput>, ErrorCollector), TranspileError> {
     let segmented = asts.segmented()?;
-    let (module, mut errors) = typecheck(segmented)?;
+    let (module, mut errors) = typecheck_with(segmented, crates)?;
 
     let builtins = builtins();
     let predefined = predefined_from(&builtins, builtin_fns());
     let mut ctx = Context::new(builtins);
     ctx = ctx.with(&predefined)?;
+    for imported in crates {
+        ctx = ctx.with(&imported.items)?;
+    }
     for ty in &module.types {
         ctx.lookup.types.insert(ty.item.ident().clone(), ty);
     }
 
-    transpile_module(&module, &ctx, &mut errors)
+    let files = transpile_module(&module, &ctx, &mut errors)?;
+    if errors.has_errors() {
+        return Err(TranspileError::Diagnostics(errors));
+    }
+    Ok((files, errors))
 }
 
 struct TypeFileContent<'a> {
     pub ty: &'a TypeDecl,
+    pub source: &'a Source,
     pub fns: Vec<&'a HirFunction>,
+    pub operators: Vec<&'a HirOperatorImpl>,
 }
 
 struct ExtensionFileContent<'a> {
@@ -357,37 +401,81 @@ struct ExtensionFileContent<'a> {
     pub fns: Vec<&'a HirFunction>,
 }
 
-fn transpile_uses(uses: &[ToplevelItem<UseDecl>]) -> String {
+/// The items of a Galvan module, generated as a Rust module of the same name
+#[derive(Default)]
+struct ModuleContent<'a> {
+    uses: Vec<&'a ToplevelItem<UseDecl>>,
+    types: Vec<&'a ToplevelItem<TypeDecl>>,
+    traits: Vec<&'a ToplevelItem<TraitDecl>>,
+    type_files: HashMap<ModuleName, TypeFileContent<'a>>,
+    extensions: HashMap<ModuleName, ExtensionFileContent<'a>>,
+    functions: Vec<&'a HirFunction>,
+    tests: Vec<&'a HirTest>,
+}
+
+/// The generated code of a Galvan module, except for its nested modules
+struct ModuleOutput {
+    imports: String,
+    /// Declarations of the files generated for types and extensions
+    files: String,
+    traits: String,
+    functions: String,
+    tests: String,
+}
+
+#[derive(Hash, PartialEq, Eq, Deref, From, Display)]
+struct ModuleName(Box<str>);
+
+fn module_name(ident: &TypeIdent) -> ModuleName {
+    ident.as_str().to_case(Case::Snake).into_boxed_str().into()
+}
+
+fn extension_module_name(ty: &TypeElement) -> ModuleName {
+    extension_name(ty)
+        .to_ascii_lowercase()
+        .into_boxed_str()
+        .into()
+}
+
+/// Extension traits are prefixed with their module so that extensions of
+/// the same type in different modules can be imported side by side
+fn module_extension_name(module: &ModulePath, ty: &TypeElement) -> String {
+    module
+        .segments()
+        .map(|segment| segment.to_case(Case::UpperCamel) + "_")
+        .chain(iter::once(extension_name(ty)))
+        .collect()
+}
+
+fn transpile_uses(uses: &[&ToplevelItem<UseDecl>], modules: &[ModulePath]) -> String {
     uses.iter()
         .map(|use_decl| {
             let path = crate::sanitize::sanitize_path(&use_decl.path);
-            if use_decl.path.segments.len() == 1 {
+            if use_decl.path.segments.len() == 1 || names_module(&use_decl.path, modules) {
                 format!("use {path}::*;")
             } else {
                 format!("use {path};")
             }
         })
+        .unique()
         .join("\n")
 }
 
+/// Whether the absolute `path` names a module of this crate
+fn names_module(path: &UsePath, modules: &[ModulePath]) -> bool {
+    match path.segments.split_first() {
+        Some((first, rest)) if first.as_str() == "crate" => modules
+            .iter()
+            .any(|module| module.segments().eq(rest.iter().map(Ident::as_str))),
+        _ => false,
+    }
+}
+
 fn transpile_module(
     module: &HirModule,
     ctx: &Context,
     errors: &mut ErrorCollector,
 ) -> Result<Vec<TranspileOutput>, TranspileError> {
-    #[derive(Hash, PartialEq, Eq, Deref, From, Display)]
-    struct ModuleName(Box<str>);
-    fn module_name(ident: &TypeIdent) -> ModuleName {
-        ident.as_str().to_case(Case::Snake).into_boxed_str().into()
-    }
-
-    fn extension_module_name(ty: &TypeElement) -> ModuleName {
-        extension_name(ty)
-            .to_ascii_lowercase()
-            .into_boxed_str()
-            .into()
-    }
-
     fn add_extension_module<'a>(
         extensions: &mut HashMap<ModuleName, ExtensionFileContent<'a>>,
         func: &'a HirFunction,
@@ -402,68 +490,192 @@ fn transpile_module(
         content.fns.push(func);
     }
 
-    let mut type_files: HashMap<ModuleName, TypeFileContent> = HashMap::new();
+    let root = ModulePath::root();
+    let mut contents: BTreeMap<&ModulePath, ModuleContent> = iter::once(&root)
+        .chain(&module.modules)
+        .map(|path| (path, ModuleContent::default()))
+        .collect();
 
     for ty in &module.types {
-        if let Some(duplicate) = type_files.insert(
-            module_name(ty.item.ident()),
-            TypeFileContent {
-                ty: &ty.item,
-                fns: Vec::new(),
-            },
-        ) {
-            panic!(
-                "File collision for types: {} and {}",
-                ty.item.ident(),
-                duplicate.ty.ident()
-            );
+        let content = contents.entry(ty.source.module()).or_default();
+        match content.type_files.entry(module_name(ty.item.ident())) {
+            Entry::Vacant(entry) => {
+                content.types.push(ty);
+                entry.insert(TypeFileContent {
+                    ty: &ty.item,
+                    source: &ty.source,
+                    fns: Vec::new(),
+                    operators: Vec::new(),
+                });
+            }
+            // Duplicate types are reported by the typechecker, only the first
+            // declaration is generated
+            Entry::Occupied(entry) if entry.get().ty.ident() == ty.item.ident() => {}
+            Entry::Occupied(entry) => {
+                let first = entry.get();
+                errors.conflict_error(
+                    TranspilerError::TypeFileCollision {
+                        first: first.ty.ident().to_string(),
+                        second: ty.item.ident().to_string(),
+                        file: entry.key().to_string(),
+                    },
+                    (ty.item.span(), &ty.source),
+                    (first.ty.span(), first.source),
+                );
+            }
         }
     }
 
-    let mut toplevel_functions = Vec::new();
-    let mut extensions: HashMap<ModuleName, ExtensionFileContent> = HashMap::new();
+    for operator in &module.operators {
+        if let Some(content) = contents
+            .get_mut(&operator.module)
+            .and_then(|content| content.type_files.get_mut(&module_name(&operator.ty)))
+        {
+            content.operators.push(operator);
+        }
+    }
+
+    for trait_decl in &module.traits {
+        let content = contents.entry(trait_decl.source.module()).or_default();
+        content.traits.push(trait_decl);
+    }
+    for use_decl in &module.uses {
+        let content = contents.entry(use_decl.source.module()).or_default();
+        content.uses.push(use_decl);
+    }
+    for test in &module.tests {
+        contents
+            .entry(test.source.module())
+            .or_default()
+            .tests
+            .push(test);
+    }
+
     for func in &module.functions {
-        if let Some(receiver) = func.signature.receiver() {
-            let elem = &receiver.param_type;
-            let base_type_ident = match elem {
-                TypeElement::Plain(ty) => &ty.ident,
-                TypeElement::Parametric(ty) => &ty.base_type,
-                _ => {
-                    add_extension_module(&mut extensions, func, elem);
-                    continue;
-                }
+        let declared_in = func.source.module();
+        let Some(receiver) = func.signature.receiver() else {
+            contents
+                .entry(declared_in)
+                .or_default()
+                .functions
+                .push(func);
+            continue;
+        };
+        let elem = &receiver.param_type;
+        let base_type_ident = match elem {
+            TypeElement::Plain(ty) => Some(&ty.ident),
+            TypeElement::Parametric(ty) => Some(&ty.base_type),
+            _ => None,
+        };
+        // Member functions are generated next to their type, preferring a
+        // type of the same module over a type declared elsewhere
+        let type_file = base_type_ident.map(module_name).and_then(|name| {
+            let has_type = |path: &ModulePath| {
+                contents
+                    .get(path)
+                    .is_some_and(|content| content.type_files.contains_key(&name))
             };
-            match type_files.get_mut(&module_name(base_type_ident)) {
-                Some(content) => content.fns.push(func),
-                None => {
-                    add_extension_module(&mut extensions, func, elem);
-                }
+            let type_module = if has_type(declared_in) {
+                Some(declared_in)
+            } else {
+                contents
+                    .keys()
+                    .copied()
+                    .filter(|&path| has_type(path))
+                    .exactly_one()
+                    .ok()
+            };
+            type_module.map(|path| (path, name))
+        });
+        let type_file = match type_file {
+            Some((path, name)) => contents
+                .get_mut(path)
+                .and_then(|content| content.type_files.get_mut(&name)),
+            None => None,
+        };
+        match type_file {
+            Some(content) => content.fns.push(func),
+            None => add_extension_module(
+                &mut contents.entry(declared_in).or_default().extensions,
+                func,
+                elem,
+            ),
+        }
+    }
+
+    let trait_decls = module
+        .traits
+        .iter()
+        .map(|trait_decl| (&trait_decl.item.ident, &trait_decl.item))
+        .collect::<HashMap<_, _>>();
+    let mut files = Vec::new();
+    let outputs = contents
+        .iter()
+        .map(|(&path, content)| {
+            let output = transpile_module_content(
+                path,
+                content,
+                ctx,
+                &trait_decls,
+                &module.modules,
+                &mut files,
+                errors,
+            );
+            (path, output)
+        })
+        .collect::<HashMap<_, _>>();
+    let root_output = &outputs[&root];
+    let child_modules = transpile_child_modules(&root, &outputs);
+
+    // Items of nested modules are reachable from the root unless their name
+    // is ambiguous; extension traits only need to be in scope
+    let mut name_counts: HashMap<String, usize> = HashMap::new();
+    let mut reexports = Vec::new();
+    for (&path, content) in &contents {
+        let names = content
+            .types
+            .iter()
+            .map(|ty| ty.item.ident().to_string())
+            .chain(content.traits.iter().map(|t| t.item.ident.to_string()))
+            .chain(content.functions.iter().map(|func| {
+                mangle_function_name(
+                    func.signature.identifier.as_str(),
+                    func.signature.overload_labels(),
+                )
+            }));
+        for name in names {
+            *name_counts.entry(name.clone()).or_default() += 1;
+            if !path.is_root() {
+                reexports.push((path, name));
             }
-        } else {
-            toplevel_functions.push(func);
         }
     }
+    let reexports = reexports
+        .into_iter()
+        .filter(|(_, name)| name_counts[name] == 1)
+        .map(|(path, name)| format!("pub(crate) use self::{}::{name};", rust_module_path(path)))
+        .chain(
+            contents
+                .iter()
+                .filter(|(path, _)| !path.is_root())
+                .flat_map(|(&path, content)| {
+                    content.extensions.values().map(move |extension| {
+                        format!(
+                            "pub(crate) use self::{}::{} as _;",
+                            rust_module_path(path),
+                            module_extension_name(path, extension.elem)
+                        )
+                    })
+                }),
+        )
+        .join("\n");
 
-    let no_generics = HashSet::new();
-    let imports = transpile_uses(&module.uses);
-    let type_files = type_files;
-    let toplevel_functions = toplevel_functions
+    let unions = module
+        .unions
         .iter()
-        .map(|func| transpile_function(func, ctx, errors, &no_generics))
+        .map(|union| union.transpile(ctx, errors))
         .collect::<Vec<_>>()
         .join("\n\n");
-    let toplevel_functions = toplevel_functions.trim();
-
-    let tests = transpile_tests(&module.tests, &imports, ctx, errors);
-
-    let modules = type_files
-        .keys()
-        .chain(extensions.keys())
-        .map(|id| sanitize_name(id))
-        .map(|mod_name| format!("mod {mod_name};\npub use self::{mod_name}::*;"))
-        .collect::<Vec<_>>()
-        .join("\n");
-    let modules = modules.trim();
 
     let main = module
         .main
@@ -503,75 +715,215 @@ fn transpile_module(
         "pub(crate) const __HAS_CLI_COMMANDS: bool = false;"
     };
 
-    let lib = TranspileOutput {
-        file_name: galvan_module!("rs").into(),
-        content: format!(
+    let lib = TranspileOutput::new(
+        galvan_module!("rs"),
+        &format!(
             "extern crate galvan; #[allow(unused_imports)] pub(crate) use ::galvan::std::*;\n pub(crate) mod {} {{\n{}\nuse crate::*;\n{}\n{}\n{}\n{}\n}}",
             galvan_module!(),
             SUPPRESS_WARNINGS,
-            imports,
+            root_output.imports,
             cli_flag,
-            [modules, toplevel_functions, &main, &cmds, &tests].join("\n\n"),
+            [
+                root_output.files.as_str(),
+                &root_output.traits,
+                &unions,
+                &root_output.functions,
+                &main,
+                &cmds,
+                &root_output.tests,
+                &reexports,
+                &child_modules,
+            ]
+            .join("\n\n"),
             cli_main
-        )
-        .into(),
+        ),
+    );
+
+    Ok(files.into_iter().chain(iter::once(lib)).collect())
+}
+
+/// Prints the collected errors and warnings with their annotated source
+/// lines
+pub fn report_diagnostics(errors: &ErrorCollector, output: DiagnosticOutput) {
+    for diagnostic in errors.diagnostics() {
+        let directive = match diagnostic.severity {
+            DiagnosticSeverity::Error => "cargo::error",
+            DiagnosticSeverity::Warning => "cargo::warning",
+            DiagnosticSeverity::Info => continue,
+        };
+        match output {
+            // Cargo only takes single-line directives
+            DiagnosticOutput::Cargo => {
+                for line in errors.render(diagnostic).lines() {
+                    println!("{directive}={line}");
+                }
+            }
+            DiagnosticOutput::Terminal => eprintln!("{}", errors.render(diagnostic)),
+            DiagnosticOutput::Json => eprintln!("{}", errors.render_json(diagnostic)),
+        }
+    }
+
+    if output == DiagnosticOutput::Terminal {
+        let first_code = errors.errors().find_map(|diagnostic| diagnostic.code);
+        if let Some(code) = first_code {
+            eprintln!(
+                "For more information about an error, try `galvan-transpile explain {code}`."
+            );
+        }
+    }
+}
+
+/// Generates the items of a module. The files generated for its types and
+/// extensions are added to `files`.
+fn transpile_module_content(
+    path: &ModulePath,
+    content: &ModuleContent,
+    ctx: &Context,
+    trait_decls: &HashMap<&TypeIdent, &TraitDecl>,
+    modules: &[ModulePath],
+    files: &mut Vec<TranspileOutput>,
+    errors: &mut ErrorCollector,
+) -> ModuleOutput {
+    // Types of the module shadow types of the same name in other modules
+    let ctx = &ctx.with_types(content.types.iter().copied());
+    let no_generics = HashSet::new();
+    let imports = transpile_uses(&content.uses, modules);
+    // Files and tests of nested modules see the items and imports of their
+    // module through `super`, so that its types shadow types of the same
+    // name declared elsewhere
+    let scope = if path.is_root() {
+        format!("use crate::*;\n{imports}")
+    } else {
+        "use super::*;".to_owned()
     };
+    let functions = content
+        .functions
+        .iter()
+        .map(|func| transpile_function(func, ctx, errors, &no_generics))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        .trim()
+        .to_owned();
+
+    let tests = transpile_tests(&content.tests, &scope, ctx, errors);
 
-    let type_files = type_files
+    let traits = content
+        .traits
         .iter()
-        .map(|(k, v)| TranspileOutput {
-            file_name: format!("{k}.rs").into(),
-            content: [
-                "use crate::*;",
-                &imports,
+        .map(|trait_decl| trait_decl.item.transpile(ctx, errors))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let file_decls = content
+        .type_files
+        .iter()
+        .map(|(id, file)| {
+            let mod_name = sanitize_name(id);
+            if path.is_root() {
+                format!("mod {mod_name};\npub use self::{mod_name}::*;")
+            } else {
+                // Explicitly imported types shadow those imported by globs
+                format!(
+                    "mod {mod_name};\npub use self::{mod_name}::{};",
+                    file.ty.ident()
+                )
+            }
+        })
+        .chain(content.extensions.keys().map(|id| {
+            let mod_name = sanitize_name(id);
+            format!("mod {mod_name};\npub use self::{mod_name}::*;")
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned();
+
+    let dir = path
+        .segments()
+        .map(|segment| format!("{segment}/"))
+        .collect::<String>();
+    for (k, v) in &content.type_files {
+        files.push(TranspileOutput::new(
+            format!("{dir}{k}.rs"),
+            [
+                scope.as_str(),
                 &v.ty.transpile(ctx, errors),
-                &transpile_member_functions(v.ty, &v.fns, ctx, errors),
+                &v.operators
+                    .iter()
+                    .map(|operator| operator.transpile(ctx, errors))
+                    .join("\n\n"),
+                &transpile_member_functions(v.ty, &v.fns, trait_decls, ctx, errors),
             ]
             .join("\n\n")
-            .trim()
-            .into(),
-        })
-        .collect_vec();
+            .trim(),
+        ));
+    }
 
-    let extension_files = extensions
-        .iter()
-        .map(|(k, v)| TranspileOutput {
-            file_name: format!("{k}.rs").into(),
-            content: [
-                "use crate::*;",
-                &imports,
-                &transpile_extension_functions(v.elem, &v.fns, ctx, errors),
+    for (k, v) in &content.extensions {
+        files.push(TranspileOutput::new(
+            format!("{dir}{k}.rs"),
+            [
+                scope.as_str(),
+                &transpile_extension_functions(
+                    &module_extension_name(path, v.elem),
+                    v.elem,
+                    &v.fns,
+                    ctx,
+                    errors,
+                ),
             ]
             .join("\n\n")
-            .trim()
-            .into(),
-        })
-        .collect_vec();
+            .trim(),
+        ));
+    }
 
-    // Output any collected warnings
-    for diagnostic in errors.diagnostics() {
-        match diagnostic.severity {
-            DiagnosticSeverity::Error => {
-                println!("cargo::error={}", diagnostic.message);
-                std::process::exit(1);
-            }
-            DiagnosticSeverity::Warning => {
-                println!("cargo::warning={}", diagnostic.message);
-            }
-            _ => {}
-        }
+    ModuleOutput {
+        imports,
+        files: file_decls,
+        traits,
+        functions,
+        tests,
     }
+}
 
-    Ok(type_files
-        .into_iter()
-        .chain(extension_files.into_iter())
-        .chain(iter::once(lib))
-        .collect())
+/// Generates the modules nested in `parent`, each with its own nested modules
+fn transpile_child_modules(
+    parent: &ModulePath,
+    outputs: &HashMap<&ModulePath, ModuleOutput>,
+) -> String {
+    outputs
+        .keys()
+        .copied()
+        .filter(|path| path.parent().as_ref() == Some(parent))
+        .sorted()
+        .map(|path| {
+            let output = &outputs[path];
+            let children = transpile_child_modules(path, outputs);
+            format!(
+                "pub(crate) mod {} {{\nuse crate::*;\n{}\n{}\n}}",
+                sanitize_name(path.name().unwrap_or_default()),
+                output.imports,
+                [
+                    output.files.as_str(),
+                    &output.traits,
+                    &output.functions,
+                    &output.tests,
+                    &children,
+                ]
+                .join("\n\n")
+            )
+        })
+        .join("\n\n")
+}
+
+/// The path of a nested module relative to the generated root module
+fn rust_module_path(path: &ModulePath) -> String {
+    path.segments().map(sanitize_name).join("::")
 }
 
 fn transpile_tests(
-    tests: &[HirTest],
-    imports: &str,
+    tests: &[&HirTest],
+    scope: &str,
     ctx: &Context,
     errors: &mut ErrorCollector,
 ) -> String {
@@ -598,7 +950,7 @@ fn transpile_tests(
     }
 
     let mut by_name: HashMap<Cow<'_, str>, Vec<&HirTest>> = HashMap::new();
-    for test in tests {
+    for &test in tests {
         by_name.entry(test_name(&test.name)).or_default().push(test);
     }
 
@@ -621,7 +973,7 @@ fn transpile_tests(
         return "".into();
     }
 
-    let test_mod = format!("#[cfg(test)]\nmod tests {{\nuse crate::*;\n{imports}\n")
+    let test_mod = format!("#[cfg(test)]\nmod tests {{\n{scope}\n")
         + resolved_tests
             .iter()
             .map(|(name, test)| transpile_test(name, test, ctx, errors))
@@ -636,16 +988,42 @@ fn transpile_tests(
 fn transpile_member_functions(
     ty: &TypeDecl,
     fns: &[&HirFunction],
+    trait_decls: &HashMap<&TypeIdent, &TraitDecl>,
     ctx: &Context,
     errors: &mut ErrorCollector,
 ) -> String {
-    if fns.is_empty() {
+    // Member functions required by a trait the type conforms to are moved
+    // into the impl block of that trait
+    let mut trait_impls = ty
+        .conformances()
+        .iter()
+        .filter_map(|conformance| trait_decls.get(conformance).copied())
+        .map(|trait_decl| (trait_decl, Vec::new()))
+        .collect::<Vec<_>>();
+    let mut inherent_fns = Vec::new();
+    for &func in fns {
+        let labels = func.signature.overload_labels();
+        let labels = labels
+            .iter()
+            .map(|label| label.as_str())
+            .collect::<Vec<_>>();
+        let name = func.signature.identifier.as_str();
+        match trait_impls
+            .iter_mut()
+            .find(|(trait_decl, _)| trait_decl.member(name, &labels).is_some())
+        {
+            Some((_, trait_fns)) => trait_fns.push(func),
+            None => inherent_fns.push(func),
+        }
+    }
+
+    if inherent_fns.is_empty() && trait_impls.is_empty() {
         return "".into();
     }
 
     // Collect generic parameters from the type declaration; they are
     // declared on the impl block and skipped on the member functions
-    let generics = ty.collect_generics();
+    let generics = ty.generic_params();
     let generic_params = if generics.is_empty() {
         String::new()
     } else {
@@ -679,36 +1057,51 @@ fn transpile_member_functions(
         )
     };
 
-    let transpiled_fns = fns
+    let skip_generics = generics.iter().cloned().collect::<HashSet<_>>();
+    let inherent_impl = if inherent_fns.is_empty() {
+        String::new()
+    } else {
+        let transpiled_fns = inherent_fns
+            .iter()
+            .map(|f| transpile_function(f, ctx, errors, &skip_generics))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        format!(
+            "impl{} {} {{\n{transpiled_fns}\n}}",
+            generic_params, type_name
+        )
+    };
+
+    let trait_impls = trait_impls
         .iter()
-        .map(|f| transpile_function(f, ctx, errors, &generics))
-        .collect::<Vec<_>>()
-        .join("\n\n");
+        .map(|(trait_decl, trait_fns)| {
+            let transpiled_fns = trait_fns
+                .iter()
+                .map(|f| transpile_trait_function(f, ctx, errors, &skip_generics))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            format!(
+                "impl{} {} for {} {{\n{transpiled_fns}\n}}",
+                generic_params, trait_decl.ident, type_name
+            )
+        })
+        .collect::<Vec<_>>();
 
-    format!(
-        "impl{} {} {{\n{transpiled_fns}\n}}",
-        generic_params, type_name
-    )
+    iter::once(inherent_impl)
+        .chain(trait_impls)
+        .filter(|block| !block.is_empty())
+        .join("\n\n")
 }
 
 fn transpile_extension_functions(
+    trait_name: &str,
     ty: &TypeElement,
     fns: &[&HirFunction],
     ctx: &Context,
     errors: &mut ErrorCollector,
 ) -> String {
     debug_assert_ne!(fns.len(), 0, "Extension functions should not be empty");
-    if fns
-        .iter()
-        .find(|f| f.signature.visibility.kind != VisibilityKind::Inherited)
-        .is_some()
-    {
-        // TODO: Add proper error handling for invalid member function visibility
-        return String::new();
-    }
-
     let no_generics = HashSet::new();
-    let trait_name = extension_name(&ty);
     let fn_signatures = fns
         .iter()
         .map(|f| FnSignature {
@@ -722,7 +1115,14 @@ fn transpile_extension_functions(
     let transpiled_fns = fns
         .iter()
         .map(|f| transpile_function(f, ctx, errors, &no_generics))
-        .map(|s| s.strip_prefix("pub(crate) ").unwrap().to_owned())
+        // Trait members take the visibility of the trait, which is `pub` so
+        // that `pub` extensions can be used from other modules
+        .map(|s| {
+            s.strip_prefix("pub(crate) ")
+                .or_else(|| s.strip_prefix("pub "))
+                .unwrap_or(&s)
+                .to_owned()
+        })
         .collect::<Vec<_>>()
         .join("\n\n");
 
@@ -883,6 +1283,14 @@ fn extension_name(ty: &TypeElement) -> String {
                     .join("_");
                 format!("Closure_{}__{}", args, escaped_name(&ty.return_ty))
             }
+            TypeElement::Union(ty) => format!(
+                "Union_{}",
+                union_members(ty)
+                    .iter()
+                    .map(escaped_name)
+                    .collect::<Vec<_>>()
+                    .join("_")
+            ),
             TypeElement::Void(_) => format!("Void"),
             TypeElement::Infer(_) => format!("Infer"),
             TypeElement::Never(_) => format!("Never"),
@@ -895,6 +1303,21 @@ fn extension_name(ty: &TypeElement) -> String {
 pub struct TranspileOutput {
     pub file_name: Box<str>,
     pub content: Box<str>,
+    /// Maps the generated code back to the Galvan code it was generated from
+    pub source_map: SourceMap,
+}
+
+impl TranspileOutput {
+    /// Creates the output from generated code that still contains source
+    /// map markers
+    fn new(file_name: impl Into<Box<str>>, marked: &str) -> Self {
+        let (content, source_map) = source_map::extract(marked);
+        Self {
+            file_name: file_name.into(),
+            content: content.into(),
+            source_map,
+        }
+    }
 }
 
 pub struct TranspileErrors<'t> {
@@ -908,8 +1331,33 @@ impl TranspileErrors<'_> {
     }
 }
 
+/// Transpiles the sources. If any errors are found, all diagnostics are
+/// returned as [`TranspileError::Diagnostics`].
+///
+/// The warnings of a successful build are discarded; use
+/// [`transpile_with_diagnostics`] to report them.
 pub fn transpile(sources: Vec<Source>) -> Result<Vec<TranspileOutput>, TranspileError> {
-    transpile_sources(sources)
+    transpile_with(sources, &[])
+}
+
+/// Transpiles the sources with calls into Rust crates checked against the
+/// APIs imported from their rustdoc JSON
+///
+/// Like [`transpile`], this discards the warnings of a successful build.
+pub fn transpile_with(
+    sources: Vec<Source>,
+    crates: &[RustdocCrate],
+) -> Result<Vec<TranspileOutput>, TranspileError> {
+    transpile_sources(sources, crates).map(|(files, _)| files)
+}
+
+/// Like [`transpile_with`], but also returns the warnings found in sources
+/// that transpiled without errors
+pub fn transpile_with_diagnostics(
+    sources: Vec<Source>,
+    crates: &[RustdocCrate],
+) -> Result<(Vec<TranspileOutput>, ErrorCollector), TranspileError> {
+    transpile_sources(sources, crates)
 }
 
 mod transpile_item;
@@ -976,7 +1424,7 @@ mod macros {
 
 use crate::context::{predefined_from, Context};
 use crate::macros::transpile;
-use crate::sanitize::sanitize_name;
+use crate::sanitize::{mangle_function_name, sanitize_name};
 use galvan_hir::builtins::{builtin_fns, builtins};
 use macros::punct;
 
@@ -1,12 +1,39 @@
-use std::env;
+use std::{env, io};
 
-use galvan_transpiler::exec::transpile_dir;
+use galvan_transpiler::exec::{rewrite_rustc_diagnostics, transpile_dir};
+use galvan_transpiler::{explain, report_diagnostics, DiagnosticOutput, TranspileError};
 
 #[allow(clippy::redundant_closure)]
 fn main() {
+    if let Some("explain") = env::args().nth(1).as_deref() {
+        let code = env::args().nth(2).unwrap_or_default().to_uppercase();
+        match explain(&code) {
+            Some(explanation) => print!("{explanation}"),
+            None => {
+                eprintln!("error: `{code}` is not a Galvan diagnostic code");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some("rustc-diagnostics") = env::args().nth(1).as_deref() {
+        let rendered = env::args().skip(2).any(|arg| arg == "--rendered");
+        rewrite_rustc_diagnostics(io::stdin().lock(), io::stdout().lock(), rendered).unwrap();
+        return;
+    }
+
+    let mut output = DiagnosticOutput::Terminal;
     let args: Vec<String> = env::args()
         .skip(1)
         .filter(|s| !s.trim().is_empty())
+        .filter(|s| match s.as_str() {
+            "--error-format=json" => {
+                output = DiagnosticOutput::Json;
+                false
+            }
+            _ => true,
+        })
         .map(|s| {
             if !s.ends_with(".galvan") {
                 s + ".galvan"
@@ -18,7 +45,20 @@ fn main() {
 
     let current_dir = env::current_dir().unwrap();
     // println!("Args: {:?}", args);
-    let transpiled = transpile_dir(current_dir, args).unwrap();
+    let transpiled = match transpile_dir(current_dir, args) {
+        Ok((transpiled, warnings)) => {
+            report_diagnostics(&warnings, output);
+            transpiled
+        }
+        Err(TranspileError::Diagnostics(errors)) => {
+            report_diagnostics(&errors, output);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+    };
 
     for output in transpiled {
         println!();
@@ -5,6 +5,9 @@ use galvan_ast::{Ident, UsePath};
 pub(crate) fn sanitize_name(name: &str) -> Cow<'_, str> {
     if RUST_KEYWORDS.contains(&name) {
         format!("r#{}", name).into()
+    } else if let Some(index) = name.strip_prefix('#') {
+        // Numbered closure parameters; `__` cannot occur in Galvan identifiers
+        format!("__{index}").into()
     } else {
         name.into()
     }
@@ -22,10 +25,17 @@ pub(crate) fn mangle_function_name<'a>(
     name
 }
 
+/// Paths into this crate start with `crate` and continue in the generated
+/// module that contains all Galvan items
 pub(crate) fn sanitize_path(path: &UsePath) -> String {
     path.segments
         .iter()
-        .map(|segment| sanitize_name(segment.as_str()).into_owned())
+        .enumerate()
+        .map(|(i, segment)| match segment.as_str() {
+            "crate" if i == 0 => concat!("crate::", galvan_module!()).to_owned(),
+            "self" | "super" if i == 0 => segment.as_str().to_owned(),
+            name => sanitize_name(name).into_owned(),
+        })
         .collect::<Vec<_>>()
         .join("::")
 }
@@ -0,0 +1,415 @@
+//! Maps byte ranges of the generated Rust code back to the Galvan code they
+//! were generated from, and rewrites rustc diagnostics with it.
+//!
+//! Code generation surrounds the code of every expression with markers that
+//! hold the file and span of the expression. The markers are stripped from
+//! the finished files and turned into a [`SourceMap`] per file. Marker
+//! characters in string literals are emitted as escapes (see
+//! [`escape_markers`]), so they never reach the generated code unescaped.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use galvan_ast::{Point, Span};
+use serde_json::{json, Value};
+
+use crate::ErrorCollector;
+
+const OPEN: char = '\u{E000}';
+const SEPARATOR: char = '\u{E001}';
+const CLOSE: char = '\u{E002}';
+
+/// Marks `code` as generated from `span` in the file that is currently being
+/// transpiled
+pub(crate) fn mark(code: String, span: Span, errors: &ErrorCollector) -> String {
+    let Some(file) = errors.current_file() else {
+        return code;
+    };
+    // Spans of synthesized nodes do not point into the source
+    if span.range == (0, 0) {
+        return code;
+    }
+    format!(
+        "{OPEN}{file}{SEPARATOR}{} {} {} {} {} {}{SEPARATOR}{code}{CLOSE}",
+        span.range.0, span.range.1, span.start.row, span.start.col, span.end.row, span.end.col
+    )
+}
+
+/// Replaces marker characters in the code of a Rust string literal with
+/// `\u{...}` escapes, which denote the same characters
+pub(crate) fn escape_markers(literal: &str) -> Cow<'_, str> {
+    if !literal.contains([OPEN, SEPARATOR, CLOSE]) {
+        return Cow::Borrowed(literal);
+    }
+    literal
+        .chars()
+        .map(|c| match c {
+            OPEN | SEPARATOR | CLOSE => c.escape_unicode().to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Removes the markers from generated code, returning the code and the
+/// source map recorded by the markers. Marker characters that do not form a
+/// marker are kept as they are.
+pub(crate) fn extract(marked: &str) -> (String, SourceMap) {
+    let mut code = String::with_capacity(marked.len());
+    let mut open = Vec::new();
+    let mut mappings = Vec::new();
+
+    let mut rest = marked;
+    while let Some(position) = rest.find([OPEN, CLOSE]) {
+        code.push_str(&rest[..position]);
+        let marker = &rest[position..];
+        if let Some(marker) = marker.strip_prefix(OPEN) {
+            match parse_marker(marker) {
+                Some((file, span, marker)) => {
+                    open.push((code.len(), file, span));
+                    rest = marker;
+                }
+                None => {
+                    code.push(OPEN);
+                    rest = marker;
+                }
+            }
+        } else {
+            match open.pop() {
+                Some((start, file, span)) => mappings.push(Mapping {
+                    generated: start..code.len(),
+                    file: file.to_owned(),
+                    span,
+                }),
+                None => code.push(CLOSE),
+            }
+            rest = &marker[CLOSE.len_utf8()..];
+        }
+    }
+    code.push_str(rest);
+
+    mappings.sort_by_key(|mapping| (mapping.generated.start, mapping.generated.end));
+    (code, SourceMap { mappings })
+}
+
+/// Splits the file and span off the rest of a marker that follows `OPEN`
+fn parse_marker(marker: &str) -> Option<(&str, Span, &str)> {
+    let (file, marker) = marker.split_once(SEPARATOR)?;
+    let (span, marker) = marker.split_once(SEPARATOR)?;
+    Some((file, parse_span(span)?, marker))
+}
+
+fn parse_span(span: &str) -> Option<Span> {
+    let numbers = span
+        .split(' ')
+        .map(|number| number.parse().ok())
+        .collect::<Option<Vec<usize>>>()?;
+    let [start, end, start_row, start_col, end_row, end_col] = numbers[..] else {
+        return None;
+    };
+    Some(Span {
+        range: (start, end),
+        start: Point {
+            row: start_row,
+            col: start_col,
+        },
+        end: Point {
+            row: end_row,
+            col: end_col,
+        },
+    })
+}
+
+/// The Galvan code that ranges of a generated file were generated from
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    mappings: Vec<Mapping>,
+}
+
+/// A range of generated code and the Galvan code it was generated from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    /// Byte range in the generated file
+    pub generated: Range<usize>,
+    /// Path of the Galvan file, or empty for sources that are not files
+    pub file: String,
+    pub span: Span,
+}
+
+impl SourceMap {
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// The innermost mapping that contains the generated range, or else the
+    /// innermost one that contains its start
+    pub fn lookup(&self, generated: Range<usize>) -> Option<&Mapping> {
+        let innermost = |contains: &dyn Fn(&Mapping) -> bool| {
+            self.mappings
+                .iter()
+                .filter(|mapping| contains(mapping))
+                .min_by_key(|mapping| mapping.generated.len())
+        };
+        innermost(&|mapping| {
+            mapping.generated.start <= generated.start && generated.end <= mapping.generated.end
+        })
+        .or_else(|| innermost(&|mapping| mapping.generated.contains(&generated.start)))
+    }
+
+    pub fn to_json(&self) -> String {
+        let mappings = self
+            .mappings
+            .iter()
+            .map(|mapping| {
+                json!({
+                    "generated": [mapping.generated.start, mapping.generated.end],
+                    "file": mapping.file,
+                    "range": [mapping.span.range.0, mapping.span.range.1],
+                    "start": [mapping.span.start.row, mapping.span.start.col],
+                    "end": [mapping.span.end.row, mapping.span.end.col],
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "version": 1, "mappings": mappings }).to_string()
+    }
+
+    /// Reads a source map written by [`Self::to_json`]
+    pub fn from_json(json: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(json).ok()?;
+        let pair = |value: &Value| -> Option<(usize, usize)> {
+            Some((
+                value.get(0)?.as_u64()? as usize,
+                value.get(1)?.as_u64()? as usize,
+            ))
+        };
+        let mappings = value
+            .get("mappings")?
+            .as_array()?
+            .iter()
+            .map(|mapping| {
+                let generated = pair(mapping.get("generated")?)?;
+                let range = pair(mapping.get("range")?)?;
+                let start = pair(mapping.get("start")?)?;
+                let end = pair(mapping.get("end")?)?;
+                Some(Mapping {
+                    generated: generated.0..generated.1,
+                    file: mapping.get("file")?.as_str()?.to_owned(),
+                    span: Span {
+                        range,
+                        start: Point {
+                            row: start.0,
+                            col: start.1,
+                        },
+                        end: Point {
+                            row: end.0,
+                            col: end.1,
+                        },
+                    },
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { mappings })
+    }
+}
+
+/// The source maps of generated files and the content of the Galvan files
+/// they point into, used to rewrite rustc diagnostics
+#[derive(Clone, Debug, Default)]
+pub struct SourceMaps {
+    maps: HashMap<String, SourceMap>,
+    sources: HashMap<String, String>,
+}
+
+impl SourceMaps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the source map of the generated file at `path`, the way rustc
+    /// names the file in its diagnostics
+    pub fn insert(&mut self, path: impl Into<String>, map: SourceMap) {
+        self.maps.insert(path.into(), map);
+    }
+
+    /// Adds the content of a Galvan file to quote it in rewritten diagnostics
+    pub fn add_source(&mut self, file: impl Into<String>, content: impl Into<String>) {
+        self.sources.insert(file.into(), content.into());
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.maps.contains_key(path)
+    }
+
+    /// Rewrites a line of rustc's `--error-format=json` or cargo's
+    /// `--message-format=json` output to point at the Galvan code that the
+    /// reported Rust code was generated from. Other lines are returned as is.
+    pub fn rewrite_json(&self, line: &str) -> String {
+        let Ok(mut value) = serde_json::from_str::<Value>(line) else {
+            return line.to_owned();
+        };
+        let rewritten =
+            diagnostic_mut(&mut value).is_some_and(|diagnostic| self.rewrite(diagnostic));
+        if rewritten {
+            value.to_string()
+        } else {
+            line.to_owned()
+        }
+    }
+
+    /// Rewrites the spans of a rustc diagnostic that point into generated
+    /// files, and re-renders it if any were rewritten
+    fn rewrite(&self, diagnostic: &mut Value) -> bool {
+        if !self.rewrite_spans(diagnostic) {
+            return false;
+        }
+        let rendered = self.render(diagnostic);
+        diagnostic["rendered"] = Value::String(rendered);
+        true
+    }
+
+    fn rewrite_spans(&self, diagnostic: &mut Value) -> bool {
+        let mut rewritten = false;
+        if let Some(spans) = diagnostic.get_mut("spans").and_then(Value::as_array_mut) {
+            for span in spans {
+                rewritten |= self.rewrite_span(span);
+            }
+        }
+        if let Some(children) = diagnostic.get_mut("children").and_then(Value::as_array_mut) {
+            for child in children {
+                rewritten |= self.rewrite_spans(child);
+            }
+        }
+        rewritten
+    }
+
+    fn rewrite_span(&self, span: &mut Value) -> bool {
+        let Some(mapping) = self.mapping(span) else {
+            return false;
+        };
+        let file_name = if mapping.file.is_empty() {
+            "<input>"
+        } else {
+            &mapping.file
+        };
+        // rustc counts columns in characters, spans count them in bytes
+        let line = |row: usize| {
+            self.sources
+                .get(&mapping.file)
+                .and_then(|source| source.lines().nth(row))
+        };
+        let start_line = line(mapping.span.start.row);
+        let column_start = char_column(start_line, mapping.span.start.col);
+        let column_end = char_column(line(mapping.span.end.row), mapping.span.end.col);
+        let text = start_line.map_or_else(Vec::new, |line| {
+            let end = if mapping.span.end.row == mapping.span.start.row {
+                column_end
+            } else {
+                line.chars().count() + 1
+            };
+            vec![json!({
+                "text": line,
+                "highlight_start": column_start,
+                "highlight_end": end.max(column_start + 1),
+            })]
+        });
+
+        span["file_name"] = json!(file_name);
+        span["byte_start"] = json!(mapping.span.range.0);
+        span["byte_end"] = json!(mapping.span.range.1);
+        span["line_start"] = json!(mapping.span.start.row + 1);
+        span["line_end"] = json!(mapping.span.end.row + 1);
+        span["column_start"] = json!(column_start);
+        span["column_end"] = json!(column_end);
+        span["text"] = json!(text);
+        span["expansion"] = Value::Null;
+        // Replacements of Rust code do not apply to the Galvan code
+        span["suggested_replacement"] = Value::Null;
+        span["suggestion_applicability"] = Value::Null;
+        true
+    }
+
+    fn mapping(&self, span: &Value) -> Option<&Mapping> {
+        let map = self.maps.get(span.get("file_name")?.as_str()?)?;
+        let start = span.get("byte_start")?.as_u64()? as usize;
+        let end = span.get("byte_end")?.as_u64()? as usize;
+        map.lookup(start..end)
+    }
+
+    /// Renders a rewritten diagnostic like rustc does, quoting the Galvan
+    /// code the spans point at
+    fn render(&self, diagnostic: &Value) -> String {
+        let field = |value: &Value, key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        let level = field(diagnostic, "level");
+        let message = field(diagnostic, "message");
+        let mut rendered = match diagnostic.pointer("/code/code").and_then(Value::as_str) {
+            Some(code) => format!("{level}[{code}]: {message}\n"),
+            None => format!("{level}: {message}\n"),
+        };
+
+        for span in diagnostic
+            .get("spans")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let line = span.get("line_start").and_then(Value::as_u64).unwrap_or(1);
+            let column = span
+                .get("column_start")
+                .and_then(Value::as_u64)
+                .unwrap_or(1);
+            let gutter = " ".repeat(line.to_string().len());
+            rendered += &format!("{gutter}--> {}:{line}:{column}\n", field(span, "file_name"));
+
+            if let Some(text) = span.pointer("/text/0") {
+                let start = text["highlight_start"].as_u64().unwrap_or(1) as usize;
+                let end = text["highlight_end"].as_u64().unwrap_or(2) as usize;
+                let label = field(span, "label");
+                rendered += &format!(
+                    "{gutter} |\n{line} | {}\n{gutter} | {}{} {label}\n",
+                    field(text, "text"),
+                    " ".repeat(start.saturating_sub(1)),
+                    "^".repeat(end.saturating_sub(start).max(1)),
+                );
+            }
+        }
+
+        for child in diagnostic
+            .get("children")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            rendered += &format!(
+                "  = {}: {}\n",
+                field(child, "level"),
+                field(child, "message")
+            );
+        }
+        rendered
+    }
+}
+
+/// The 1-based character column of a byte column in `line`, or the byte
+/// column if the line is not known
+fn char_column(line: Option<&str>, byte_column: usize) -> usize {
+    let preceding = line.and_then(|line| line.get(..byte_column.min(line.len())));
+    preceding.map_or(byte_column, |code| code.chars().count()) + 1
+}
+
+/// The rustc diagnostic in a line of rustc or cargo JSON output
+pub(crate) fn diagnostic_mut(value: &mut Value) -> Option<&mut Value> {
+    if value.get("reason").and_then(Value::as_str) == Some("compiler-message") {
+        return value.get_mut("message");
+    }
+    (value.get("$message_type").and_then(Value::as_str) == Some("diagnostic")).then_some(value)
+}
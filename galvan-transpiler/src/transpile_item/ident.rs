@@ -1,8 +1,9 @@
 use crate::context::Context;
 use crate::sanitize::sanitize_name;
-use crate::ErrorCollector;
 use crate::Transpile;
+use crate::{ErrorCollector, TranspilerWarning};
 use galvan_ast::{Ident, TypeIdent};
+use galvan_hir::typecheck::is_union_ident;
 
 impl Transpile for Ident {
     fn transpile(&self, _ctx: &Context, _errors: &mut ErrorCollector) -> String {
@@ -13,9 +14,14 @@ impl Transpile for Ident {
 
 impl Transpile for TypeIdent {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        if is_union_ident(self) || is_self_type(self) {
+            return format!("{self}");
+        }
         let Some(_decl) = ctx.lookup.types.get(self) else {
             errors.warning(
-                format!("Type resolving error: Type {} not found", self),
+                TranspilerWarning::UnresolvedType {
+                    name: self.to_string(),
+                },
                 None,
             );
             return format!("{self}");
@@ -26,6 +32,11 @@ impl Transpile for TypeIdent {
     }
 }
 
+/// `Self` in trait member signatures refers to the conforming type
+fn is_self_type(ident: &TypeIdent) -> bool {
+    ident.as_str() == "Self"
+}
+
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)]
 pub enum TypeOwnership {
@@ -52,10 +63,14 @@ impl TranspileType for TypeIdent {
         errors: &mut ErrorCollector,
     ) -> String {
         let Some(_decl) = ctx.lookup.types.get(self) else {
-            errors.warning(
-                format!("Type resolving error: Type {} not found", self),
-                None,
-            );
+            if !is_self_type(self) {
+                errors.warning(
+                    TranspilerWarning::UnresolvedType {
+                        name: self.to_string(),
+                    },
+                    None,
+                );
+            }
             let prefix = match ownership {
                 TypeOwnership::Owned | TypeOwnership::MutOwned => "",
                 TypeOwnership::Borrowed => "&",
@@ -1,4 +1,7 @@
 pub(crate) mod ident;
+mod operator;
 mod r#struct;
+mod r#trait;
 mod r#type;
+mod union;
 mod visibility;
@@ -0,0 +1,52 @@
+use crate::context::Context;
+use crate::{ErrorCollector, Transpile};
+use galvan_ast::{ArithmeticOperator, InfixOperator, TypeDecl};
+use galvan_hir::hir::HirOperatorImpl;
+use itertools::Itertools;
+
+impl Transpile for HirOperatorImpl {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        let Some(TypeDecl::Struct(def)) = ctx.lookup.types.get(&self.ty).map(|decl| &decl.item)
+        else {
+            errors.error(crate::TranspilerError::InvalidOperationOnType {
+                operation: format!("deriving `{}` for {}", self.operator.symbol(), self.ty),
+                allowed_types: "struct types".to_string(),
+            });
+            return String::new();
+        };
+
+        let (op_trait, op_fn, symbol) = match self.operator {
+            ArithmeticOperator::Add => ("Add", "add", "+"),
+            ArithmeticOperator::Sub => ("Sub", "sub", "-"),
+            ArithmeticOperator::Mul => ("Mul", "mul", "*"),
+            ArithmeticOperator::Div => ("Div", "div", "/"),
+            ArithmeticOperator::Rem | ArithmeticOperator::Exp => {
+                errors.error(crate::TranspilerError::InvalidOperationOnType {
+                    operation: format!("`{}` on {}", self.operator.symbol(), self.ty),
+                    allowed_types: "numbers, since structs only derive `+`, `-`, `*` and `/`"
+                        .to_string(),
+                });
+                return String::new();
+            }
+        };
+
+        let ident = self.ty.transpile(ctx, errors);
+        let fields = def
+            .members
+            .iter()
+            .map(|member| {
+                let field = member.ident.transpile(ctx, errors);
+                format!("{field}: self.{field} {symbol} rhs.{field}")
+            })
+            .join(",\n");
+
+        format!(
+            "impl ::std::ops::{op_trait} for {ident} {{\n\
+             type Output = Self;\n\
+             fn {op_fn}(self, rhs: Self) -> Self {{\n\
+             Self {{\n{fields}\n}}\n\
+             }}\n\
+             }}"
+        )
+    }
+}
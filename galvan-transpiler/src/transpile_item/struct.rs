@@ -2,7 +2,6 @@ use crate::context::Context;
 use crate::macros::{impl_transpile, transpile};
 use crate::{ErrorCollector, Transpile};
 use galvan_ast::{DeclModifier, EnumTypeMember, StructTypeMember, TupleTypeMember, TypeDecl};
-use std::collections::HashSet;
 
 static DERIVE: &str = "#[derive(Clone, Debug, PartialEq)]";
 
@@ -10,11 +9,7 @@ impl Transpile for TypeDecl {
     fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
         match self {
             TypeDecl::Tuple(def) => {
-                // Collect generic parameters from tuple members
-                let mut generics = HashSet::new();
-                for member in &def.members {
-                    member.r#type.collect_generics_recursive(&mut generics);
-                }
+                let generics = self.generic_params();
 
                 let generic_params = if generics.is_empty() {
                     String::new()
@@ -40,11 +35,7 @@ impl Transpile for TypeDecl {
                 format!("{DERIVE} {visibility} struct {ident}{generic_params}({members});")
             }
             TypeDecl::Struct(def) => {
-                // Collect generic parameters from struct members
-                let mut generics = HashSet::new();
-                for member in &def.members {
-                    member.r#type.collect_generics_recursive(&mut generics);
-                }
+                let generics = self.generic_params();
 
                 let generic_params = if generics.is_empty() {
                     String::new()
@@ -76,10 +67,25 @@ impl Transpile for TypeDecl {
                 format!("{DERIVE} {visibility} enum {ident} {{\n{members}\n}}")
             }
             TypeDecl::Alias(def) => {
+                let generics = self.generic_params();
+
+                // Rust does not enforce bounds on type aliases, so the
+                // parameters are left unbounded
+                let generic_params = if generics.is_empty() {
+                    String::new()
+                } else {
+                    let params = generics
+                        .iter()
+                        .map(|g| crate::capitalize_generic(g.as_str()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("<{}>", params)
+                };
+
                 let visibility = def.visibility.transpile(ctx, errors);
                 let ident = def.ident.transpile(ctx, errors);
                 let r#type = def.r#type.transpile(ctx, errors);
-                format!("{visibility} type {ident} = {type};")
+                format!("{visibility} type {ident}{generic_params} = {type};")
             }
             TypeDecl::Empty(def) => {
                 let visibility = def.visibility.transpile(ctx, errors);
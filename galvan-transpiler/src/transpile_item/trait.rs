@@ -0,0 +1,25 @@
+use std::collections::HashSet;
+
+use crate::codegen::transpile_signature;
+use crate::context::Context;
+use crate::{ErrorCollector, Transpile};
+use galvan_ast::{FnSignature, TraitDecl, Visibility};
+use itertools::Itertools;
+
+impl Transpile for TraitDecl {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        let no_generics = HashSet::new();
+        let visibility = self.visibility.transpile(ctx, errors);
+        let members = self
+            .members
+            .iter()
+            .map(|member| FnSignature {
+                visibility: Visibility::private(),
+                ..member.clone()
+            })
+            .map(|member| transpile_signature(&member, ctx, errors, &no_generics) + ";")
+            .join("\n");
+
+        format!("{visibility} trait {} {{\n{members}\n}}", self.ident)
+    }
+}
@@ -2,6 +2,7 @@ use crate::context::Context;
 use crate::macros::{impl_transpile, impl_transpile_variants, transpile};
 use crate::{ErrorCollector, Transpile};
 use galvan_ast::*;
+use galvan_hir::typecheck::union_ident;
 use galvan_resolver::Lookup;
 use itertools::Itertools;
 
@@ -101,6 +102,13 @@ impl Transpile for ClosureTypeItem {
     }
 }
 
+impl Transpile for UnionTypeItem {
+    fn transpile(&self, _ctx: &Context, _errors: &mut ErrorCollector) -> String {
+        // The enum itself is generated once per module from `HirModule::unions`
+        union_ident(self).to_string()
+    }
+}
+
 impl_transpile_variants! { TypeElement;
     Plain
     Array
@@ -113,6 +121,7 @@ impl_transpile_variants! { TypeElement;
     Generic
     Parametric
     Closure
+    Union
     Void
     Infer
     Never
@@ -0,0 +1,59 @@
+use crate::context::Context;
+use crate::{ErrorCollector, Transpile};
+use galvan_ast::TypeElement;
+use galvan_hir::hir::HirUnion;
+use itertools::Itertools;
+
+static DERIVE: &str = "#[derive(Clone, Debug, PartialEq)]";
+
+impl Transpile for HirUnion {
+    fn transpile(&self, ctx: &Context, errors: &mut ErrorCollector) -> String {
+        let ident = self.ident.as_str();
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| (variant.case.as_str(), variant.ty.transpile(ctx, errors)))
+            .collect_vec();
+
+        let members = variants
+            .iter()
+            .map(|(case, ty)| format!("{case}({ty})"))
+            .join(",\n");
+        let from_impls = variants
+            .iter()
+            .map(|(case, ty)| {
+                format!(
+                    "impl From<{ty}> for {ident} {{\n\
+                     fn from(value: {ty}) -> Self {{ Self::{case}(value) }}\n\
+                     }}"
+                )
+            })
+            .join("\n\n");
+
+        // Only builtin types are known to implement Display, which makes the
+        // union usable in string interpolation
+        let displays = self.variants.iter().all(|variant| match &variant.ty {
+            TypeElement::Plain(plain) => ctx.mapping.types.contains_key(&plain.ident),
+            _ => false,
+        });
+        let display_impl = if displays {
+            let arms = variants
+                .iter()
+                .map(|(case, _)| format!("Self::{case}(value) => write!(f, \"{{}}\", value),"))
+                .join("\n");
+            format!(
+                "impl ::std::fmt::Display for {ident} {{\n\
+                 fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {{\n\
+                 match self {{\n{arms}\n}}\n\
+                 }}\n\
+                 }}"
+            )
+        } else {
+            String::new()
+        };
+
+        format!("{DERIVE} pub enum {ident} {{\n{members}\n}}\n\n{from_impls}\n\n{display_impl}")
+            .trim()
+            .to_owned()
+    }
+}
@@ -50,9 +50,9 @@ mod test_utils {
 }
 
 #[allow(unused_imports)]
-use galvan_files::Source;
+use galvan_files::{ModulePath, Source};
 #[allow(unused_imports)]
-use galvan_transpiler::{galvan_module, transpile};
+use galvan_transpiler::{galvan_module, transpile, SourceMaps, TranspileError};
 use test_utils::*;
 
 generate_code_tests!(test_transpilation, TRANSPILE, trim_all {
@@ -182,6 +182,40 @@ fn transpiles_namespaced_method_calls_as_scoped_imports() {
     assert!(output.contains("{ use reader::*; book.score__with(5) }"));
 }
 
+#[test]
+fn transpiles_files_as_nested_modules() {
+    let orders = ModulePath::root().child("shop").child("orders");
+    let outputs = transpile(vec![
+        Source::in_module(
+            orders,
+            "pub type Order { count: Int }
+             pub fn place(count: Int) -> Order { Order(count: count) }",
+        ),
+        Source::from_string(
+            "use crate::shop::orders
+             fn order() -> Order { shop::orders::place(2) }",
+        ),
+    ])
+    .unwrap();
+
+    let file_names = outputs
+        .iter()
+        .map(|output| output.file_name.as_ref())
+        .collect::<Vec<_>>();
+    assert!(file_names.contains(&"shop/orders/order.rs"));
+    let lib = &outputs
+        .iter()
+        .find(|output| output.file_name.as_ref() == galvan_module!("rs"))
+        .unwrap()
+        .content;
+    assert!(lib.contains("use crate::galvan_module::shop::orders::*;"));
+    assert!(lib.contains("pub(crate) mod shop {"));
+    assert!(lib.contains("pub(crate) mod orders {"));
+    assert!(lib.contains("pub use self::order::Order;"));
+    assert!(lib.contains("pub(crate) use self::shop::orders::place;"));
+    assert!(lib.contains("crate::galvan_module::shop::orders::place(2)"));
+}
+
 #[test]
 fn rejects_double_underscore_identifiers() {
     assert!(transpile(vec![Source::from_string("fn bad__name() {}")]).is_err());
@@ -194,3 +228,199 @@ fn rejects_invalid_main_function_signatures() {
     assert!(transpile(vec![Source::from_string("main {}")]).is_err());
     assert!(transpile(vec![Source::from_string("fn main() {} cmd main() {}")]).is_err());
 }
+
+#[test]
+fn returns_all_errors_as_diagnostics() {
+    let code = "type HttpServer {}\ntype HTTPServer {}\nfn pick() -> Int {\n    let counter = 1\n    countr\n}";
+    let Err(TranspileError::Diagnostics(errors)) = transpile(vec![Source::from_string(code)])
+    else {
+        panic!("expected diagnostics");
+    };
+    let codes = errors
+        .errors()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect::<Vec<_>>();
+    assert!(codes.contains(&"G0027"), "{codes:?}");
+    assert!(codes.contains(&"G0002"), "{codes:?}");
+
+    let collision = errors
+        .errors()
+        .find(|diagnostic| diagnostic.code == Some("G0027"))
+        .unwrap();
+    assert_eq!(collision.span.as_ref().unwrap().start_point.row, 1);
+    assert_eq!(collision.related[0].span.start_point.row, 0);
+}
+
+#[test]
+fn maps_generated_code_back_to_galvan_source() {
+    let code = "fn greet() {\n    print \"Hello\"\n}";
+    let outputs = transpile(vec![Source::in_module(ModulePath::root(), code)]).unwrap();
+    let lib = outputs
+        .iter()
+        .find(|output| output.file_name.as_ref() == galvan_module!("rs"))
+        .unwrap();
+
+    let generated = lib.content.find("\"Hello\"").unwrap();
+    let mapping = lib
+        .source_map
+        .lookup(generated..generated + "\"Hello\"".len())
+        .expect("generated literal should be mapped");
+    assert_eq!(mapping.file, "main.galvan");
+    assert_eq!(mapping.span.start.row, 1);
+    assert!(code[mapping.span.range.0..mapping.span.range.1].contains("Hello"));
+    assert!(!lib.content.contains('\u{E000}'));
+}
+
+#[test]
+fn rewrites_rustc_diagnostics_to_galvan_source() {
+    let code = "fn greet() {\n    print \"Hello\"\n}";
+    let outputs = transpile(vec![Source::in_module(ModulePath::root(), code)]).unwrap();
+    let lib = outputs
+        .iter()
+        .find(|output| output.file_name.as_ref() == galvan_module!("rs"))
+        .unwrap();
+    let generated = lib.content.find("\"Hello\"").unwrap();
+
+    let mut maps = SourceMaps::new();
+    maps.insert("out/galvan_module.rs", lib.source_map.clone());
+    maps.add_source("main.galvan", code);
+    let diagnostic = serde_json::json!({
+        "$message_type": "diagnostic",
+        "message": "mismatched types",
+        "code": { "code": "E0308", "explanation": null },
+        "level": "error",
+        "spans": [{
+            "file_name": "out/galvan_module.rs",
+            "byte_start": generated,
+            "byte_end": generated + 7,
+            "line_start": 1,
+            "line_end": 1,
+            "column_start": 1,
+            "column_end": 8,
+            "is_primary": true,
+            "text": [],
+            "label": "expected `i64`",
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null,
+        }],
+        "children": [],
+        "rendered": "error[E0308]: mismatched types",
+    });
+
+    let rewritten: serde_json::Value =
+        serde_json::from_str(&maps.rewrite_json(&diagnostic.to_string())).unwrap();
+    let span = &rewritten["spans"][0];
+    assert_eq!(span["file_name"], "main.galvan");
+    assert_eq!(span["line_start"], 2);
+    assert_eq!(span["text"][0]["text"], "    print \"Hello\"");
+    let rendered = rewritten["rendered"].as_str().unwrap();
+    assert!(rendered.contains("--> main.galvan:2:"), "{rendered}");
+}
+
+#[test]
+fn marker_characters_in_string_literals_do_not_break_the_source_map() {
+    let code = "fn greet() {\n    print \"\u{E000}\u{E001}\u{E002}\"\n    print \"Hello\"\n}";
+    let outputs = transpile(vec![Source::in_module(ModulePath::root(), code)]).unwrap();
+    let lib = outputs
+        .iter()
+        .find(|output| output.file_name.as_ref() == galvan_module!("rs"))
+        .unwrap();
+
+    assert!(!lib.content.contains(['\u{E000}', '\u{E001}', '\u{E002}']));
+    assert!(
+        lib.content.contains("\\u{e000}\\u{e001}\\u{e002}"),
+        "{}",
+        lib.content
+    );
+    let generated = lib.content.find("\"Hello\"").unwrap();
+    let mapping = lib
+        .source_map
+        .lookup(generated..generated + "\"Hello\"".len())
+        .expect("generated literal should be mapped");
+    assert_eq!(mapping.span.start.row, 2);
+}
+
+#[test]
+fn rewritten_columns_count_characters() {
+    let code = "fn greet() {\n    print \"Grüße\", \"Hello\"\n}";
+    let outputs = transpile(vec![Source::in_module(ModulePath::root(), code)]).unwrap();
+    let lib = outputs
+        .iter()
+        .find(|output| output.file_name.as_ref() == galvan_module!("rs"))
+        .unwrap();
+    let generated = lib.content.find("\"Hello\"").unwrap();
+
+    let mut maps = SourceMaps::new();
+    maps.insert("out/galvan_module.rs", lib.source_map.clone());
+    maps.add_source("main.galvan", code);
+    let diagnostic = serde_json::json!({
+        "$message_type": "diagnostic",
+        "message": "mismatched types",
+        "code": null,
+        "level": "error",
+        "spans": [{
+            "file_name": "out/galvan_module.rs",
+            "byte_start": generated,
+            "byte_end": generated + 7,
+            "line_start": 1,
+            "line_end": 1,
+            "column_start": 1,
+            "column_end": 8,
+            "is_primary": true,
+            "text": [],
+            "label": null,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null,
+        }],
+        "children": [],
+        "rendered": "error: mismatched types",
+    });
+
+    let rewritten: serde_json::Value =
+        serde_json::from_str(&maps.rewrite_json(&diagnostic.to_string())).unwrap();
+    let span = &rewritten["spans"][0];
+    let line = "    print \"Grüße\", \"Hello\"";
+    let column = line[..line.find("\"Hello\"").unwrap()].chars().count() + 1;
+    assert_eq!(span["line_start"], 2);
+    assert_eq!(span["column_start"], column);
+    assert_eq!(span["column_end"], column + 7);
+    assert_eq!(span["text"][0]["highlight_start"], column);
+    assert_eq!(span["text"][0]["highlight_end"], column + 7);
+}
+
+#[test]
+fn transpiles_repetition_and_slices_of_strings_and_arrays() {
+    let output = transpile_source(
+        "fn line() -> String { \"-\" ** 3 }
+         fn rows(row: [Int]) -> [Int] { row ** 2 }
+         fn prefix(text: String) -> String { text[:5] }
+         fn middle(values: [Int]) -> [Int] { values[1:3] }",
+    );
+
+    assert!(output.contains(".repeat(("), "{output}");
+    assert!(output.contains("::std::iter::repeat(("), "{output}");
+    assert!(output.contains(".chars().skip(__start).take("), "{output}");
+    // Array slices clamp their bounds the same way string slices do
+    assert!(output.contains(".min(__items.len())"), "{output}");
+    assert!(
+        output.contains(".clamp(__start, __items.len())"),
+        "{output}"
+    );
+}
+
+#[test]
+fn rejects_repetition_and_slices_of_other_types() {
+    let code =
+        "fn twice(flag: Bool) -> Bool { flag ** 2 }\nfn part(count: Int) -> Int { count[1:2] }";
+    let Err(TranspileError::Diagnostics(errors)) = transpile(vec![Source::from_string(code)])
+    else {
+        panic!("expected diagnostics");
+    };
+    let invalid_operations = errors
+        .errors()
+        .filter(|diagnostic| diagnostic.code == Some("G0016"))
+        .count();
+    assert_eq!(invalid_operations, 2);
+}
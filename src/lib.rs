@@ -20,12 +20,8 @@ macro_rules! include {
 #[macro_export]
 macro_rules! setup {
     () => {
-        let errors = ::galvan::__private::__setup_galvan();
-
-        if !errors.is_empty() {
-            // println!("cargo:warning={}", errors);
-            panic!("Galvan Transpiler Error:\n{}", errors);
-        }
+        // Errors are reported to Cargo, which then fails the build
+        ::galvan::__private::__setup_galvan();
         // TODO: How to build a rerun rule for this?
     };
 }